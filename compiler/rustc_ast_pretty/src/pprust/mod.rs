@@ -52,6 +52,18 @@ pub fn item_to_string(i: &ast::Item) -> String {
     State::new().item_to_string(i)
 }
 
+pub fn assoc_item_to_string(i: &ast::AssocItem) -> String {
+    State::to_string(|s| s.print_assoc_item(i))
+}
+
+pub fn foreign_item_to_string(i: &ast::ForeignItem) -> String {
+    State::to_string(|s| s.print_foreign_item(i))
+}
+
+pub fn stmt_to_string(stmt: &ast::Stmt) -> String {
+    State::to_string(|s| s.print_stmt(stmt))
+}
+
 pub fn path_to_string(p: &ast::Path) -> String {
     State::new().path_to_string(p)
 }
@@ -1,8 +1,11 @@
+use crate::llvm::{self, False};
 use measureme::{event_id::SEPARATOR_BYTE, EventId, StringComponent, StringId};
-use rustc_data_structures::profiling::{SelfProfiler, TimingGuard};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::profiling::{SelfProfiler, SelfProfilerRef, TimingGuard};
 use std::ffi::{c_void, CStr};
 use std::os::raw::c_char;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 fn llvm_args_to_string_id(profiler: &SelfProfiler, pass_name: &str, ir_name: &str) -> EventId {
     let pass_name = profiler.get_or_alloc_cached_string(pass_name);
@@ -19,25 +22,103 @@ fn llvm_args_to_string_id(profiler: &SelfProfiler, pass_name: &str, ir_name: &st
     EventId::from_label(profiler.alloc_string(components.as_slice()))
 }
 
+/// A pass that is currently running, for attributing its time to functions.
+struct RunningPass {
+    ir_name: String,
+    start: Instant,
+    /// Time spent in passes nested in this one, which is attributed to those passes instead.
+    nested: Duration,
+}
+
 pub struct LlvmSelfProfiler<'a> {
     profiler: Arc<SelfProfiler>,
     stack: Vec<TimingGuard<'a>>,
     llvm_pass_event_kind: StringId,
+    record_pass_events: bool,
+    /// Set when recording `llvm-functions` events: the passes currently running, and the time
+    /// spent so far in passes running on each function, keyed by symbol name.
+    running_passes: Vec<RunningPass>,
+    function_times: Option<FxHashMap<String, Duration>>,
 }
 
 impl<'a> LlvmSelfProfiler<'a> {
     pub fn new(profiler: Arc<SelfProfiler>) -> Self {
+        Self::with_options(profiler, true, false)
+    }
+
+    pub fn with_options(
+        profiler: Arc<SelfProfiler>,
+        record_pass_events: bool,
+        record_function_times: bool,
+    ) -> Self {
         let llvm_pass_event_kind = profiler.alloc_string("LLVM Pass");
-        Self { profiler, stack: Vec::default(), llvm_pass_event_kind }
+        Self {
+            profiler,
+            stack: Vec::default(),
+            llvm_pass_event_kind,
+            record_pass_events,
+            running_passes: Vec::default(),
+            function_times: record_function_times.then(FxHashMap::default),
+        }
     }
 
     fn before_pass_callback(&'a mut self, pass_name: &str, ir_name: &str) {
-        let event_id = llvm_args_to_string_id(&self.profiler, pass_name, ir_name);
+        if self.function_times.is_some() {
+            self.running_passes.push(RunningPass {
+                ir_name: ir_name.to_owned(),
+                start: Instant::now(),
+                nested: Duration::ZERO,
+            });
+        }
 
-        self.stack.push(TimingGuard::start(&self.profiler, self.llvm_pass_event_kind, event_id));
+        if self.record_pass_events {
+            let event_id = llvm_args_to_string_id(&self.profiler, pass_name, ir_name);
+            self.stack.push(TimingGuard::start(
+                &self.profiler,
+                self.llvm_pass_event_kind,
+                event_id,
+            ));
+        }
     }
+
     fn after_pass_callback(&mut self) {
-        self.stack.pop();
+        if let Some(function_times) = &mut self.function_times {
+            let pass = self.running_passes.pop().unwrap();
+            let elapsed = pass.start.elapsed();
+            if let Some(parent) = self.running_passes.last_mut() {
+                parent.nested += elapsed;
+            }
+
+            // Passes over a `LazyCallGraph::SCC` run on all of its functions at once, so split
+            // the time evenly between them. Time spent in module and loop passes can't be
+            // attributed to a single function and is not recorded.
+            let own_time = elapsed.saturating_sub(pass.nested);
+            let parentheses: &[_] = &['(', ')'];
+            let functions: Vec<_> = pass.ir_name.trim_matches(parentheses).split(", ").collect();
+            let share = own_time / functions.len() as u32;
+            for function in functions {
+                if let Some(time) = function_times.get_mut(function) {
+                    *time += share;
+                }
+            }
+        }
+
+        if self.record_pass_events {
+            self.stack.pop();
+        }
+    }
+
+    /// Starts attributing pass time to the given functions, which should be the definitions in
+    /// the module about to be optimized.
+    pub fn track_functions<'f>(&mut self, functions: impl Iterator<Item = &'f str>) {
+        if let Some(function_times) = &mut self.function_times {
+            function_times.extend(functions.map(|name| (name.to_owned(), Duration::ZERO)));
+        }
+    }
+
+    /// The time spent in passes running on each tracked function.
+    pub fn function_times(&self) -> Option<&FxHashMap<String, Duration>> {
+        self.function_times.as_ref()
     }
 }
 
@@ -56,3 +137,65 @@ fn after_pass_callback(&mut self) {
     let llvm_self_profiler = &mut *(llvm_self_profiler as *mut LlvmSelfProfiler<'_>);
     llvm_self_profiler.after_pass_callback();
 }
+
+/// Counts the instructions of every function defined in `llmod`, keyed by symbol name.
+pub fn function_instruction_counts(llmod: &llvm::Module) -> FxHashMap<String, u64> {
+    let mut counts = FxHashMap::default();
+    unsafe {
+        let mut function = llvm::LLVMGetFirstFunction(llmod);
+        while let Some(llfn) = function {
+            if llvm::LLVMIsDeclaration(llfn) == False {
+                let mut count = 0;
+                let mut block = Some(llvm::LLVMGetFirstBasicBlock(llfn));
+                while let Some(llbb) = block {
+                    let mut instruction = llvm::LLVMGetFirstInstruction(llbb);
+                    while let Some(inst) = instruction {
+                        count += 1;
+                        instruction = llvm::LLVMGetNextInstruction(inst);
+                    }
+                    block = llvm::LLVMGetNextBasicBlock(llbb);
+                }
+                let name = String::from_utf8_lossy(llvm::get_value_name(llfn)).into_owned();
+                counts.insert(name, count);
+            }
+            function = llvm::LLVMGetNextFunction(llfn);
+        }
+    }
+    counts
+}
+
+/// Records the `llvm-functions` self-profile events for the functions of one module: their
+/// instruction counts before and after optimization, the time spent optimizing them, and
+/// that time as a share (in parts per million) of the optimization time of the whole module.
+///
+/// Functions are identified by their symbol name, which is unique to the `Instance` they were
+/// generated for, and also labelled with its demangled form to make them easier to read.
+pub fn record_function_stats(
+    prof: &SelfProfilerRef,
+    cgu_name: &str,
+    instructions_before: &FxHashMap<String, u64>,
+    instructions_after: &FxHashMap<String, u64>,
+    function_times: Option<&FxHashMap<String, Duration>>,
+    module_time: Duration,
+) {
+    for (symbol_name, &before) in instructions_before {
+        let demangled = format!("{:#}", rustc_demangle::demangle(symbol_name));
+        let record = |stat_kind, value| {
+            prof.llvm_function_stat(stat_kind, &symbol_name[..], &demangled[..], cgu_name, value)
+        };
+        record("llvm_function_instructions_pre_opt", before);
+        // Functions that were inlined everywhere and then removed have no instructions left.
+        let after = instructions_after.get(symbol_name).copied().unwrap_or(0);
+        record("llvm_function_instructions_post_opt", after);
+
+        if let Some(&time) = function_times.and_then(|times| times.get(symbol_name)) {
+            record("llvm_function_opt_time_ns", time.as_nanos() as u64);
+            let share = if module_time.is_zero() {
+                0
+            } else {
+                (time.as_nanos() * 1_000_000 / module_time.as_nanos()) as u64
+            };
+            record("llvm_function_opt_time_share_ppm", share);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::back::lto::ThinBuffer;
 use crate::back::profiling::{
-    selfprofile_after_pass_callback, selfprofile_before_pass_callback, LlvmSelfProfiler,
+    function_instruction_counts, record_function_stats, selfprofile_after_pass_callback,
+    selfprofile_before_pass_callback, LlvmSelfProfiler,
 };
 use crate::base;
 use crate::common;
@@ -38,6 +39,7 @@
 use std::slice;
 use std::str;
 use std::sync::Arc;
+use std::time::Instant;
 
 pub fn llvm_err(handler: &rustc_errors::Handler, msg: &str) -> FatalError {
     match llvm::last_error() {
@@ -203,7 +205,8 @@ pub fn target_machine_factory(
     let abi = SmallCStr::new(&sess.target.llvm_abiname);
     let trap_unreachable =
         sess.opts.unstable_opts.trap_unreachable.unwrap_or(sess.target.trap_unreachable);
-    let emit_stack_size_section = sess.opts.unstable_opts.emit_stack_sizes;
+    let emit_stack_size_section =
+        sess.opts.unstable_opts.emit_stack_sizes || sess.analyze_stack_usage();
 
     let asm_comments = sess.asm_comments();
     let relax_elf_relocations =
@@ -454,8 +457,23 @@ pub(crate) unsafe fn optimize_with_new_llvm_pass_manager(
         None
     };
 
-    let mut llvm_profiler = if cgcx.prof.llvm_recording_enabled() {
-        Some(LlvmSelfProfiler::new(cgcx.prof.get_self_profiler().unwrap()))
+    let record_function_stats = cgcx.prof.llvm_function_recording_enabled();
+    let mut llvm_profiler = if cgcx.prof.llvm_recording_enabled() || record_function_stats {
+        Some(LlvmSelfProfiler::with_options(
+            cgcx.prof.get_self_profiler().unwrap(),
+            cgcx.prof.llvm_recording_enabled(),
+            record_function_stats,
+        ))
+    } else {
+        None
+    };
+
+    let instructions_before = if record_function_stats {
+        let counts = function_instruction_counts(module.module_llvm.llmod());
+        if let Some(llvm_profiler) = &mut llvm_profiler {
+            llvm_profiler.track_functions(counts.keys().map(|name| &name[..]));
+        }
+        Some(counts)
     } else {
         None
     };
@@ -470,6 +488,7 @@ pub(crate) unsafe fn optimize_with_new_llvm_pass_manager(
     // FIXME: NewPM doesn't provide a facility to pass custom InlineParams.
     // We would have to add upstream support for this first, before we can support
     // config.inline_threshold and our more aggressive default thresholds.
+    let start = Instant::now();
     let result = llvm::LLVMRustOptimizeWithNewPassManager(
         module.module_llvm.llmod(),
         &*module.module_llvm.tm,
@@ -499,6 +518,20 @@ pub(crate) unsafe fn optimize_with_new_llvm_pass_manager(
         llvm_plugins.as_ptr().cast(),
         llvm_plugins.len(),
     );
+    let module_time = start.elapsed();
+
+    if let Some(instructions_before) = instructions_before {
+        let instructions_after = function_instruction_counts(module.module_llvm.llmod());
+        record_function_stats(
+            &cgcx.prof,
+            &module.name,
+            &instructions_before,
+            &instructions_after,
+            llvm_profiler.as_ref().and_then(|p| p.function_times()),
+            module_time,
+        );
+    }
+
     result.into_result().map_err(|()| llvm_err(diag_handler, "failed to run LLVM passes"))
 }
 
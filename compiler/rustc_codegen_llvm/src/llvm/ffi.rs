@@ -1198,6 +1198,8 @@ pub fn LLVMCreateStringAttribute(
     pub fn LLVMRustCreateAllocKindAttr(C: &Context, size_arg: u64) -> &Attribute;
 
     // Operations on functions
+    pub fn LLVMGetFirstFunction(M: &Module) -> Option<&Value>;
+    pub fn LLVMGetNextFunction(Fn: &Value) -> Option<&Value>;
     pub fn LLVMRustGetOrInsertFunction<'a>(
         M: &'a Module,
         Name: *const c_char,
@@ -1219,6 +1221,7 @@ pub fn LLVMRustAddFunctionAttributes<'a>(
 
     // Operations on basic blocks
     pub fn LLVMGetBasicBlockParent(BB: &BasicBlock) -> &Value;
+    pub fn LLVMGetNextBasicBlock(BB: &BasicBlock) -> Option<&BasicBlock>;
     pub fn LLVMAppendBasicBlockInContext<'a>(
         C: &'a Context,
         Fn: &'a Value,
@@ -1228,6 +1231,8 @@ pub fn LLVMAppendBasicBlockInContext<'a>(
     // Operations on instructions
     pub fn LLVMIsAInstruction(Val: &Value) -> Option<&Value>;
     pub fn LLVMGetFirstBasicBlock(Fn: &Value) -> &BasicBlock;
+    pub fn LLVMGetFirstInstruction(BB: &BasicBlock) -> Option<&Value>;
+    pub fn LLVMGetNextInstruction(Inst: &Value) -> Option<&Value>;
 
     // Operations on call sites
     pub fn LLVMSetInstructionCallConv(Instr: &Value, CC: c_uint);
@@ -0,0 +1,394 @@
+//! The stack usage analysis of `-Z print-stack-usage` and `-Z stack-usage-limit`.
+//!
+//! LLVM records the frame size of every function it emits in the `.stack_sizes` section of ELF
+//! object files. After codegen, the analysis reads these sections and propagates the frame sizes
+//! through the call graph of the crate, which is built from the MIR of the crate's mono items
+//! while `tcx` is still around. The worst-case stack usage of a function is its own frame plus
+//! the largest stack usage of the functions it calls.
+//!
+//! The result is only an upper bound if every call can be followed: recursion and calls through
+//! function pointers or trait objects make the stack usage unbounded. Functions of upstream
+//! crates are called by symbol name, but their frame sizes and callees aren't known, so the stack
+//! usage of their callers is incomplete. Calls LLVM inserts itself, like `memcpy`, aren't part of
+//! the call graph either.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stack::ensure_sufficient_stack;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::Session;
+use rustc_span::Span;
+
+use object::{Object, ObjectSection, ObjectSymbol, RelocationTarget, SymbolKind};
+
+use std::fs;
+use std::path::Path;
+
+use crate::{CompiledModule, ModuleKind};
+
+/// The call graph of the crate, without references to `tcx`, so that it can be analyzed once the
+/// codegen worker threads are done.
+pub struct StackUsageGraph {
+    functions: Vec<Function>,
+    by_symbol_name: FxHashMap<String, usize>,
+}
+
+struct Function {
+    name: String,
+    span: Span,
+    is_local: bool,
+    is_entry_point: bool,
+    callees: Vec<usize>,
+    indirect_call: Option<Span>,
+}
+
+/// The worst-case stack usage of a function and the calls it makes.
+#[derive(Clone, Default)]
+struct Usage {
+    /// The stack usage in bytes, leaving out calls that can't be followed.
+    bytes: u64,
+    /// The callee with the largest stack usage.
+    worst_callee: Option<usize>,
+    /// A function in a cycle of calls that is reachable from this function.
+    recursion: Option<usize>,
+    /// A function that is reachable from this function and makes an indirect call.
+    indirect_call: Option<usize>,
+    /// A function that is reachable from this function and whose stack usage isn't known.
+    unknown: Option<usize>,
+}
+
+impl Usage {
+    fn is_unbounded(&self) -> bool {
+        self.recursion.is_some() || self.indirect_call.is_some()
+    }
+}
+
+impl StackUsageGraph {
+    pub fn new(tcx: TyCtxt<'_>) -> StackUsageGraph {
+        let call_graph = tcx.call_graph(());
+        let mut symbol_names: Vec<_> = call_graph.functions.keys().copied().collect();
+        symbol_names.sort_unstable();
+        let by_symbol_name: FxHashMap<_, _> = symbol_names
+            .iter()
+            .enumerate()
+            .map(|(index, symbol_name)| (symbol_name.name.to_string(), index))
+            .collect();
+        let functions = symbol_names
+            .iter()
+            .map(|symbol_name| {
+                let function = &call_graph.functions[symbol_name];
+                Function {
+                    name: with_no_trimmed_paths!(function.instance.to_string()),
+                    span: tcx.def_span(function.instance.def_id()),
+                    is_local: function.is_local,
+                    is_entry_point: function.is_entry_point,
+                    callees: function
+                        .callees
+                        .iter()
+                        .map(|callee| by_symbol_name[callee.name])
+                        .collect(),
+                    indirect_call: function.indirect_call,
+                }
+            })
+            .collect();
+        StackUsageGraph { functions, by_symbol_name }
+    }
+
+    /// Reads the frame sizes from the object files of `modules`, and reports the stack usage of
+    /// the crate's entry points.
+    pub fn analyze(&self, sess: &Session, modules: &[CompiledModule]) {
+        let mut frame_sizes = vec![None; self.functions.len()];
+        let mut found_frame_sizes = false;
+        for module in modules {
+            let (ModuleKind::Regular, Some(object)) = (module.kind, &module.object) else {
+                continue;
+            };
+            let result = read_frame_sizes(object, |symbol_name, size| {
+                found_frame_sizes = true;
+                // ThinLTO renames the local symbols it promotes to global ones.
+                let symbol_name = match symbol_name.find(".llvm.") {
+                    Some(index) => &symbol_name[..index],
+                    None => symbol_name,
+                };
+                if let Some(&index) = self.by_symbol_name.get(symbol_name) {
+                    // Functions that are instantiated in several codegen units can have different
+                    // frame sizes in each of them.
+                    let frame_size: &mut Option<u64> = &mut frame_sizes[index];
+                    *frame_size = Some(frame_size.unwrap_or(0).max(size));
+                }
+            });
+            if let Err(err) = result {
+                sess.warn(&format!(
+                    "the stack usage of the crate can't be analyzed, because the frame sizes \
+                    couldn't be read from `{}`: {}",
+                    object.display(),
+                    err
+                ));
+                return;
+            }
+        }
+        if !found_frame_sizes {
+            sess.warn(
+                "the stack usage of the crate can't be analyzed, because LLVM didn't report \
+                the frame sizes of its functions; only ELF targets support this",
+            );
+            return;
+        }
+
+        let mut analysis = Analysis {
+            graph: self,
+            frame_sizes: &frame_sizes,
+            usages: vec![None; self.functions.len()],
+            on_stack: vec![false; self.functions.len()],
+        };
+        let mut entry_points: Vec<_> = (0..self.functions.len())
+            .filter(|&index| self.functions[index].is_entry_point)
+            .map(|index| (index, analysis.usage(index)))
+            .collect();
+        entry_points.sort_by(|(index1, usage1), (index2, usage2)| {
+            usage2
+                .bytes
+                .cmp(&usage1.bytes)
+                .then_with(|| self.functions[*index1].name.cmp(&self.functions[*index2].name))
+        });
+
+        if sess.opts.unstable_opts.print_stack_usage {
+            for (index, usage) in &entry_points {
+                self.print_usage(*index, usage, &analysis);
+            }
+        }
+        if let Some(limit) = sess.opts.unstable_opts.stack_usage_limit {
+            for (index, usage) in &entry_points {
+                self.check_limit(sess, *index, usage, &analysis, limit as u64);
+            }
+        }
+    }
+
+    fn print_usage(&self, index: usize, usage: &Usage, analysis: &Analysis<'_>) {
+        let function = &self.functions[index];
+        let qualifier = if usage.is_unbounded() {
+            "unbounded, at least "
+        } else if usage.unknown.is_some() {
+            "at least "
+        } else {
+            ""
+        };
+        println!("print-stack-usage: `{}`: {}{} bytes", function.name, qualifier, usage.bytes);
+        for callee in analysis.worst_path(index) {
+            println!(
+                "print-stack-usage:     `{}`: {} bytes",
+                self.functions[callee].name,
+                analysis.frame_sizes[callee].unwrap_or(0)
+            );
+        }
+        if let Some(recursion) = usage.recursion {
+            println!(
+                "print-stack-usage:     unbounded: `{}` is recursive",
+                self.functions[recursion].name
+            );
+        }
+        if let Some(indirect_call) = usage.indirect_call {
+            println!(
+                "print-stack-usage:     unbounded: `{}` calls a function pointer or trait object",
+                self.functions[indirect_call].name
+            );
+        }
+        if let Some(unknown) = usage.unknown {
+            println!(
+                "print-stack-usage:     incomplete: the stack usage of `{}` isn't known",
+                self.functions[unknown].name
+            );
+        }
+    }
+
+    fn check_limit(
+        &self,
+        sess: &Session,
+        index: usize,
+        usage: &Usage,
+        analysis: &Analysis<'_>,
+        limit: u64,
+    ) {
+        let function = &self.functions[index];
+        if usage.bytes > limit {
+            let path: Vec<_> = analysis
+                .worst_path(index)
+                .map(|callee| format!("`{}`", self.functions[callee].name))
+                .collect();
+            sess.struct_span_err(
+                function.span,
+                &format!(
+                    "the worst-case stack usage of `{}` is {} bytes, which exceeds the limit of \
+                    {} bytes",
+                    function.name, usage.bytes, limit
+                ),
+            )
+            .note(&format!("the largest stack usage is through {}", path.join(" -> ")))
+            .emit();
+        } else if let Some(recursion) = usage.recursion {
+            sess.struct_span_warn(
+                function.span,
+                &format!(
+                    "the stack usage of `{}` can't be checked against the limit",
+                    function.name
+                ),
+            )
+            .span_note(
+                self.functions[recursion].span,
+                &format!("`{}` is recursive", self.functions[recursion].name),
+            )
+            .emit();
+        } else if let Some(indirect_call) = usage.indirect_call {
+            let caller = &self.functions[indirect_call];
+            let mut warning = sess.struct_span_warn(
+                function.span,
+                &format!(
+                    "the stack usage of `{}` can't be checked against the limit",
+                    function.name
+                ),
+            );
+            if let Some(span) = caller.indirect_call {
+                warning.span_note(
+                    span,
+                    &format!("`{}` calls a function pointer or trait object here", caller.name),
+                );
+            }
+            warning.emit();
+        }
+    }
+}
+
+struct Analysis<'a> {
+    graph: &'a StackUsageGraph,
+    frame_sizes: &'a [Option<u64>],
+    usages: Vec<Option<Usage>>,
+    on_stack: Vec<bool>,
+}
+
+impl Analysis<'_> {
+    fn usage(&mut self, index: usize) -> Usage {
+        if let Some(usage) = &self.usages[index] {
+            return usage.clone();
+        }
+        if self.on_stack[index] {
+            // The function is part of a cycle, whose first call is already being analyzed.
+            return Usage { recursion: Some(index), ..Usage::default() };
+        }
+        self.on_stack[index] = true;
+
+        let function = &self.graph.functions[index];
+        let mut usage = Usage {
+            indirect_call: function.indirect_call.map(|_| index),
+            // Local functions without a frame size have been inlined into all of their callers.
+            unknown: (!function.is_local).then_some(index),
+            ..Usage::default()
+        };
+        for &callee in &function.callees {
+            let callee_usage = ensure_sufficient_stack(|| self.usage(callee));
+            if usage.worst_callee.is_none() || callee_usage.bytes > usage.bytes {
+                usage.bytes = callee_usage.bytes;
+                usage.worst_callee = Some(callee);
+            }
+            usage.recursion = usage.recursion.or(callee_usage.recursion);
+            usage.indirect_call = usage.indirect_call.or(callee_usage.indirect_call);
+            usage.unknown = usage.unknown.or(callee_usage.unknown);
+        }
+        usage.bytes += self.frame_sizes[index].unwrap_or(0);
+
+        self.on_stack[index] = false;
+        self.usages[index] = Some(usage.clone());
+        usage
+    }
+
+    /// The chain of calls with the largest stack usage, starting with the function itself.
+    fn worst_path(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut visited = vec![false; self.graph.functions.len()];
+        std::iter::successors(Some(index), move |&index| {
+            visited[index] = true;
+            let callee = self.usages[index].as_ref()?.worst_callee?;
+            // Stop at the first recursive call.
+            (!visited[callee]).then_some(callee)
+        })
+    }
+}
+
+/// Calls `f` with the symbol name and the frame size of every function in the `.stack_sizes`
+/// sections of the object file at `path`. Each entry of those sections is the address of a
+/// function, which is relocated against the function's symbol, followed by its frame size as
+/// ULEB128.
+fn read_frame_sizes(path: &Path, mut f: impl FnMut(&str, u64)) -> Result<(), String> {
+    let data = fs::read(path).map_err(|err| err.to_string())?;
+    let file = object::File::parse(&*data).map_err(|err| err.to_string())?;
+    let address_size = if file.is_64() { 8 } else { 4 };
+    for section in file.sections() {
+        if section.name() != Ok(".stack_sizes") {
+            continue;
+        }
+        let section_data = section.data().map_err(|err| err.to_string())?;
+        let relocations: FxHashMap<_, _> = section.relocations().collect();
+        let mut position = 0;
+        while position + address_size < section_data.len() {
+            let address_position = position;
+            position += address_size;
+            let frame_size = read_uleb128(section_data, &mut position)
+                .ok_or_else(|| "truncated or malformed `.stack_sizes` section".to_string())?;
+
+            let Some(relocation) = relocations.get(&(address_position as u64)) else { continue };
+            let RelocationTarget::Symbol(symbol_index) = relocation.target() else { continue };
+            let symbol = file.symbol_by_index(symbol_index).map_err(|err| err.to_string())?;
+            if symbol.kind() != SymbolKind::Section {
+                f(symbol.name().map_err(|err| err.to_string())?, frame_size);
+                continue;
+            }
+
+            // Relocations against local functions can refer to the section of the function
+            // instead, with the function's offset as the addend.
+            let addend = if relocation.has_implicit_addend() {
+                let bytes = &section_data[address_position..address_position + address_size];
+                read_address(&file, bytes)
+            } else {
+                relocation.addend() as u64
+            };
+            let address = symbol.address().wrapping_add(addend);
+            let function = file.symbols().find(|function| {
+                function.kind() == SymbolKind::Text
+                    && function.section_index() == symbol.section_index()
+                    && function.address() == address
+            });
+            if let Some(function) = function {
+                f(function.name().map_err(|err| err.to_string())?, frame_size);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads a ULEB128 value like `rustc_serialize::leb128`, but returns `None` instead of
+/// panicking if the data ends in the middle of it or if it doesn't fit in a `u64`.
+fn read_uleb128(data: &[u8], position: &mut usize) -> Option<u64> {
+    let mut result = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(*position)?;
+        *position += 1;
+        if shift >= 64 || (shift == 63 && byte & 0x7e != 0) {
+            return None;
+        }
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+        shift += 7;
+    }
+}
+
+fn read_address(file: &object::File<'_>, bytes: &[u8]) -> u64 {
+    let mut buf = [0; 8];
+    if file.is_little_endian() {
+        buf[..bytes.len()].copy_from_slice(bytes);
+        u64::from_le_bytes(buf)
+    } else {
+        buf[8 - bytes.len()..].copy_from_slice(bytes);
+        u64::from_be_bytes(buf)
+    }
+}
@@ -1,5 +1,6 @@
 use super::link::{self, ensure_removed};
 use super::lto::{self, SerializedModule};
+use super::stack_usage::StackUsageGraph;
 use super::symbol_export::symbol_name_for_instance_in_crate;
 
 use crate::{
@@ -484,6 +485,7 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
             phantom: PhantomData,
         },
         output_filenames: tcx.output_filenames(()).clone(),
+        stack_usage_graph: None,
     }
 }
 
@@ -1869,6 +1871,8 @@ pub struct OngoingCodegen<B: ExtraBackendMethods> {
     pub shared_emitter_main: SharedEmitterMain,
     pub output_filenames: Arc<OutputFilenames>,
     pub coordinator: Coordinator<B>,
+    /// The call graph for `-Z print-stack-usage`, which is analyzed once the object files exist.
+    pub stack_usage_graph: Option<StackUsageGraph>,
 }
 
 impl<B: ExtraBackendMethods> OngoingCodegen<B> {
@@ -1891,6 +1895,12 @@ pub fn join(self, sess: &Session) -> (CodegenResults, FxHashMap<WorkProductId, W
 
         sess.abort_if_errors();
 
+        if let Some(stack_usage_graph) = &self.stack_usage_graph {
+            sess.time("analyze_stack_usage", || {
+                stack_usage_graph.analyze(sess, &compiled_modules.modules)
+            });
+        }
+
         let work_products =
             copy_all_cgu_workproducts_to_incr_comp_cache_dir(sess, &compiled_modules);
         produce_final_output_artifacts(sess, &compiled_modules, &self.output_filenames);
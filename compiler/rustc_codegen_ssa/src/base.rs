@@ -1,4 +1,5 @@
 use crate::back::metadata::create_compressed_metadata_file;
+use crate::back::stack_usage::StackUsageGraph;
 use crate::back::write::{
     compute_per_cgu_lto_type, start_async_codegen, submit_codegened_module_to_llvm,
     submit_post_lto_module_to_llvm, submit_pre_lto_module_to_llvm, ComputedLtoType, OngoingCodegen,
@@ -574,7 +575,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
         None
     };
 
-    let ongoing_codegen = start_async_codegen(
+    let mut ongoing_codegen = start_async_codegen(
         backend.clone(),
         tcx,
         target_cpu,
@@ -734,6 +735,11 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
         };
     }
 
+    if tcx.sess.analyze_stack_usage() {
+        ongoing_codegen.stack_usage_graph =
+            Some(tcx.sess.time("build_call_graph", || StackUsageGraph::new(tcx)));
+    }
+
     ongoing_codegen.codegen_finished(tcx);
 
     // Since the main thread is sometimes blocked during codegen, we keep track
@@ -187,6 +187,16 @@ fn struct_generic(
                 }
                 flush_last_line(last_frame, times);
             }
+            // When the step limit is hit, the labels above collapse recursive calls, which hides
+            // where the interpreter was actually spending its steps. Print every frame instead.
+            if matches!(self.error, err_exhaust!(StepLimitReached)) && self.stacktrace.len() > 1 {
+                let mut backtrace = String::from("interpreter backtrace:");
+                for frame_info in &self.stacktrace {
+                    backtrace.push_str("\n    ");
+                    backtrace.push_str(&frame_info.to_string());
+                }
+                err.note(&backtrace);
+            }
             // Let the caller attach any additional information it wants.
             decorate(err);
         };
@@ -22,6 +22,7 @@
 };
 
 use super::error::*;
+use super::profile::ConstEvalProfiler;
 
 impl<'mir, 'tcx> InterpCx<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>> {
     /// "Intercept" a function call to a panic-related function
@@ -101,6 +102,9 @@ pub struct CompileTimeInterpreter<'mir, 'tcx> {
     /// * Pointers to allocations inside of statics can never leak outside, to a non-static global.
     /// This boolean here controls the second part.
     pub(super) can_access_statics: bool,
+
+    /// Samples the virtual call stack if `-Zconst-eval-profile` is enabled.
+    pub(super) profiler: Option<ConstEvalProfiler<'tcx>>,
 }
 
 impl<'mir, 'tcx> CompileTimeInterpreter<'mir, 'tcx> {
@@ -109,6 +113,7 @@ pub(crate) fn new(const_eval_limit: Limit, can_access_statics: bool) -> Self {
             steps_remaining: const_eval_limit.0,
             stack: Vec::new(),
             can_access_statics,
+            profiler: None,
         }
     }
 }
@@ -433,6 +438,10 @@ fn binary_ptr_op(
     }
 
     fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
+        if let Some(profiler) = &mut ecx.machine.profiler {
+            profiler.step(&ecx.machine.stack);
+        }
+
         // The step limit has already been hit in a previous call to `before_terminator`.
         if ecx.machine.steps_remaining == 0 {
             return Ok(());
@@ -15,6 +15,7 @@
 mod eval_queries;
 mod fn_queries;
 mod machine;
+mod profile;
 mod valtrees;
 
 pub use error::*;
@@ -0,0 +1,72 @@
+//! A sampling profiler for the compile-time interpreter, for `-Zconst-eval-profile`.
+//!
+//! Every `interval` steps, i.e. evaluated terminators, the profiler records the interpreter's
+//! stack of function instances and the location in the innermost frame. When an evaluation is
+//! done, its samples are added to the `CodeStats` of the session, which writes all of them as
+//! folded stacks at the end of the compilation.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::Span;
+
+use crate::interpret::{AllocId, Frame};
+
+pub(crate) struct ConstEvalProfiler<'tcx> {
+    interval: usize,
+    steps_until_sample: usize,
+    samples: FxHashMap<Sample<'tcx>, u64>,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct Sample<'tcx> {
+    instances: Vec<ty::Instance<'tcx>>,
+    location: Option<mir::Location>,
+    span: Span,
+}
+
+impl<'tcx> ConstEvalProfiler<'tcx> {
+    pub(crate) fn new(interval: usize) -> Self {
+        let interval = interval.max(1);
+        ConstEvalProfiler { interval, steps_until_sample: interval, samples: Default::default() }
+    }
+
+    pub(crate) fn step(&mut self, stack: &[Frame<'_, 'tcx, AllocId, ()>]) {
+        self.steps_until_sample -= 1;
+        if self.steps_until_sample > 0 {
+            return;
+        }
+        self.steps_until_sample = self.interval;
+
+        let Some(frame) = stack.last() else { return };
+        let sample = Sample {
+            instances: stack.iter().map(|frame| frame.instance).collect(),
+            location: frame.current_loc().ok(),
+            span: frame.current_span(),
+        };
+        *self.samples.entry(sample).or_default() += 1;
+    }
+
+    /// Adds the samples of this evaluation to the session's profile.
+    pub(crate) fn record(self, tcx: TyCtxt<'tcx>) {
+        let source_map = tcx.sess.source_map();
+        let samples = self.samples.into_iter().map(|(sample, count)| {
+            let mut stack: Vec<_> = sample
+                .instances
+                .iter()
+                .map(|instance| with_no_trimmed_paths!(instance.to_string()))
+                .collect();
+            // The innermost frame is the location in the innermost instance, so that flamegraphs
+            // show which statements of a function the interpreter spends its steps on.
+            let lo = source_map.lookup_char_pos(sample.span.lo());
+            let file = source_map.filename_for_diagnostics(&lo.file.name);
+            stack.push(match sample.location {
+                Some(location) => format!("{:?} at {}:{}", location, file, lo.line),
+                None => format!("{}:{}", file, lo.line),
+            });
+            (stack, count)
+        });
+        tcx.sess.code_stats.record_const_eval_samples(samples);
+    }
+}
@@ -112,6 +112,7 @@ struct EventFilter: u32 {
         const LLVM                = 1 << 7;
         const INCR_RESULT_HASHING = 1 << 8;
         const ARTIFACT_SIZES = 1 << 9;
+        const LLVM_FUNCTIONS = 1 << 10;
 
         const DEFAULT = Self::GENERIC_ACTIVITIES.bits |
                         Self::QUERY_PROVIDERS.bits |
@@ -140,6 +141,7 @@ struct EventFilter: u32 {
     ("llvm", EventFilter::LLVM),
     ("incr-result-hashing", EventFilter::INCR_RESULT_HASHING),
     ("artifact-sizes", EventFilter::ARTIFACT_SIZES),
+    ("llvm-functions", EventFilter::LLVM_FUNCTIONS),
 ];
 
 /// Something that uniquely identifies a query invocation.
@@ -377,6 +379,44 @@ pub fn artifact_size<A>(&self, artifact_kind: &str, artifact_name: A, size: u64)
         }))
     }
 
+    /// Record a statistic about a single function that LLVM optimized, e.g. its
+    /// instruction count or the time spent optimizing it.
+    ///
+    /// `stat_kind` names the statistic, `symbol_name` identifies the function,
+    /// `demangled_name` is a readable form of it, and `cgu_name` names the codegen
+    /// unit the function was optimized in.
+    #[inline(always)]
+    pub fn llvm_function_stat<A>(
+        &self,
+        stat_kind: &str,
+        symbol_name: A,
+        demangled_name: A,
+        cgu_name: &str,
+        value: u64,
+    ) where
+        A: Borrow<str> + Into<String>,
+    {
+        drop(self.exec(EventFilter::LLVM_FUNCTIONS, |profiler| {
+            let builder = EventIdBuilder::new(&profiler.profiler);
+            let event_label = profiler.get_or_alloc_cached_string(stat_kind);
+            let symbol_name = profiler.get_or_alloc_cached_string(symbol_name);
+            let demangled_name = profiler.get_or_alloc_cached_string(demangled_name);
+            let cgu_name = profiler.get_or_alloc_cached_string(cgu_name);
+            let event_id =
+                builder.from_label_and_args(event_label, &[symbol_name, demangled_name, cgu_name]);
+            let thread_id = get_thread_id();
+
+            profiler.profiler.record_integer_event(
+                profiler.llvm_function_stat_event_kind,
+                event_id,
+                thread_id,
+                value,
+            );
+
+            TimingGuard::none()
+        }))
+    }
+
     #[inline(always)]
     pub fn generic_activity_with_args(
         &self,
@@ -499,6 +539,11 @@ pub fn enabled(&self) -> bool {
     pub fn llvm_recording_enabled(&self) -> bool {
         self.event_filter_mask.contains(EventFilter::LLVM)
     }
+
+    #[inline]
+    pub fn llvm_function_recording_enabled(&self) -> bool {
+        self.event_filter_mask.contains(EventFilter::LLVM_FUNCTIONS)
+    }
     #[inline]
     pub fn get_self_profiler(&self) -> Option<Arc<SelfProfiler>> {
         self.profiler.clone()
@@ -545,6 +590,7 @@ pub struct SelfProfiler {
     query_blocked_event_kind: StringId,
     query_cache_hit_event_kind: StringId,
     artifact_size_event_kind: StringId,
+    llvm_function_stat_event_kind: StringId,
 }
 
 impl SelfProfiler {
@@ -574,6 +620,7 @@ pub fn new(
         let query_blocked_event_kind = profiler.alloc_string("QueryBlocked");
         let query_cache_hit_event_kind = profiler.alloc_string("QueryCacheHit");
         let artifact_size_event_kind = profiler.alloc_string("ArtifactSize");
+        let llvm_function_stat_event_kind = profiler.alloc_string("LlvmFunctionStat");
 
         let mut event_filter_mask = EventFilter::empty();
 
@@ -619,6 +666,7 @@ pub fn new(
             query_blocked_event_kind,
             query_cache_hit_event_kind,
             artifact_size_event_kind,
+            llvm_function_stat_event_kind,
         })
     }
 
@@ -0,0 +1,269 @@
+//! Applies the machine-applicable suggestions of the diagnostics emitted during compilation to
+//! the source files, for `-Z apply-suggestions`.
+//!
+//! Suggestions are applied in the order their diagnostics were emitted. A suggestion whose
+//! edits overlap with those of a suggestion that was already selected is reported as a conflict
+//! and not applied at all, so that the result never contains half of a suggestion.
+
+use rustc_errors::{pluralize, MachineApplicableSuggestion, SuggestionEdit};
+use rustc_session::config::ApplySuggestions;
+use rustc_session::Session;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The number of unchanged lines shown around each change in the diff of a dry run.
+const CONTEXT_LINES: usize = 3;
+
+pub(crate) fn apply_suggestions(sess: &Session, mode: ApplySuggestions) {
+    let suggestions = sess.diagnostic().take_machine_applicable_suggestions();
+    let (applied, edits_by_file) = select_edits(sess, suggestions);
+
+    for (path, edits) in &edits_by_file {
+        let old = match fs::read_to_string(path) {
+            Ok(old) => old,
+            Err(err) => {
+                sess.err(&format!("failed to read `{}`: {}", path.display(), err));
+                continue;
+            }
+        };
+        let Some(new) = apply_edits(&old, edits) else {
+            sess.err(&format!(
+                "failed to apply suggestions to `{}`: the file changed during compilation",
+                path.display()
+            ));
+            continue;
+        };
+        match mode {
+            ApplySuggestions::Write => {
+                if let Err(err) = fs::write(path, new) {
+                    sess.err(&format!("failed to write `{}`: {}", path.display(), err));
+                }
+            }
+            ApplySuggestions::DryRun => print!("{}", unified_diff(path, &old, edits)),
+        }
+    }
+
+    if mode == ApplySuggestions::Write && applied > 0 {
+        sess.note_without_error(&format!(
+            "applied {} suggestion{} to {} file{}",
+            applied,
+            pluralize!(applied),
+            edits_by_file.len(),
+            pluralize!(edits_by_file.len())
+        ));
+    }
+}
+
+/// Selects the suggestions to apply, and returns how many there are together with their edits
+/// grouped by file and sorted by position.
+fn select_edits(
+    sess: &Session,
+    suggestions: Vec<MachineApplicableSuggestion>,
+) -> (usize, BTreeMap<PathBuf, Vec<SuggestionEdit>>) {
+    let mut applied = 0;
+    let mut selected: BTreeMap<PathBuf, Vec<SuggestionEdit>> = BTreeMap::new();
+    for suggestion in suggestions {
+        // Edits that were already selected, e.g. because a diagnostic was emitted twice, are
+        // not conflicts.
+        let new_edits: Vec<_> = suggestion
+            .edits
+            .iter()
+            .filter(|edit| !selected.get(&edit.path).map_or(false, |edits| edits.contains(edit)))
+            .collect();
+        if new_edits.is_empty() {
+            continue;
+        }
+
+        let conflicts = new_edits.iter().enumerate().any(|(i, edit)| {
+            let selected = selected.get(&edit.path).into_iter().flatten();
+            selected.chain(new_edits[..i].iter().copied()).any(|other| overlaps(edit, other))
+        });
+        if conflicts {
+            sess.struct_span_warn(
+                suggestion.span,
+                &format!(
+                    "not applying suggestion `{}` because it conflicts with another suggestion",
+                    suggestion.msg
+                ),
+            )
+            .emit();
+            continue;
+        }
+
+        applied += 1;
+        for edit in new_edits {
+            selected.entry(edit.path.clone()).or_default().push(edit.clone());
+        }
+    }
+
+    for edits in selected.values_mut() {
+        edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+    }
+    (applied, selected)
+}
+
+/// Whether two edits can't both be applied. This includes insertions at the same position, as
+/// the order of the inserted code would be arbitrary.
+fn overlaps(a: &SuggestionEdit, b: &SuggestionEdit) -> bool {
+    a.path == b.path
+        && (a.range.start == b.range.start
+            || (a.range.start < b.range.end && b.range.start < a.range.end))
+}
+
+/// Applies non-overlapping `edits`, sorted by position, to `old`. Returns `None` if they don't
+/// fit the text.
+fn apply_edits(old: &str, edits: &[SuggestionEdit]) -> Option<String> {
+    let mut new = String::with_capacity(old.len());
+    let mut copied = 0;
+    for edit in edits {
+        if edit.range.start < copied
+            || !old.is_char_boundary(edit.range.start)
+            || !old.is_char_boundary(edit.range.end)
+        {
+            return None;
+        }
+        new.push_str(&old[copied..edit.range.start]);
+        new.push_str(&edit.replacement);
+        copied = edit.range.end;
+    }
+    new.push_str(&old[copied..]);
+    Some(new)
+}
+
+/// A run of old lines replaced by new ones.
+struct Change {
+    /// The index of the first old line.
+    start: usize,
+    old_lines: Vec<String>,
+    new_lines: Vec<String>,
+}
+
+impl Change {
+    fn old_end(&self) -> usize {
+        self.start + self.old_lines.len()
+    }
+}
+
+/// Renders the effect of `edits` on `old` as a unified diff.
+fn unified_diff(path: &Path, old: &str, edits: &[SuggestionEdit]) -> String {
+    let lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let mut line_starts: Vec<usize> = lines
+        .iter()
+        .scan(0, |start, line| {
+            let line_start = *start;
+            *start += line.len();
+            Some(line_start)
+        })
+        .collect();
+    line_starts.push(old.len());
+    let line_of = |offset: usize| match line_starts.binary_search(&offset) {
+        Ok(line) => line,
+        Err(line) => line - 1,
+    };
+
+    // Group the edits by the lines they touch.
+    let mut groups: Vec<(usize, usize, Vec<&SuggestionEdit>)> = Vec::new();
+    for edit in edits {
+        let first = line_of(edit.range.start);
+        let last =
+            if edit.range.end > edit.range.start { line_of(edit.range.end - 1) } else { first };
+        match groups.last_mut() {
+            Some((_, group_last, group_edits)) if first <= *group_last => {
+                *group_last = (*group_last).max(last);
+                group_edits.push(edit);
+            }
+            _ => groups.push((first, last, vec![edit])),
+        }
+    }
+
+    let changes: Vec<Change> = groups
+        .into_iter()
+        .map(|(first, last, group_edits)| {
+            // An insertion at the very end of the file is on a line past the last one.
+            let text_start = line_starts[first.min(lines.len())];
+            let text_end = line_starts[(last + 1).min(lines.len())];
+            let old_text = &old[text_start..text_end];
+            let mut new_text = String::new();
+            let mut copied = text_start;
+            for edit in group_edits {
+                new_text.push_str(&old[copied..edit.range.start]);
+                new_text.push_str(&edit.replacement);
+                copied = edit.range.end;
+            }
+            new_text.push_str(&old[copied..text_end]);
+            Change {
+                start: first,
+                old_lines: old_text.split_inclusive('\n').map(str::to_owned).collect(),
+                new_lines: new_text.split_inclusive('\n').map(str::to_owned).collect(),
+            }
+        })
+        .collect();
+
+    let mut diff = format!("--- {}\n+++ {}\n", path.display(), path.display());
+    // The number of lines added so far, minus the number of lines removed.
+    let mut offset: isize = 0;
+    let mut changes = changes.iter().peekable();
+    while let Some(first_change) = changes.next() {
+        // Changes that are close enough to share context lines go into the same hunk.
+        let mut hunk = vec![first_change];
+        while let Some(next) =
+            changes.next_if(|next| next.start <= hunk.last().unwrap().old_end() + 2 * CONTEXT_LINES)
+        {
+            hunk.push(next);
+        }
+
+        let start = first_change.start.saturating_sub(CONTEXT_LINES);
+        let end = (hunk.last().unwrap().old_end() + CONTEXT_LINES).min(lines.len());
+        let old_count = end - start;
+        let hunk_offset: isize = hunk
+            .iter()
+            .map(|change| change.new_lines.len() as isize - change.old_lines.len() as isize)
+            .sum();
+        let new_count = (old_count as isize + hunk_offset) as usize;
+        let new_start = (start as isize + offset) as usize;
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(start, old_count),
+            hunk_range(new_start, new_count)
+        ));
+
+        let mut line = start;
+        for change in hunk {
+            for context in &lines[line..change.start] {
+                push_diff_line(&mut diff, ' ', context);
+            }
+            for old_line in &change.old_lines {
+                push_diff_line(&mut diff, '-', old_line);
+            }
+            for new_line in &change.new_lines {
+                push_diff_line(&mut diff, '+', new_line);
+            }
+            line = change.old_end();
+        }
+        for context in &lines[line..end] {
+            push_diff_line(&mut diff, ' ', context);
+        }
+        offset += hunk_offset;
+    }
+    diff
+}
+
+/// Formats the range of a hunk starting at the 0-based line `start`. Empty ranges refer to the
+/// line before them.
+fn hunk_range(start: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, count)
+    }
+}
+
+fn push_diff_line(diff: &mut String, prefix: char, line: &str) {
+    diff.push(prefix);
+    diff.push_str(line);
+    if !line.ends_with('\n') {
+        diff.push_str("\n\\ No newline at end of file\n");
+    }
+}
@@ -29,6 +29,7 @@
 use rustc_metadata::locator;
 use rustc_save_analysis as save;
 use rustc_save_analysis::DumpHandler;
+use rustc_session::config::PrintTypeSizes;
 use rustc_session::config::{nightly_options, CG_OPTIONS, Z_OPTIONS};
 use rustc_session::config::{ErrorOutputType, Input, OutputType, PrintRequest, TrimmedDefPaths};
 use rustc_session::cstore::MetadataLoader;
@@ -54,6 +55,7 @@
 use std::sync::LazyLock;
 use std::time::Instant;
 
+mod apply_suggestions;
 pub mod args;
 pub mod pretty;
 
@@ -362,6 +364,15 @@ fn run_compiler(
 
             queries.prepare_outputs()?;
 
+            // Written on every exit from here on, as the profile is most useful when const eval
+            // fails, e.g. because it hit the step limit.
+            let _const_eval_profile = if sess.opts.unstable_opts.const_eval_profile.is_some() {
+                let path = queries.prepare_outputs()?.peek().with_extension("const-eval.folded");
+                Some(ConstEvalProfileOnDrop { sess, path })
+            } else {
+                None
+            };
+
             if sess.opts.output_types.contains_key(&OutputType::DepInfo)
                 && sess.opts.output_types.len() == 1
             {
@@ -400,13 +411,32 @@ fn run_compiler(
 
             queries.ongoing_codegen()?;
 
-            if sess.opts.unstable_opts.print_type_sizes {
-                sess.code_stats.print_type_sizes();
+            match sess.opts.unstable_opts.print_type_sizes {
+                Some(PrintTypeSizes::Text) => sess.code_stats.print_type_sizes(),
+                Some(PrintTypeSizes::Json) => {
+                    let path = queries.prepare_outputs()?.peek().with_extension("type-sizes.json");
+                    if let Err(err) =
+                        sess.code_stats.write_type_sizes_json(sess.source_map(), &path)
+                    {
+                        sess.err(&format!(
+                            "failed to write type sizes to `{}`: {}",
+                            path.display(),
+                            err
+                        ));
+                    }
+                }
+                None => {}
             }
 
             let linker = queries.linker()?;
             Ok(Some(linker))
-        })?;
+        });
+
+        // Suggestions are applied even if compilation failed, as many of them fix errors.
+        if let Some(mode) = sess.opts.unstable_opts.apply_suggestions {
+            apply_suggestions::apply_suggestions(sess, mode);
+        }
+        let linker = linker?;
 
         if let Some(linker) = linker {
             let _timer = sess.timer("link");
@@ -441,6 +471,25 @@ pub fn set_sigpipe_handler() {
 #[cfg(windows)]
 pub fn set_sigpipe_handler() {}
 
+/// Writes the samples of `-Zconst-eval-profile` when dropped, so that they are written even
+/// if compilation stops with an error.
+struct ConstEvalProfileOnDrop<'a> {
+    sess: &'a Session,
+    path: PathBuf,
+}
+
+impl Drop for ConstEvalProfileOnDrop<'_> {
+    fn drop(&mut self) {
+        if let Err(err) = self.sess.code_stats.write_const_eval_profile(&self.path) {
+            self.sess.err(&format!(
+                "failed to write const-eval profile to `{}`: {}",
+                self.path.display(),
+                err
+            ));
+        }
+    }
+}
+
 // Extract output directory and file from matches.
 fn make_output(matches: &getopts::Matches) -> (Option<PathBuf>, Option<PathBuf>) {
     let odir = matches.opt_str("out-dir").map(|o| PathBuf::from(&o));
@@ -26,6 +26,7 @@
 use Level::*;
 
 use emitter::{is_case_difference, Emitter, EmitterWriter};
+use lint_baseline::LintBaseline;
 use registry::Registry;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_data_structures::stable_hasher::StableHasher;
@@ -39,13 +40,13 @@
 pub use rustc_lint_defs::{pluralize, Applicability};
 use rustc_span::source_map::SourceMap;
 use rustc_span::HashStableContext;
-use rustc_span::{Loc, Span};
+use rustc_span::{FileName, Loc, Span};
 
 use std::borrow::Cow;
 use std::hash::Hash;
 use std::num::NonZeroUsize;
 use std::panic;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::{error, fmt};
 
 use termcolor::{Color, ColorSpec};
@@ -55,8 +56,10 @@
 mod diagnostic_builder;
 pub mod emitter;
 pub mod json;
+pub mod lint_baseline;
 mod lock;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 
@@ -140,6 +143,24 @@ pub struct SubstitutionPart {
     pub snippet: String,
 }
 
+/// A machine-applicable suggestion of an emitted diagnostic, resolved to edits of source files.
+/// Collected when `HandlerFlags::collect_suggestions` is set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MachineApplicableSuggestion {
+    pub msg: String,
+    /// The primary span of the diagnostic the suggestion belongs to.
+    pub span: Span,
+    pub edits: Vec<SuggestionEdit>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuggestionEdit {
+    pub path: PathBuf,
+    /// The bytes of the file on disk to replace.
+    pub range: std::ops::Range<usize>,
+    pub replacement: String,
+}
+
 /// Used to translate between `Span`s and byte positions within a single output line in highlighted
 /// code of structured suggestions.
 #[derive(Debug, Clone, Copy)]
@@ -452,6 +473,13 @@ struct HandlerInner {
     ///
     /// [RFC-2383]: https://rust-lang.github.io/rfcs/2383-lint-reasons.html
     fulfilled_expectations: FxHashSet<LintExpectationId>,
+
+    /// Lint warnings to suppress, or to record, set with `-Z lint-baseline`.
+    lint_baseline: Option<LintBaseline>,
+
+    /// The machine-applicable suggestions of all emitted diagnostics, if
+    /// `HandlerFlags::collect_suggestions` is set.
+    machine_applicable_suggestions: Vec<MachineApplicableSuggestion>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -484,6 +512,9 @@ pub struct HandlerFlags {
     pub macro_backtrace: bool,
     /// If true, identical diagnostics are reported only once.
     pub deduplicate_diagnostics: bool,
+    /// If true, the machine-applicable suggestions of emitted diagnostics are collected.
+    /// (rustc: see `-Z apply-suggestions`)
+    pub collect_suggestions: bool,
 }
 
 impl Drop for HandlerInner {
@@ -591,6 +622,8 @@ pub fn with_emitter_and_flags(
                 check_unstable_expect_diagnostics: false,
                 unstable_expect_diagnostics: Vec::new(),
                 fulfilled_expectations: Default::default(),
+                lint_baseline: None,
+                machine_applicable_suggestions: Vec::new(),
             }),
         }
     }
@@ -999,6 +1032,23 @@ pub fn take_future_breakage_diagnostics(&self) -> Vec<Diagnostic> {
         std::mem::take(&mut self.inner.borrow_mut().future_breakage_diagnostics)
     }
 
+    pub fn take_machine_applicable_suggestions(&self) -> Vec<MachineApplicableSuggestion> {
+        std::mem::take(&mut self.inner.borrow_mut().machine_applicable_suggestions)
+    }
+
+    /// Suppresses the lint warnings recorded in `baseline`, or records all of them if it is
+    /// recording.
+    pub fn set_lint_baseline(&self, baseline: LintBaseline) {
+        self.inner.borrow_mut().lint_baseline = Some(baseline);
+    }
+
+    /// Writes the lint baseline out, if one is recording. Returns the path it was written to.
+    pub fn write_lint_baseline(&self) -> Option<(PathBuf, std::io::Result<()>)> {
+        let inner = self.inner.borrow();
+        let baseline = inner.lint_baseline.as_ref().filter(|baseline| baseline.is_recording())?;
+        Some((baseline.path().clone(), baseline.write()))
+    }
+
     pub fn abort_if_errors(&self) {
         self.inner.borrow_mut().abort_if_errors()
     }
@@ -1164,6 +1214,17 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
             return None;
         }
 
+        if let Some(baseline) = &mut self.lint_baseline
+            && let Some(sm) = self.emitter.source_map()
+            && baseline.suppresses(diagnostic, sm)
+        {
+            return None;
+        }
+
+        if self.flags.collect_suggestions {
+            self.collect_suggestions(diagnostic);
+        }
+
         if let Some(ref code) = diagnostic.code {
             self.emitted_diagnostic_codes.insert(code.clone());
         }
@@ -1216,6 +1277,44 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
         }
     }
 
+    /// Resolves the machine-applicable suggestions of `diagnostic` to edits of the files they
+    /// apply to. Suggestions with several alternative substitutions, or which change code that
+    /// is not in a local file, are skipped.
+    fn collect_suggestions(&mut self, diagnostic: &Diagnostic) {
+        let Ok(suggestions) = &diagnostic.suggestions else { return };
+        let Some(span) = diagnostic.span.primary_span() else { return };
+        let Some(sm) = self.emitter.source_map() else { return };
+        let args = self.emitter.to_fluent_args(diagnostic.args());
+        for suggestion in suggestions {
+            if suggestion.applicability != Applicability::MachineApplicable {
+                continue;
+            }
+            let [substitution] = &suggestion.substitutions[..] else { continue };
+            let edits: Option<Vec<_>> = substitution
+                .parts
+                .iter()
+                .map(|part| {
+                    if part.span.is_dummy() || sm.is_imported(part.span) {
+                        return None;
+                    }
+                    let file = sm.lookup_source_file(part.span.lo());
+                    let FileName::Real(name) = &file.name else { return None };
+                    let path = name.local_path()?.to_path_buf();
+                    let lo = file.original_relative_byte_pos(part.span.lo()).to_usize();
+                    let hi = file.original_relative_byte_pos(part.span.hi()).to_usize();
+                    Some(SuggestionEdit { path, range: lo..hi, replacement: part.snippet.clone() })
+                })
+                .collect();
+            let Some(edits) = edits else { continue };
+            let msg = self.emitter.translate_message(&suggestion.msg, &args).to_string();
+            self.machine_applicable_suggestions.push(MachineApplicableSuggestion {
+                msg,
+                span,
+                edits,
+            });
+        }
+    }
+
     fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
         self.emitter.emit_artifact_notification(path, artifact_type);
     }
@@ -0,0 +1,170 @@
+//! Lint baselines, which record the lint warnings a crate already has so that only new ones are
+//! reported.
+//!
+//! Lints that are denied, such as with `-D warnings`, are recorded and suppressed just like
+//! warnings, so that a new lint can be enforced right away for all code but the recorded one.
+//!
+//! Warnings are identified by the lint, the file they are in and a fingerprint of the source
+//! code they point at, rather than by line numbers, so that a baseline keeps matching while
+//! unrelated code around the warnings changes.
+//!
+//! The baseline is applied when diagnostics are emitted by the `Handler`, after they have been
+//! recorded for incremental compilation, so that warnings replayed from the incremental cache
+//! are suppressed or recorded just like fresh ones.
+
+use crate::{Diagnostic, DiagnosticId, Level};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_span::source_map::SourceMap;
+use rustc_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::path::PathBuf;
+
+/// The version of the baseline file format, bumped whenever fingerprints change.
+const VERSION: u32 = 1;
+
+pub struct LintBaseline {
+    path: PathBuf,
+    mode: Mode,
+}
+
+enum Mode {
+    /// The number of warnings with each key that are left to suppress.
+    Suppress(FxHashMap<WarningKey, usize>),
+    /// The number of warnings with each key that have been emitted.
+    Record(BTreeMap<WarningKey, usize>),
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct WarningKey {
+    lint: String,
+    file: String,
+    fingerprint: String,
+}
+
+// The following data types are provided just for serialisation.
+
+#[derive(Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    warnings: Vec<BaselineEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineEntry {
+    lint: String,
+    file: String,
+    fingerprint: String,
+    /// How many warnings of this lint point at identical code in the file.
+    count: usize,
+}
+
+impl LintBaseline {
+    /// Reads the baseline at `path`, to suppress the warnings recorded in it.
+    pub fn load(path: PathBuf) -> io::Result<LintBaseline> {
+        let contents = fs::read_to_string(&path)?;
+        let file: BaselineFile = serde_json::from_str(&contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if file.version != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported version {}, expected {}", file.version, VERSION),
+            ));
+        }
+        let mut suppressed = FxHashMap::default();
+        for BaselineEntry { lint, file, fingerprint, count } in file.warnings {
+            *suppressed.entry(WarningKey { lint, file, fingerprint }).or_default() += count;
+        }
+        Ok(LintBaseline { path, mode: Mode::Suppress(suppressed) })
+    }
+
+    /// Creates a baseline that records all lint warnings, to be written to `path` with
+    /// [`LintBaseline::write`].
+    pub fn record(path: PathBuf) -> LintBaseline {
+        LintBaseline { path, mode: Mode::Record(BTreeMap::new()) }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn is_recording(&self) -> bool {
+        matches!(self.mode, Mode::Record(_))
+    }
+
+    /// Checks a diagnostic that is about to be emitted against the baseline. Returns `true` if
+    /// it is a lint warning or error recorded in the baseline and should not be emitted.
+    pub(crate) fn suppresses(&mut self, diagnostic: &Diagnostic, sm: &SourceMap) -> bool {
+        let Some(key) = warning_key(diagnostic, sm) else { return false };
+        match &mut self.mode {
+            Mode::Suppress(suppressed) => match suppressed.get_mut(&key) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    true
+                }
+                _ => false,
+            },
+            Mode::Record(recorded) => {
+                *recorded.entry(key).or_default() += 1;
+                false
+            }
+        }
+    }
+
+    /// Writes the recorded warnings out. Does nothing if the baseline is not recording.
+    pub fn write(&self) -> io::Result<()> {
+        let Mode::Record(recorded) = &self.mode else { return Ok(()) };
+        let warnings = recorded
+            .iter()
+            .map(|(key, &count)| BaselineEntry {
+                lint: key.lint.clone(),
+                file: key.file.clone(),
+                fingerprint: key.fingerprint.clone(),
+                count,
+            })
+            .collect();
+        let file = BaselineFile { version: VERSION, warnings };
+        // One entry per line keeps diffs of checked-in baselines readable.
+        fs::write(&self.path, serde_json::to_string_pretty(&file)? + "\n")
+    }
+}
+
+/// Returns the key identifying `diagnostic` in a baseline, if it is a lint warning or a denied
+/// lint that can be suppressed by one. Warnings forced with `--force-warn` or without a location
+/// never are.
+fn warning_key(diagnostic: &Diagnostic, sm: &SourceMap) -> Option<WarningKey> {
+    if !matches!(diagnostic.level, Level::Warning(None) | Level::Error { lint: true }) {
+        return None;
+    }
+    let Some(DiagnosticId::Lint { name, is_force_warn: false, .. }) = &diagnostic.code else {
+        return None;
+    };
+    let span = diagnostic.span.primary_span().filter(|span| !span.is_dummy())?;
+    let file = sm.lookup_source_file(span.lo());
+    Some(WarningKey {
+        lint: name.clone(),
+        file: sm.filename_for_diagnostics(&file.name).to_string(),
+        fingerprint: fingerprint(span, sm)?,
+    })
+}
+
+/// Hashes the code `span` points at together with the lines it is on, ignoring whitespace, so
+/// that the fingerprint only changes when that code does.
+fn fingerprint(span: Span, sm: &SourceMap) -> Option<String> {
+    let snippet = sm.span_to_snippet(span).ok()?;
+    let lines = sm.span_to_lines(span).ok()?;
+    let mut hasher = StableHasher::new();
+    snippet.split_whitespace().for_each(|word| word.hash(&mut hasher));
+    for line in &lines.lines {
+        let text = lines.file.get_line(line.line_index)?;
+        text.split_whitespace().for_each(|word| word.hash(&mut hasher));
+    }
+    let hash: u64 = hasher.finish();
+    Some(format!("{hash:016x}"))
+}
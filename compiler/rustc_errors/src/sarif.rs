@@ -0,0 +1,512 @@
+//! A SARIF emitter for errors.
+//!
+//! SARIF (Static Analysis Results Interchange Format) 2.1.0 is the format consumed by code
+//! scanning tools. Unlike the JSON emitter, which writes one object per diagnostic, a SARIF log
+//! is a single document: diagnostics are collected while compiling and the log is written once
+//! the emitter is dropped, with a single run describing the crate being compiled.
+//!
+//! Diagnostics are mapped as follows:
+//!
+//! * error codes and lint names become rule IDs, listed in the `rules` of the run;
+//! * primary spans become the `locations` of a result, other labelled spans and sub-diagnostics
+//!   with spans become its `relatedLocations`;
+//! * each substitution of a suggestion becomes one of the `fixes` of a result;
+//! * diagnostics without a primary span, such as linker errors, cannot be results and are
+//!   reported as tool execution notifications instead.
+
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+use crate::{
+    CodeSuggestion, DiagnosticId, FluentBundle, LazyFallbackBundle, Level, SpanLabel, SubDiagnostic,
+};
+use rustc_lint_defs::Applicability;
+
+use rustc_data_structures::sync::Lrc;
+use rustc_error_messages::FluentArgs;
+use rustc_span::Span;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+#[cfg(test)]
+mod tests;
+
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    registry: Option<Registry>,
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    fallback_bundle: LazyFallbackBundle,
+    /// The name of the crate being compiled, used to categorize the run.
+    crate_name: Option<String>,
+    rules: BTreeMap<String, ReportingDescriptor>,
+    results: Vec<SarifResult>,
+    notifications: Vec<Notification>,
+    has_errors: bool,
+}
+
+impl SarifEmitter {
+    pub fn stderr(
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+        crate_name: Option<String>,
+    ) -> SarifEmitter {
+        SarifEmitter::new(
+            Box::new(io::BufWriter::new(io::stderr())),
+            registry,
+            source_map,
+            fluent_bundle,
+            fallback_bundle,
+            crate_name,
+        )
+    }
+
+    pub fn basic(
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        let file_path_mapping = FilePathMapping::empty();
+        SarifEmitter::stderr(
+            None,
+            Lrc::new(SourceMap::new(file_path_mapping)),
+            fluent_bundle,
+            fallback_bundle,
+            None,
+        )
+    }
+
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+        crate_name: Option<String>,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst,
+            registry,
+            sm: source_map,
+            fluent_bundle,
+            fallback_bundle,
+            crate_name,
+            rules: BTreeMap::new(),
+            results: Vec::new(),
+            notifications: Vec::new(),
+            has_errors: false,
+        }
+    }
+
+    /// Returns the rule ID for a diagnostic code, adding the rule to the run if it is new.
+    fn rule_id(&mut self, code: &DiagnosticId) -> String {
+        let id = match code {
+            DiagnosticId::Error(code) => code,
+            DiagnosticId::Lint { name, .. } => name,
+        };
+        if !self.rules.contains_key(id) {
+            let rule = match code {
+                DiagnosticId::Error(code) => ReportingDescriptor {
+                    id: code.clone(),
+                    full_description: self
+                        .registry
+                        .as_ref()
+                        .and_then(|registry| registry.try_find_description(code).ok().flatten())
+                        .map(|description| Message { text: description.to_owned() }),
+                    help_uri: Some(format!("https://doc.rust-lang.org/error-index.html#{code}")),
+                },
+                DiagnosticId::Lint { name, .. } => {
+                    ReportingDescriptor { id: name.clone(), full_description: None, help_uri: None }
+                }
+            };
+            self.rules.insert(id.clone(), rule);
+        }
+        id.clone()
+    }
+
+    fn location(&self, span: Span, message: Option<String>) -> Option<Location> {
+        if span.is_dummy() {
+            return None;
+        }
+        let (uri, region) = self.region(span);
+        Some(Location {
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation { uri },
+                region,
+            },
+            message: message.map(|text| Message { text }),
+        })
+    }
+
+    /// Returns the URI of the file `span` is in, and the region of that file it covers.
+    fn region(&self, span: Span) -> (String, Region) {
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let byte_offset = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        let uri = path_to_uri(&self.sm.filename_for_diagnostics(&start.file.name).to_string());
+        let region = Region {
+            start_line: start.line,
+            start_column: start.col.0 + 1,
+            end_line: end.line,
+            end_column: end.col.0 + 1,
+            byte_offset,
+            byte_length: byte_end - byte_offset,
+        };
+        (uri, region)
+    }
+
+    fn span_label_location(
+        &self,
+        span_label: &SpanLabel,
+        args: &FluentArgs<'_>,
+    ) -> Option<Location> {
+        let message =
+            span_label.label.as_ref().map(|m| self.translate_message(m, args).to_string());
+        self.location(span_label.span, message)
+    }
+
+    fn sub_diagnostic_locations(
+        &self,
+        sub: &SubDiagnostic,
+        args: &FluentArgs<'_>,
+    ) -> Vec<Location> {
+        let message =
+            format!("{}: {}", sub.level.to_str(), self.translate_messages(&sub.message, args));
+        let span = sub.render_span.as_ref().unwrap_or(&sub.span);
+        span.primary_spans()
+            .iter()
+            .filter_map(|&span| self.location(span, Some(message.clone())))
+            .collect()
+    }
+
+    fn fixes(&self, suggestion: &CodeSuggestion, args: &FluentArgs<'_>) -> Vec<Fix> {
+        let description = self.translate_message(&suggestion.msg, args).to_string();
+        suggestion
+            .substitutions
+            .iter()
+            .map(|substitution| {
+                // Replacements are grouped by the file they apply to, in order of appearance.
+                let mut artifact_changes: Vec<ArtifactChange> = Vec::new();
+                for part in &substitution.parts {
+                    let (uri, deleted_region) = self.region(part.span);
+                    let replacement = Replacement {
+                        deleted_region,
+                        inserted_content: ArtifactContent { text: part.snippet.clone() },
+                    };
+                    match artifact_changes.iter_mut().find(|c| c.artifact_location.uri == uri) {
+                        Some(change) => change.replacements.push(replacement),
+                        None => artifact_changes.push(ArtifactChange {
+                            artifact_location: ArtifactLocation { uri },
+                            replacements: vec![replacement],
+                        }),
+                    }
+                }
+                Fix {
+                    description: Message { text: description.clone() },
+                    artifact_changes,
+                    properties: FixProperties { applicability: suggestion.applicability },
+                }
+            })
+            .collect()
+    }
+
+    fn log(&mut self) -> SarifLog {
+        let automation_details =
+            self.crate_name.as_ref().map(|name| AutomationDetails { id: format!("{name}/") });
+        SarifLog {
+            schema: "https://json.schemastore.org/sarif-2.1.0.json",
+            version: "2.1.0",
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: "rustc",
+                        version: option_env!("CFG_RELEASE"),
+                        information_uri: "https://www.rust-lang.org/",
+                        rules: std::mem::take(&mut self.rules).into_values().collect(),
+                    },
+                },
+                automation_details,
+                column_kind: "unicodeCodePoints",
+                results: std::mem::take(&mut self.results),
+                invocations: vec![Invocation {
+                    execution_successful: !self.has_errors,
+                    tool_execution_notifications: std::mem::take(&mut self.notifications),
+                }],
+            }],
+        }
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
+        let level = match diag.level {
+            Level::Bug | Level::DelayedBug | Level::Fatal | Level::Error { .. } => {
+                self.has_errors = true;
+                "error"
+            }
+            Level::Warning(_) => "warning",
+            Level::Note | Level::OnceNote | Level::Help => "note",
+            Level::FailureNote | Level::Allow | Level::Expect(_) => return,
+        };
+
+        let args = self.to_fluent_args(diag.args());
+        let mut text = self.translate_messages(&diag.message, &args).to_string();
+        let mut locations = vec![];
+        let mut related_locations = vec![];
+        for span_label in diag.span.span_labels() {
+            let location = self.span_label_location(&span_label, &args);
+            if span_label.is_primary {
+                locations.extend(location);
+            } else {
+                related_locations.extend(location);
+            }
+        }
+        for child in &diag.children {
+            let child_locations = self.sub_diagnostic_locations(child, &args);
+            if child_locations.is_empty() {
+                // Notes and help without a span of their own are part of the main message, as
+                // they would be when rendered for humans.
+                let message = self.translate_messages(&child.message, &args);
+                text.push_str(&format!("\n{}: {}", child.level.to_str(), message));
+            }
+            related_locations.extend(child_locations);
+        }
+
+        if locations.is_empty() {
+            self.notifications.push(Notification { level, message: Message { text } });
+            return;
+        }
+
+        let fixes = diag
+            .suggestions
+            .iter()
+            .flatten()
+            .flat_map(|suggestion| self.fixes(suggestion, &args))
+            .collect();
+        let rule_id = diag.code.as_ref().map(|code| self.rule_id(code));
+        self.results.push(SarifResult {
+            rule_id,
+            level,
+            message: Message { text },
+            locations,
+            related_locations,
+            fixes,
+        });
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &**self.fallback_bundle
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let log = self.log();
+        let result = writeln!(&mut self.dst, "{}", serde_json::to_string(&log).unwrap())
+            .and_then(|_| self.dst.flush());
+        // Don't panic while already unwinding from a fatal error, the process would abort.
+        if let Err(e) = result {
+            if !std::thread::panicking() {
+                panic!("failed to print SARIF log: {:?}", e);
+            }
+        }
+    }
+}
+
+/// Turns a file name as displayed in diagnostics into a URI reference. Absolute paths become
+/// `file` URIs, relative paths stay relative to the working directory of the compiler.
+fn path_to_uri(path: &str) -> String {
+    let mut uri = String::new();
+    if Path::new(path).is_absolute() {
+        uri.push_str("file://");
+        if !path.starts_with('/') {
+            uri.push('/');
+        }
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'\\' => uri.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+// The following data types are provided just for serialisation, and follow the names of the
+// SARIF 2.1.0 specification.
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    /// Identifies the crate the run is for, so that the runs of all crates of a project can be
+    /// told apart.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    automation_details: Option<AutomationDetails>,
+    /// Columns are counted in characters, not in UTF-16 code units as SARIF assumes by default.
+    column_kind: &'static str,
+    results: Vec<SarifResult>,
+    invocations: Vec<Invocation>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: ToolComponent,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent {
+    name: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'static str>,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Serialize)]
+struct AutomationDetails {
+    id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    /// The error code or lint name.
+    id: String,
+    /// The long explanation of an error code, as shown by `rustc --explain`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    /// "error", "warning" or "note".
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    /// The label of the span.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    /// 1-based.
+    start_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_line: usize,
+    /// 1-based, character offset of the first character after the region.
+    end_column: usize,
+    byte_offset: u32,
+    byte_length: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Fix {
+    description: Message,
+    artifact_changes: Vec<ArtifactChange>,
+    properties: FixProperties,
+}
+
+#[derive(Serialize)]
+struct FixProperties {
+    /// Whether the fix can be applied by tools, as for `rustfix`.
+    applicability: Applicability,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: Region,
+    inserted_content: ArtifactContent,
+}
+
+#[derive(Serialize)]
+struct ArtifactContent {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Invocation {
+    execution_successful: bool,
+    tool_execution_notifications: Vec<Notification>,
+}
+
+#[derive(Serialize)]
+struct Notification {
+    level: &'static str,
+    message: Message,
+}
@@ -0,0 +1,151 @@
+use super::*;
+
+use crate::{DiagnosticId, Handler};
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::{BytePos, Span};
+
+use std::str;
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Emits diagnostics for `code` with `f`, and returns the run of the resulting SARIF log.
+fn test_run(code: &str, f: impl FnOnce(&Handler)) -> Value {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            None,
+            sm,
+            None,
+            fallback_bundle,
+            Some("test".to_owned()),
+        );
+
+        let handler = Handler::with_emitter(true, None, Box::new(se));
+        f(&handler);
+        // The log is only written once the emitter is dropped.
+        drop(handler);
+
+        let bytes = output.lock().unwrap();
+        let log: Value = serde_json::from_str(str::from_utf8(&bytes).unwrap()).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(log["runs"].as_array().unwrap().len(), 1);
+        log["runs"][0].clone()
+    })
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn result_region() {
+    let run = test_run("fn main() {\n    let x = 1;\n}\n", |handler| {
+        handler.span_err(span(20, 21), "foo");
+    });
+    assert_eq!(run["automationDetails"]["id"], "test/");
+    assert_eq!(run["invocations"][0]["executionSuccessful"], false);
+
+    let result = &run["results"][0];
+    assert_eq!(result["level"], "error");
+    assert_eq!(result["message"]["text"], "foo");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "test.rs");
+    let region = &location["region"];
+    assert_eq!(region["startLine"], 2);
+    assert_eq!(region["startColumn"], 9);
+    assert_eq!(region["endLine"], 2);
+    assert_eq!(region["endColumn"], 10);
+    assert_eq!(region["byteOffset"], 20);
+    assert_eq!(region["byteLength"], 1);
+}
+
+#[test]
+fn lint_rule() {
+    let run = test_run("fn main() {\n    let x = 1;\n}\n", |handler| {
+        handler
+            .struct_span_warn(span(20, 21), "unused variable: `x`")
+            .code(DiagnosticId::Lint {
+                name: "unused_variables".to_owned(),
+                has_future_breakage: false,
+                is_force_warn: false,
+            })
+            .span_suggestion(span(20, 21), "prefix it", "_x", Applicability::MachineApplicable)
+            .emit();
+    });
+    assert_eq!(run["invocations"][0]["executionSuccessful"], true);
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "unused_variables");
+
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "unused_variables");
+    assert_eq!(result["level"], "warning");
+
+    let fix = &result["fixes"][0];
+    assert_eq!(fix["description"]["text"], "prefix it");
+    assert_eq!(fix["properties"]["applicability"], "MachineApplicable");
+    let change = &fix["artifactChanges"][0];
+    assert_eq!(change["artifactLocation"]["uri"], "test.rs");
+    let replacement = &change["replacements"][0];
+    assert_eq!(replacement["deletedRegion"]["byteOffset"], 20);
+    assert_eq!(replacement["deletedRegion"]["byteLength"], 1);
+    assert_eq!(replacement["insertedContent"]["text"], "_x");
+}
+
+#[test]
+fn error_code_rule() {
+    let run = test_run("fn main() {}\n", |handler| {
+        handler
+            .struct_span_err_with_code(span(3, 7), "foo", DiagnosticId::Error("E0308".to_owned()))
+            .span_note(span(0, 2), "bar")
+            .emit();
+    });
+    let rule = &run["tool"]["driver"]["rules"][0];
+    assert_eq!(rule["id"], "E0308");
+    assert_eq!(rule["helpUri"], "https://doc.rust-lang.org/error-index.html#E0308");
+
+    let related = &run["results"][0]["relatedLocations"][0];
+    assert_eq!(related["message"]["text"], "note: bar");
+    assert_eq!(related["physicalLocation"]["region"]["byteOffset"], 0);
+}
+
+#[test]
+fn spanless_diagnostics_are_notifications() {
+    let run = test_run("", |handler| {
+        handler.err("linking failed");
+    });
+    assert!(run["results"].as_array().unwrap().is_empty());
+    let notification = &run["invocations"][0]["toolExecutionNotifications"][0];
+    assert_eq!(notification["level"], "error");
+    assert_eq!(notification["message"]["text"], "linking failed");
+}
+
+#[test]
+fn relative_uri() {
+    assert_eq!(path_to_uri("src/main.rs"), "src/main.rs");
+}
+
+#[test]
+#[cfg(unix)]
+fn absolute_uri() {
+    assert_eq!(path_to_uri("/home/user/my crate/lib.rs"), "file:///home/user/my%20crate/lib.rs");
+}
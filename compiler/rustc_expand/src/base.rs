@@ -1,4 +1,5 @@
 use crate::expand::{self, AstFragment, Invocation};
+use crate::expansion_trace::ExpansionTrace;
 use crate::module::DirOwnership;
 
 use rustc_ast::attr::MarkedAttrs;
@@ -994,6 +995,8 @@ pub struct ExtCtxt<'a> {
     /// in the AST, but insert it here so that we know
     /// not to expand it again.
     pub(super) expanded_inert_attrs: MarkedAttrs,
+    /// Every macro invocation that was expanded, for `-Z dump-expansion-trace`.
+    pub(crate) expansion_trace: Option<ExpansionTrace>,
 }
 
 impl<'a> ExtCtxt<'a> {
@@ -1023,6 +1026,12 @@ pub fn new(
             expansions: FxIndexMap::default(),
             expanded_inert_attrs: MarkedAttrs::new(),
             buffered_early_lint: vec![],
+            expansion_trace: sess
+                .opts
+                .unstable_opts
+                .dump_expansion_trace
+                .enabled()
+                .then(ExpansionTrace::default),
         }
     }
 
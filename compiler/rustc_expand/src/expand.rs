@@ -379,6 +379,7 @@ pub fn expand_crate(&mut self, krate: ast::Crate) -> ast::Crate {
         let krate = self.fully_expand_fragment(AstFragment::Crate(krate)).make_crate();
         assert_eq!(krate.id, ast::CRATE_NODE_ID);
         self.cx.trace_macros_diag();
+        self.cx.write_expansion_trace();
         krate
     }
 
@@ -449,7 +450,17 @@ pub fn fully_expand_fragment(&mut self, input_fragment: AstFragment) -> AstFragm
             self.cx.force_mode = force;
 
             let fragment_kind = invoc.fragment_kind;
-            let (expanded_fragment, new_invocations) = match self.expand_invoc(invoc, &ext.kind) {
+            let traced =
+                self.cx.expansion_trace.as_mut().and_then(|trace| trace.start(&invoc, &ext));
+            let result = self.expand_invoc(invoc, &ext.kind);
+            if let Some(traced) = traced {
+                let fragment = match result {
+                    ExpandResult::Ready(ref fragment) => Some(fragment),
+                    ExpandResult::Retry(_) => None,
+                };
+                self.cx.expansion_trace.as_mut().unwrap().finish(traced, expn_id, fragment);
+            }
+            let (expanded_fragment, new_invocations) = match result {
                 ExpandResult::Ready(fragment) => {
                     let mut derive_invocations = Vec::new();
                     let derive_placeholders = self
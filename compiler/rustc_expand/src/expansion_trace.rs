@@ -0,0 +1,246 @@
+//! Records every macro invocation for `-Z dump-expansion-trace`.
+//!
+//! For every invocation that is expanded, the trace records which macro was
+//! invoked and how it is implemented, its input and its output, the time the
+//! expansion took, and the expansion it created along with that expansion's
+//! parent. The input and output are pretty-printed, and the output only
+//! contains the invocations of nested macros, which are recorded separately.
+
+use crate::base::{Annotatable, ExtCtxt, SyntaxExtension, SyntaxExtensionKind};
+use crate::expand::{AstFragment, Invocation, InvocationKind};
+
+use rustc_ast_pretty::pprust;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_session::config::SwitchWithOptPath;
+use rustc_span::{ExpnId, LocalExpnId, Span};
+use serde_json::{json, Value};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+pub(crate) struct ExpansionTrace {
+    expansions: Vec<TracedExpansion>,
+    /// The time spent in expansions that happened while expanding the
+    /// current invocation, like eager expansion of `format_args!` arguments.
+    nested_time: Duration,
+}
+
+struct TracedExpansion {
+    id: LocalExpnId,
+    kind: &'static str,
+    expander: &'static str,
+    path: String,
+    input: Option<String>,
+    output: Option<String>,
+    depth: usize,
+    time: Duration,
+    self_time: Duration,
+}
+
+/// An invocation that is being expanded.
+pub(crate) struct StartedExpansion {
+    kind: &'static str,
+    expander: &'static str,
+    path: String,
+    input: Option<String>,
+    depth: usize,
+    start: Instant,
+    outer_nested_time: Duration,
+}
+
+impl ExpansionTrace {
+    /// Starts tracing the expansion of `invoc`, which is about to be expanded
+    /// with `ext`. Inert attributes aren't traced.
+    pub(crate) fn start(
+        &mut self,
+        invoc: &Invocation,
+        ext: &SyntaxExtension,
+    ) -> Option<StartedExpansion> {
+        if let SyntaxExtensionKind::NonMacroAttr = ext.kind {
+            return None;
+        }
+        let (kind, path, input) = match &invoc.kind {
+            InvocationKind::Bang { mac, .. } => {
+                ("bang", &mac.path, Some(pprust::tts_to_string(&mac.args.inner_tokens())))
+            }
+            InvocationKind::Attr { attr, item, .. } => {
+                let input = annotatable_to_string(item)
+                    .map(|item| format!("{}\n{}", pprust::attribute_to_string(attr), item));
+                ("attr", &attr.get_normal_item().path, input)
+            }
+            InvocationKind::Derive { path, item } => ("derive", path, annotatable_to_string(item)),
+        };
+        let expander = match ext.kind {
+            SyntaxExtensionKind::Bang(..)
+            | SyntaxExtensionKind::Attr(..)
+            | SyntaxExtensionKind::Derive(..) => "proc_macro",
+            _ if ext.builtin_name.is_some() => "builtin",
+            _ => "declarative",
+        };
+        Some(StartedExpansion {
+            kind,
+            expander,
+            path: pprust::path_to_string(path),
+            input,
+            depth: invoc.expansion_data.depth,
+            start: Instant::now(),
+            outer_nested_time: std::mem::take(&mut self.nested_time),
+        })
+    }
+
+    /// Finishes tracing an expansion. The output is `None` if the invocation
+    /// couldn't be expanded yet and will be retried later, in which case it
+    /// isn't recorded.
+    pub(crate) fn finish(
+        &mut self,
+        started: StartedExpansion,
+        id: LocalExpnId,
+        output: Option<&AstFragment>,
+    ) {
+        let time = started.start.elapsed();
+        let self_time = time.saturating_sub(self.nested_time);
+        self.nested_time = started.outer_nested_time + time;
+        let Some(output) = output else { return };
+        self.expansions.push(TracedExpansion {
+            id,
+            kind: started.kind,
+            expander: started.expander,
+            path: started.path,
+            input: started.input,
+            output: fragment_to_string(output),
+            depth: started.depth,
+            time,
+            self_time,
+        });
+    }
+}
+
+impl<'a> ExtCtxt<'a> {
+    pub(crate) fn write_expansion_trace(&self) {
+        let Some(trace) = &self.expansion_trace else { return };
+        let options = &self.sess.opts.unstable_opts;
+        let SwitchWithOptPath::Enabled(ref path) = options.dump_expansion_trace else { return };
+        let output_directory = path.as_deref().unwrap_or_else(|| Path::new("."));
+        if let Err(err) = self.write_trace(trace, output_directory) {
+            self.sess.err(&format!("failed to write expansion trace: {}", err));
+        }
+    }
+
+    fn write_trace(&self, trace: &ExpansionTrace, output_directory: &Path) -> io::Result<()> {
+        let mut by_macro: FxHashMap<(&str, &str), (usize, Duration)> = FxHashMap::default();
+        let expansions: Vec<_> = trace
+            .expansions
+            .iter()
+            .map(|expansion| {
+                let entry = by_macro.entry((&expansion.path, expansion.kind)).or_default();
+                entry.0 += 1;
+                entry.1 += expansion.self_time;
+
+                let expn_data = expansion.id.expn_data();
+                json!({
+                    "id": expansion.id.as_u32(),
+                    "parent": expn_id_to_json(expn_data.parent),
+                    "depth": expansion.depth,
+                    "kind": expansion.kind,
+                    "macro": expansion.path,
+                    "expander": expansion.expander,
+                    "description": expn_data.kind.descr(),
+                    "call_site": self.location_to_json(expn_data.call_site),
+                    "def_site": self.location_to_json(expn_data.def_site),
+                    "input": expansion.input,
+                    "output": expansion.output,
+                    "time_ns": expansion.time.as_nanos() as u64,
+                    "self_time_ns": expansion.self_time.as_nanos() as u64,
+                })
+            })
+            .collect();
+
+        let mut by_macro: Vec<_> = by_macro.into_iter().collect();
+        by_macro.sort_by(|(a_key, (_, a_time)), (b_key, (_, b_time))| {
+            b_time.cmp(a_time).then_with(|| a_key.cmp(b_key))
+        });
+        let macros: Vec<_> = by_macro
+            .into_iter()
+            .map(|((path, kind), (count, time))| {
+                json!({
+                    "macro": path,
+                    "kind": kind,
+                    "invocations": count,
+                    "self_time_ns": time.as_nanos() as u64,
+                })
+            })
+            .collect();
+
+        let json = json!({
+            "crate": self.ecfg.crate_name,
+            "macros": macros,
+            "expansions": expansions,
+        });
+        let file_name = format!("{}.expansion-trace.json", self.ecfg.crate_name);
+        fs::create_dir_all(output_directory)?;
+        fs::write(output_directory.join(file_name), serde_json::to_string_pretty(&json)?)
+    }
+
+    fn location_to_json(&self, span: Span) -> Value {
+        if span.is_dummy() {
+            return Value::Null;
+        }
+        let loc = self.sess.source_map().lookup_char_pos(span.lo());
+        json!({
+            "file": loc.file.name.prefer_remapped().to_string(),
+            "line": loc.line,
+            "column": loc.col.0 + 1,
+        })
+    }
+}
+
+/// Expansions are identified by their index, which is the `N` of `expnN` in
+/// `-Z unpretty=expanded,hygiene`.
+fn expn_id_to_json(expn_id: ExpnId) -> Value {
+    match expn_id.as_local() {
+        Some(LocalExpnId::ROOT) | None => Value::Null,
+        Some(expn_id) => json!(expn_id.as_u32()),
+    }
+}
+
+fn annotatable_to_string(annotatable: &Annotatable) -> Option<String> {
+    Some(match annotatable {
+        Annotatable::Item(item) => pprust::item_to_string(item),
+        Annotatable::TraitItem(item) | Annotatable::ImplItem(item) => {
+            pprust::assoc_item_to_string(item)
+        }
+        Annotatable::ForeignItem(item) => pprust::foreign_item_to_string(item),
+        Annotatable::Stmt(stmt) => pprust::stmt_to_string(stmt),
+        Annotatable::Expr(expr) => pprust::expr_to_string(expr),
+        Annotatable::Crate(krate) => pprust::crate_to_string_for_macros(krate),
+        // Only inert attributes can be applied to the remaining kinds of nodes.
+        _ => return None,
+    })
+}
+
+fn fragment_to_string(fragment: &AstFragment) -> Option<String> {
+    fn join<T>(nodes: &[T], to_string: impl Fn(&T) -> String) -> String {
+        nodes.iter().map(to_string).collect::<Vec<_>>().join("\n")
+    }
+
+    Some(match fragment {
+        AstFragment::OptExpr(expr) => {
+            expr.as_ref().map(|expr| pprust::expr_to_string(expr)).unwrap_or_default()
+        }
+        AstFragment::Expr(expr) => pprust::expr_to_string(expr),
+        AstFragment::Pat(pat) => pprust::pat_to_string(pat),
+        AstFragment::Ty(ty) => pprust::ty_to_string(ty),
+        AstFragment::Stmts(stmts) => join(stmts, pprust::stmt_to_string),
+        AstFragment::Items(items) => join(items, |item| pprust::item_to_string(item)),
+        AstFragment::TraitItems(items) | AstFragment::ImplItems(items) => {
+            join(items, |item| pprust::assoc_item_to_string(item))
+        }
+        AstFragment::ForeignItems(items) => {
+            join(items, |item| pprust::foreign_item_to_string(item))
+        }
+        AstFragment::Crate(krate) => pprust::crate_to_string_for_macros(krate),
+        _ => return None,
+    })
+}
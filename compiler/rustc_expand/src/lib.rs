@@ -17,6 +17,7 @@
 
 extern crate proc_macro as pm;
 
+mod expansion_trace;
 mod placeholders;
 mod proc_macro_server;
 
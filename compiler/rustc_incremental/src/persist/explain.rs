@@ -0,0 +1,174 @@
+//! Writes a report of why incremental compilation had to re-execute queries,
+//! for `-Z incremental-explain`.
+//!
+//! Every re-executed query is attributed to the root cause of its
+//! re-execution: the node whose change made the chain of its dependencies
+//! fail to be marked green, like the HIR of an item that was edited. The
+//! report aggregates the queries and the time spent in them by root cause and
+//! by the source file the root causes are in. If the previous session was
+//! explained too, the report also lists the command-line options that changed
+//! since, which is the reason when the whole previous graph was discarded.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::dep_graph::{DepNode, DepNodeExt};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::SwitchWithOptPath;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+pub(crate) fn write_incremental_explanation(tcx: TyCtxt<'_>) {
+    let SwitchWithOptPath::Enabled(ref path) = tcx.sess.opts.unstable_opts.incremental_explain
+    else {
+        return;
+    };
+    let output_directory = path.as_deref().unwrap_or_else(|| Path::new("."));
+    if let Err(err) = write_report(tcx, output_directory) {
+        tcx.sess.err(&format!("failed to write incremental explanation: {}", err));
+    }
+}
+
+#[derive(Default)]
+struct RootCause {
+    reexecuted: usize,
+    reexecuted_unchanged: usize,
+    time: Duration,
+    queries: BTreeMap<String, usize>,
+}
+
+fn write_report(tcx: TyCtxt<'_>, output_directory: &Path) -> io::Result<()> {
+    let Some(explanation) = tcx.dep_graph.explain(tcx) else { return Ok(()) };
+
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    fs::create_dir_all(output_directory)?;
+    let path = output_directory.join(format!("{}.incremental-explain.json", crate_name));
+
+    let option_hashes = tcx.sess.opts.dep_tracking_hashes();
+    // The report of the previous session is about to be overwritten, so this
+    // is the last chance to compare the options against it.
+    let changed_options = fs::read(&path)
+        .ok()
+        .and_then(|previous| serde_json::from_slice::<serde_json::Value>(&previous).ok())
+        .and_then(|previous| previous.get("option_hashes")?.as_object().cloned())
+        .map(|previous_hashes| {
+            let mut changed: Vec<String> = option_hashes
+                .iter()
+                .filter(|&(name, &hash)| {
+                    previous_hashes.get(name).and_then(|h| h.as_u64()) != Some(hash)
+                })
+                .map(|(name, _)| name.clone())
+                .collect();
+            changed.extend(
+                previous_hashes.keys().filter(|name| !option_hashes.contains_key(*name)).cloned(),
+            );
+            changed.sort();
+            changed
+        });
+
+    let mut root_causes: FxHashMap<DepNode, RootCause> = FxHashMap::default();
+    let mut total_time = Duration::ZERO;
+    let mut reexecuted_unchanged = 0;
+    for node in &explanation.reexecuted {
+        let root_cause = root_causes.entry(node.root_cause).or_default();
+        root_cause.reexecuted += 1;
+        if !node.changed {
+            root_cause.reexecuted_unchanged += 1;
+            reexecuted_unchanged += 1;
+        }
+        root_cause.time += node.self_time;
+        *root_cause.queries.entry(format!("{:?}", node.node.kind)).or_default() += 1;
+        total_time += node.self_time;
+    }
+
+    let mut root_causes: Vec<_> = root_causes.into_iter().collect();
+    root_causes.sort_by(|(a_node, a), (b_node, b)| {
+        b.time
+            .cmp(&a.time)
+            .then_with(|| b.reexecuted.cmp(&a.reexecuted))
+            .then_with(|| (a_node.kind as u16, a_node.hash).cmp(&(b_node.kind as u16, b_node.hash)))
+    });
+
+    let mut source_files: BTreeMap<String, RootCause> = BTreeMap::new();
+    let root_causes_json: Vec<_> = root_causes
+        .iter()
+        .map(|(node, root_cause)| {
+            // Nodes of the previous session that don't exist in this one may
+            // refer to items that were removed, so they can't be printed.
+            let exists = tcx.dep_graph.dep_node_exists(node);
+            let (description, file) = if exists {
+                (format!("{:?}", node), node_file(tcx, node))
+            } else {
+                (format!("{:?}({})", node.kind, node.hash), None)
+            };
+            if let Some(file) = &file {
+                let source_file = source_files.entry(file.clone()).or_default();
+                source_file.reexecuted += root_cause.reexecuted;
+                source_file.reexecuted_unchanged += root_cause.reexecuted_unchanged;
+                source_file.time += root_cause.time;
+                for (kind, count) in &root_cause.queries {
+                    *source_file.queries.entry(kind.clone()).or_default() += count;
+                }
+            }
+            json!({
+                "node": description,
+                "kind": format!("{:?}", node.kind),
+                "exists": exists,
+                "file": file,
+                "reexecuted": root_cause.reexecuted,
+                "reexecuted_unchanged": root_cause.reexecuted_unchanged,
+                "time_ns": root_cause.time.as_nanos() as u64,
+                "queries": root_cause.queries,
+            })
+        })
+        .collect();
+
+    let mut source_files: Vec<_> = source_files.into_iter().collect();
+    source_files.sort_by(|(_, a), (_, b)| b.time.cmp(&a.time));
+    let source_files_json: Vec<_> = source_files
+        .iter()
+        .map(|(file, source_file)| {
+            json!({
+                "file": file,
+                "reexecuted": source_file.reexecuted,
+                "reexecuted_unchanged": source_file.reexecuted_unchanged,
+                "time_ns": source_file.time.as_nanos() as u64,
+                "queries": source_file.queries,
+            })
+        })
+        .collect();
+
+    let json = json!({
+        "crate": crate_name.as_str(),
+        "previous_session": {
+            "loaded": explanation.previous_node_count > 0,
+            "nodes": explanation.previous_node_count,
+        },
+        "changed_options": changed_options,
+        "option_hashes": option_hashes,
+        "summary": {
+            "green": explanation.green_node_count,
+            "reexecuted": explanation.reexecuted.len(),
+            "reexecuted_unchanged": reexecuted_unchanged,
+            "time_ns": total_time.as_nanos() as u64,
+        },
+        "root_causes": root_causes_json,
+        "source_files": source_files_json,
+    });
+    fs::write(path, serde_json::to_string_pretty(&json)?)
+}
+
+/// The source file of the item a node is about, if it is about a local item.
+fn node_file(tcx: TyCtxt<'_>, node: &DepNode) -> Option<String> {
+    let def_id = node.extract_def_id(tcx)?.as_local()?;
+    // This runs after all queries have completed, so use the span without
+    // going through a query.
+    let span = tcx.source_span_untracked(def_id);
+    if span.is_dummy() {
+        return None;
+    }
+    Some(tcx.sess.source_map().span_to_filename(span).prefer_local().to_string())
+}
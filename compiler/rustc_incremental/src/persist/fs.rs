@@ -118,6 +118,8 @@
 
 use rand::{thread_rng, RngCore};
 
+use super::shared_cache;
+
 #[cfg(test)]
 mod tests;
 
@@ -256,7 +258,14 @@ pub fn prepare_session_directory(
         let source_directory = find_source_directory(&crate_dir, &source_directories_already_tried);
 
         let Some(source_directory) = source_directory else {
-            // There's nowhere to copy from, we're done
+            // There's nowhere to copy from, unless the shared cache has a
+            // session for the crate.
+            if shared_cache::restore_session(sess, &crate_dir, &session_dir) {
+                debug!("restored session directory from shared cache");
+                sess.init_incr_comp_session(session_dir, directory_lock, true);
+                return Ok(());
+            }
+
             debug!(
                 "no source directory found. Continuing with empty session \
                     directory."
@@ -376,7 +385,9 @@ pub fn finalize_session_directory(sess: &Session, svh: Svh) {
             debug!("finalize_session_directory() - directory renamed successfully");
 
             // This unlocks the directory
-            sess.finalize_incr_comp_session(new_path);
+            sess.finalize_incr_comp_session(new_path.clone());
+
+            shared_cache::publish_session(sess, &new_path);
         }
         Err(e) => {
             // Warn about the error. However, no need to abort compilation now.
@@ -4,10 +4,12 @@
 
 mod data;
 mod dirty_clean;
+mod explain;
 mod file_format;
 mod fs;
 mod load;
 mod save;
+mod shared_cache;
 mod work_product;
 
 pub use fs::finalize_session_directory;
@@ -9,8 +9,10 @@
 
 use super::data::*;
 use super::dirty_clean;
+use super::explain;
 use super::file_format;
 use super::fs::*;
+use super::shared_cache;
 use super::work_product;
 
 /// Saves and writes the [`DepGraph`] to the file system.
@@ -32,6 +34,8 @@ pub fn save_dep_graph(tcx: TyCtxt<'_>) {
             return;
         }
 
+        sess.time("incr_comp_explain", || explain::write_incremental_explanation(tcx));
+
         let query_cache_path = query_cache_path(sess);
         let dep_graph_path = dep_graph_path(sess);
         let staging_dep_graph_path = staging_dep_graph_path(sess);
@@ -175,7 +179,7 @@ pub fn build_dep_graph(
     file_format::write_file_header(&mut encoder, sess.is_nightly_build());
 
     // First encode the commandline arguments hash
-    sess.opts.dep_tracking_hash(false).encode(&mut encoder);
+    shared_cache::options_hash(sess).encode(&mut encoder);
 
     Some(DepGraph::new(
         &sess.prof,
@@ -184,5 +188,6 @@ pub fn build_dep_graph(
         encoder,
         sess.opts.unstable_opts.query_dep_graph,
         sess.opts.unstable_opts.incremental_info,
+        sess.opts.unstable_opts.incremental_explain.enabled(),
     ))
 }
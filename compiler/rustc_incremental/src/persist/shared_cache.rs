@@ -0,0 +1,333 @@
+//! A content-addressed store of finalized session directories that can be
+//! shared between checkouts of the same code, for
+//! `-Z incremental-shared-cache`.
+//!
+//! The store has the following layout:
+//!
+//! - `objects/{hash[..2]}/{hash[2..]}` holds the contents of every file of
+//!   every published session directory, named by the hash of the contents.
+//!   Since object files, the dependency graph and the query cache rarely
+//!   change all at once, publishing a session mostly adds files that are
+//!   already there.
+//! - `manifests/{crate-dir}-{key}` lists the files of the most recently
+//!   published session directory of a crate, as lines of `{hash} {name}`. The
+//!   key is a hash of the compiler version and of the command-line options
+//!   that invalidate the dependency graph, so that only sessions the compiler
+//!   could load are ever restored.
+//! - `manifests/{crate-dir}-{key}.used` is rewritten whenever the manifest is
+//!   restored, for garbage collection.
+//!
+//! When a session starts and the incremental compilation directory doesn't
+//! have a finalized session directory for the crate yet, the files of the
+//! manifest are hard-linked (or copied) into the new session directory, which
+//! then is used as if it had been copied from a local session directory.
+//! When a session directory is finalized, its files are added to the store
+//! and the manifest of the crate is replaced.
+//!
+//! ## Path remapping
+//!
+//! Checkouts at different paths don't produce the same dependency graph
+//! unless their paths are remapped to the same one, e.g. with
+//! `--remap-path-prefix=$PWD=/checkout`. As the options that remap the path
+//! of the checkout then still differ, remapped paths inside the working
+//! directory are hashed relative to it when checking whether a dependency
+//! graph can be loaded.
+//!
+//! ## Synchronization
+//!
+//! All accesses to the store are synchronized with the `lock` file in its
+//! root. Restoring a session holds a shared lock, while publishing a session
+//! and collecting garbage hold an exclusive one. Files are written to a
+//! temporary name and then renamed, so that a crashed compiler never leaves a
+//! partial object or manifest behind.
+//!
+//! ## Garbage collection
+//!
+//! At most once a day, publishing a session also deletes the manifests that
+//! have been neither published nor restored for a week, and then all objects
+//! that no remaining manifest refers to.
+
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::flock;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_fs_util::link_or_copy;
+use rustc_session::Session;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use rand::{thread_rng, RngCore};
+
+use super::file_format;
+
+#[cfg(test)]
+mod tests;
+
+const LOCK_FILENAME: &str = "lock";
+const LAST_GC_FILENAME: &str = "last-gc";
+const OBJECTS_DIRNAME: &str = "objects";
+const MANIFESTS_DIRNAME: &str = "manifests";
+const USED_EXT: &str = "used";
+const TEMP_EXT: &str = "tmp";
+
+/// How long a manifest is kept after it was last published or restored.
+const MAX_MANIFEST_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// How often garbage is collected.
+const GC_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The hash of the command-line options that is stored in the dependency
+/// graph and compared when loading it.
+///
+/// When sharing sessions between checkouts, remapping the path of the
+/// checkout must not invalidate the dependency graph, so the prefixes of
+/// `--remap-path-prefix` that are inside the working directory are hashed
+/// relative to it. Otherwise, this is the usual dependency tracking hash.
+pub(crate) fn options_hash(sess: &Session) -> u64 {
+    if sess.opts.unstable_opts.incremental_shared_cache.is_none() {
+        return sess.opts.dep_tracking_hash(false);
+    }
+    let Some(working_dir) = sess.opts.working_dir.local_path() else {
+        return sess.opts.dep_tracking_hash(false);
+    };
+    let is_relocatable = |from: &PathBuf| from.starts_with(working_dir);
+    if !sess.opts.remap_path_prefix.iter().any(|(from, _)| is_relocatable(from)) {
+        return sess.opts.dep_tracking_hash(false);
+    }
+
+    let mut opts = sess.opts.clone();
+    for (from, _) in &mut opts.remap_path_prefix {
+        if let Ok(relative) = from.strip_prefix(working_dir) {
+            *from = Path::new("<working-dir>").join(relative);
+        }
+    }
+    opts.dep_tracking_hash(false)
+}
+
+/// Fills the empty `session_dir` with the files of the session of the crate
+/// in the shared store, if there is one. Returns whether it did.
+pub(crate) fn restore_session(sess: &Session, crate_dir: &Path, session_dir: &Path) -> bool {
+    let Some(store) = &sess.opts.unstable_opts.incremental_shared_cache else { return false };
+    let manifest_path = manifest_path(sess, store, crate_dir);
+    match restore_files(store, &manifest_path, session_dir) {
+        Ok(files) => {
+            if sess.opts.unstable_opts.incremental_info {
+                eprintln!(
+                    "[incremental] session directory: {} files restored from shared cache `{}`",
+                    files,
+                    store.display()
+                );
+            }
+            true
+        }
+        Err(err) => {
+            if err.kind() != io::ErrorKind::NotFound {
+                sess.warn(&format!(
+                    "failed to restore incremental compilation session from shared cache `{}`: {}",
+                    store.display(),
+                    err
+                ));
+            }
+            // Don't leave the files restored so far behind.
+            if let Ok(entries) = session_dir.read_dir() {
+                for entry in entries.flatten() {
+                    let _ = fs::remove_file(entry.path());
+                }
+            }
+            false
+        }
+    }
+}
+
+/// Adds the files of the finalized `session_dir` to the shared store and
+/// makes them the session of its crate.
+pub(crate) fn publish_session(sess: &Session, session_dir: &Path) {
+    let Some(store) = &sess.opts.unstable_opts.incremental_shared_cache else { return };
+    let crate_dir = session_dir.parent().unwrap();
+    let manifest_path = manifest_path(sess, store, crate_dir);
+    if let Err(err) = publish_files(store, &manifest_path, session_dir) {
+        sess.warn(&format!(
+            "failed to publish incremental compilation session to shared cache `{}`: {}",
+            store.display(),
+            err
+        ));
+    }
+}
+
+fn manifest_path(sess: &Session, store: &Path, crate_dir: &Path) -> PathBuf {
+    let mut hasher = StableHasher::new();
+    file_format::rustc_version(sess.is_nightly_build()).hash(&mut hasher);
+    options_hash(sess).hash(&mut hasher);
+    let key: Fingerprint = hasher.finish();
+    let (key_0, key_1) = key.as_value();
+
+    // The name of the crate directory is made of the crate name and its
+    // stable crate id.
+    let crate_dir_name = crate_dir.file_name().unwrap().to_string_lossy();
+    store.join(MANIFESTS_DIRNAME).join(format!("{}-{:016x}{:016x}", crate_dir_name, key_0, key_1))
+}
+
+fn object_path(store: &Path, hash: &str) -> PathBuf {
+    store.join(OBJECTS_DIRNAME).join(&hash[..2]).join(&hash[2..])
+}
+
+fn lock_store(store: &Path, exclusive: bool) -> io::Result<flock::Lock> {
+    fs::create_dir_all(store)?;
+    flock::Lock::new(
+        &store.join(LOCK_FILENAME),
+        true, // wait
+        true, // create the lock file
+        exclusive,
+    )
+}
+
+fn restore_files(store: &Path, manifest_path: &Path, session_dir: &Path) -> io::Result<usize> {
+    let _lock = lock_store(store, false)?;
+    let manifest = fs::read_to_string(manifest_path)?;
+
+    let mut files = 0;
+    for line in manifest.lines() {
+        let Some((hash, name)) = parse_manifest_line(line) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("malformed manifest `{}`", manifest_path.display()),
+            ));
+        };
+        link_or_copy(object_path(store, hash), session_dir.join(name))?;
+        files += 1;
+    }
+
+    write_atomically(&manifest_path.with_extension(USED_EXT), b"")?;
+    Ok(files)
+}
+
+fn publish_files(store: &Path, manifest_path: &Path, session_dir: &Path) -> io::Result<()> {
+    let _lock = lock_store(store, true)?;
+
+    let mut names: Vec<_> = session_dir
+        .read_dir()?
+        .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+        .collect::<io::Result<_>>()?;
+    names.sort();
+
+    let mut manifest = String::new();
+    for name in names {
+        let path = session_dir.join(&name);
+        let hash = hash_file(&path)?;
+        let object_path = object_path(store, &hash);
+        if !object_path.exists() {
+            fs::create_dir_all(object_path.parent().unwrap())?;
+            let temp_path = temp_path(&object_path);
+            link_or_copy(&path, &temp_path)?;
+            fs::rename(&temp_path, &object_path)?;
+        }
+        manifest.push_str(&format!("{} {}\n", hash, name));
+    }
+
+    fs::create_dir_all(manifest_path.parent().unwrap())?;
+    write_atomically(manifest_path, manifest.as_bytes())?;
+
+    let last_gc_path = store.join(LAST_GC_FILENAME);
+    if !modified_within(&last_gc_path, GC_INTERVAL) {
+        collect_garbage(store)?;
+        write_atomically(&last_gc_path, b"")?;
+    }
+    Ok(())
+}
+
+/// Deletes the manifests that haven't been used for a while and the objects
+/// that aren't referred to by any manifest. The store must be locked
+/// exclusively.
+fn collect_garbage(store: &Path) -> io::Result<()> {
+    let mut referenced = FxHashSet::default();
+    for entry in store.join(MANIFESTS_DIRNAME).read_dir()? {
+        let path = entry?.path();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(USED_EXT) => {
+                if !path.with_extension("").exists() {
+                    fs::remove_file(&path)?;
+                }
+                continue;
+            }
+            Some(TEMP_EXT) => {
+                fs::remove_file(&path)?;
+                continue;
+            }
+            _ => {}
+        }
+
+        if !modified_within(&path, MAX_MANIFEST_AGE)
+            && !modified_within(&path.with_extension(USED_EXT), MAX_MANIFEST_AGE)
+        {
+            fs::remove_file(&path)?;
+            continue;
+        }
+        for line in fs::read_to_string(&path)?.lines() {
+            if let Some((hash, _)) = parse_manifest_line(line) {
+                referenced.insert(hash.to_owned());
+            }
+        }
+    }
+
+    for prefix in store.join(OBJECTS_DIRNAME).read_dir()? {
+        let prefix = prefix?;
+        let prefix_name = prefix.file_name().to_string_lossy().into_owned();
+        for object in prefix.path().read_dir()? {
+            let object = object?;
+            let hash = format!("{}{}", prefix_name, object.file_name().to_string_lossy());
+            if !referenced.contains(&hash) {
+                fs::remove_file(object.path())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Splits a line of a manifest into the hash of the file and its name.
+/// Names with path separators are rejected, as they would refer to files
+/// outside of the session directory.
+fn parse_manifest_line(line: &str) -> Option<(&str, &str)> {
+    let (hash, name) = line.split_once(' ')?;
+    let is_valid_hash = hash.len() > 2 && hash.bytes().all(|b| b.is_ascii_hexdigit());
+    let is_valid_name =
+        !name.is_empty() && name != "." && name != ".." && !name.contains(std::path::is_separator);
+    (is_valid_hash && is_valid_name).then_some((hash, name))
+}
+
+fn hash_file(path: &Path) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = StableHasher::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buffer[..read]);
+    }
+    let (hash_0, hash_1) = hasher.finish::<Fingerprint>().as_value();
+    Ok(format!("{:016x}{:016x}", hash_0, hash_1))
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap().to_os_string();
+    file_name.push(format!(".{:x}.{}", thread_rng().next_u32(), TEMP_EXT));
+    path.with_file_name(file_name)
+}
+
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp_path = temp_path(path);
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
+fn modified_within(path: &Path, duration: Duration) -> bool {
+    match fs::metadata(path).and_then(|metadata| metadata.modified()) {
+        Ok(modified) => {
+            SystemTime::now().duration_since(modified).map_or(true, |age| age < duration)
+        }
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,15 @@
+use super::*;
+
+#[test]
+fn test_parse_manifest_line() {
+    assert_eq!(
+        parse_manifest_line("0123456789abcdef0123456789abcdef dep-graph.bin"),
+        Some(("0123456789abcdef0123456789abcdef", "dep-graph.bin"))
+    );
+    assert_eq!(parse_manifest_line("0123456789abcdef x y.o"), Some(("0123456789abcdef", "x y.o")));
+    assert_eq!(parse_manifest_line("dep-graph.bin"), None);
+    assert_eq!(parse_manifest_line("not-a-hash dep-graph.bin"), None);
+    assert_eq!(parse_manifest_line("0123456789abcdef "), None);
+    assert_eq!(parse_manifest_line("0123456789abcdef .."), None);
+    assert_eq!(parse_manifest_line("0123456789abcdef ../dep-graph.bin"), None);
+}
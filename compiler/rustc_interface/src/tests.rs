@@ -10,8 +10,9 @@
     rustc_optgroups, ErrorOutputType, ExternLocation, LocationDetail, Options, Passes,
 };
 use rustc_session::config::{
-    BranchProtection, Externs, OomStrategy, OutputType, OutputTypes, PAuthKey, PacRet,
-    ProcMacroExecutionStrategy, SymbolManglingVersion, WasiExecModel,
+    ApplySuggestions, BranchProtection, DumpResolutionsFormat, Externs, OomStrategy, OutputType,
+    OutputTypes, PAuthKey, PacRet, PrintTypeSizes, ProcMacroExecutionStrategy,
+    SymbolManglingVersion, WasiExecModel,
 };
 use rustc_session::config::{CFGuard, ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
 use rustc_session::lint::Level;
@@ -644,28 +645,40 @@ macro_rules! untracked {
 
     // Make sure that changing an [UNTRACKED] option leaves the hash unchanged.
     // This list is in alphabetical order.
+    untracked!(apply_suggestions, Some(ApplySuggestions::DryRun));
     untracked!(assert_incr_state, Some(String::from("loaded")));
+    untracked!(const_eval_profile, Some(1000));
     untracked!(deduplicate_diagnostics, false);
     untracked!(dep_tasks, true);
     untracked!(dlltool, Some(PathBuf::from("custom_dlltool.exe")));
     untracked!(dont_buffer_diagnostics, true);
     untracked!(dump_dep_graph, true);
     untracked!(dump_drop_tracking_cfg, Some("cfg.dot".to_string()));
+    untracked!(dump_expansion_trace, SwitchWithOptPath::Enabled(Some(PathBuf::from("abc"))));
     untracked!(dump_mir, Some(String::from("abc")));
     untracked!(dump_mir_dataflow, true);
     untracked!(dump_mir_dir, String::from("abc"));
     untracked!(dump_mir_exclude_pass_number, true);
     untracked!(dump_mir_graphviz, true);
+    untracked!(dump_mono_items, SwitchWithOptPath::Enabled(Some(PathBuf::from("abc"))));
+    untracked!(dump_obligation_tree, Some(String::from("abc")));
+    untracked!(dump_obligation_tree_dir, String::from("abc"));
+    untracked!(dump_obligation_tree_graphviz, true);
+    untracked!(dump_resolutions, Some(DumpResolutionsFormat::Json));
+    untracked!(dump_resolutions_dir, Some(PathBuf::from("abc")));
     untracked!(emit_stack_sizes, true);
     untracked!(future_incompat_test, true);
     untracked!(hir_stats, true);
     untracked!(identify_regions, true);
+    untracked!(incremental_explain, SwitchWithOptPath::Enabled(Some(PathBuf::from("abc"))));
     untracked!(incremental_ignore_spans, true);
     untracked!(incremental_info, true);
+    untracked!(incremental_shared_cache, Some(PathBuf::from("abc")));
     untracked!(incremental_verify_ich, true);
     untracked!(input_stats, true);
     untracked!(keep_hygiene_data, true);
     untracked!(link_native_libraries, false);
+    untracked!(lint_baseline, Some(PathBuf::from("baseline.json")));
     untracked!(llvm_time_trace, true);
     untracked!(ls, true);
     untracked!(macro_backtrace, true);
@@ -683,7 +696,7 @@ macro_rules! untracked {
     untracked!(profile_closures, true);
     untracked!(print_llvm_passes, true);
     untracked!(print_mono_items, Some(String::from("abc")));
-    untracked!(print_type_sizes, true);
+    untracked!(print_type_sizes, Some(PrintTypeSizes::Text));
     untracked!(proc_macro_backtrace, true);
     untracked!(proc_macro_execution_strategy, ProcMacroExecutionStrategy::CrossThread);
     untracked!(query_dep_graph, true);
@@ -704,6 +717,7 @@ macro_rules! untracked {
     untracked!(unstable_options, true);
     untracked!(validate_mir, true);
     untracked!(verbose, true);
+    untracked!(write_lint_baseline, true);
 
     macro_rules! tracked {
         ($name: ident, $non_default_value: expr) => {
@@ -774,6 +788,7 @@ macro_rules! tracked {
     tracked!(polonius, true);
     tracked!(precise_enum_drop_elaboration, false);
     tracked!(print_fuel, Some("abc".to_string()));
+    tracked!(print_stack_usage, true);
     tracked!(profile, true);
     tracked!(profile_emit, Some(PathBuf::from("abc")));
     tracked!(profiler_runtime, "abc".to_string());
@@ -791,6 +806,7 @@ macro_rules! tracked {
     tracked!(simulate_remapped_rust_src_base, Some(PathBuf::from("/rustc/abc")));
     tracked!(src_hash_algorithm, Some(SourceFileHashAlgorithm::Sha1));
     tracked!(stack_protector, StackProtector::All);
+    tracked!(stack_usage_limit, Some(4096));
     tracked!(symbol_mangling_version, Some(SymbolManglingVersion::V0));
     tracked!(teach, true);
     tracked!(thinlto, Some(true));
@@ -256,6 +256,10 @@ pub fn cstore(&self) -> &CStore {
         &self.cstore
     }
 
+    pub fn local_crate_name(&self) -> Symbol {
+        self.local_crate_name
+    }
+
     pub fn into_cstore(self) -> CStore {
         self.cstore
     }
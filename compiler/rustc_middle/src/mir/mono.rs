@@ -418,6 +418,29 @@ fn hash_stable(&self, hcx: &mut StableHashingContext<'a>, hasher: &mut StableHas
     }
 }
 
+/// The direct calls between the functions that are codegened in the local crate, which the stack
+/// usage analysis of `-Z print-stack-usage` and `-Z stack-usage-limit` combines with the frame
+/// sizes LLVM reports. Functions are identified by their symbol name, since that is all the
+/// object files know about them.
+#[derive(Debug, Default)]
+pub struct CallGraph<'tcx> {
+    pub functions: FxHashMap<SymbolName<'tcx>, CallGraphFunction<'tcx>>,
+}
+
+#[derive(Debug)]
+pub struct CallGraphFunction<'tcx> {
+    pub instance: Instance<'tcx>,
+    /// Whether the function is codegened in the local crate. The callees of other functions,
+    /// which are only in the graph because a local function calls them, aren't known.
+    pub is_local: bool,
+    /// Whether the analysis reports the function on its own: the crate's entry function, and
+    /// exported functions that no other local function calls.
+    pub is_entry_point: bool,
+    pub callees: Vec<SymbolName<'tcx>>,
+    /// The first call through a function pointer or a trait object, whose callee isn't known.
+    pub indirect_call: Option<Span>,
+}
+
 pub struct CodegenUnitNameBuilder<'tcx> {
     tcx: TyCtxt<'tcx>,
     cache: FxHashMap<CrateNum, String>,
@@ -1770,6 +1770,13 @@
     query codegen_unit(_: Symbol) -> &'tcx CodegenUnit<'tcx> {
         desc { "codegen_unit" }
     }
+
+    /// The calls between the functions of all codegen units, for `-Z print-stack-usage`.
+    query call_graph(_: ()) -> &'tcx CallGraph<'tcx> {
+        eval_always
+        no_hash
+        desc { "building the call graph of the crate's functions" }
+    }
     query unused_generic_params(key: ty::InstanceDef<'tcx>) -> FiniteBitSet<u32> {
         cache_on_disk_if { key.def_id().is_local() }
         desc {
@@ -10,7 +10,7 @@
 use rustc_hir::lang_items::LangItem;
 use rustc_index::bit_set::BitSet;
 use rustc_index::vec::{Idx, IndexVec};
-use rustc_session::{config::OptLevel, DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+use rustc_session::{config::OptLevel, DataTypeKind, FieldInfo, NicheInfo, SizeKind, VariantInfo};
 use rustc_span::symbol::Symbol;
 use rustc_span::{Span, DUMMY_SP};
 use rustc_target::abi::call::{
@@ -1924,7 +1924,7 @@ fn generator_layout(
     fn record_layout_for_printing(&self, layout: TyAndLayout<'tcx>) {
         // If we are running with `-Zprint-type-sizes`, maybe record layouts
         // for dumping later.
-        if self.tcx.sess.opts.unstable_opts.print_type_sizes {
+        if self.tcx.sess.opts.unstable_opts.print_type_sizes.is_some() {
             self.record_layout_for_printing_outlined(layout)
         }
     }
@@ -1937,8 +1937,14 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
             return;
         }
 
+        let opt_niche = layout.largest_niche.map(|niche| NicheInfo {
+            offset: niche.offset.bytes(),
+            size: niche.value.size(self).bytes(),
+            available: niche.available(self),
+        });
+
         // (delay format until we actually need it)
-        let record = |kind, packed, opt_discr_size, variants| {
+        let record = |kind, packed, opt_discr_size, variants, def_id| {
             let type_desc = format!("{:?}", layout.ty);
             self.tcx.sess.code_stats.record_type_size(
                 kind,
@@ -1947,7 +1953,9 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                 layout.size,
                 packed,
                 opt_discr_size,
+                opt_niche,
                 variants,
+                Some(self.tcx.def_span(def_id)),
             );
         };
 
@@ -1957,9 +1965,9 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                 adt_def
             }
 
-            ty::Closure(..) => {
+            ty::Closure(def_id, _) => {
                 debug!("print-type-size t: `{:?}` record closure", layout.ty);
-                record(DataTypeKind::Closure, false, None, vec![]);
+                record(DataTypeKind::Closure, false, None, vec![], def_id);
                 return;
             }
 
@@ -1971,6 +1979,7 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
 
         let adt_kind = adt_def.adt_kind();
         let adt_packed = adt_def.repr().pack.is_some();
+        let adt_did = adt_def.did();
 
         let build_variant_info = |n: Option<Symbol>, flds: &[Symbol], layout: TyAndLayout<'tcx>| {
             let mut min_size = Size::ZERO;
@@ -2017,11 +2026,12 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                         adt_packed,
                         None,
                         vec![build_variant_info(Some(variant_def.name), &fields, layout)],
+                        adt_did,
                     );
                 } else {
                     // (This case arises for *empty* enums; so give it
                     // zero variants.)
-                    record(adt_kind.into(), adt_packed, None, vec![]);
+                    record(adt_kind.into(), adt_packed, None, vec![], adt_did);
                 }
             }
 
@@ -2051,6 +2061,7 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                         _ => None,
                     },
                     variant_infos,
+                    adt_did,
                 );
             }
         }
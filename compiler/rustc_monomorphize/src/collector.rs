@@ -225,6 +225,10 @@ pub struct InliningMap<'tcx> {
     // Contains one bit per mono item in the `targets` field. That bit
     // is true if that mono item needs to be inlined into every CGU.
     inlines: GrowableBitSet<usize>,
+
+    // The items the graph walk started from, i.e. the ones that are
+    // instantiated without being referenced by another mono item.
+    roots: Vec<MonoItem<'tcx>>,
 }
 
 /// Struct to store mono items in each collecting and if they should
@@ -271,6 +275,7 @@ fn new() -> InliningMap<'tcx> {
             index: FxHashMap::default(),
             targets: Vec::new(),
             inlines: GrowableBitSet::with_capacity(1024),
+            roots: Vec::new(),
         }
     }
 
@@ -323,6 +328,24 @@ pub fn iter_accesses<F>(&self, mut f: F)
             f(accessor, &self.targets[range.clone()])
         }
     }
+
+    // All items referenced by `source`, each with whether it will be made
+    // available for inlining.
+    pub fn accesses(
+        &self,
+        source: MonoItem<'tcx>,
+    ) -> impl Iterator<Item = (MonoItem<'tcx>, bool)> + '_ {
+        let range = self.index.get(&source).cloned().unwrap_or(0..0);
+        let start = range.start;
+        self.targets[range]
+            .iter()
+            .enumerate()
+            .map(move |(i, &target)| (target, self.inlines.contains(start + i)))
+    }
+
+    pub fn roots(&self) -> &[MonoItem<'tcx>] {
+        &self.roots
+    }
 }
 
 #[instrument(skip(tcx, mode), level = "debug")]
@@ -339,6 +362,7 @@ pub fn collect_crate_mono_items(
 
     let mut visited = MTLock::new(FxHashSet::default());
     let mut inlining_map = MTLock::new(InliningMap::new());
+    inlining_map.get_mut().roots = roots.clone();
     let recursion_limit = tcx.recursion_limit();
 
     {
@@ -938,6 +962,72 @@ fn visit_local(
     }
 }
 
+/// Returns the functions `instance` calls directly, and the first of its calls whose callee isn't
+/// known statically, for the call graph of `-Z print-stack-usage`. Unlike the neighbors the
+/// collector records, the callees include functions of upstream crates, and they exclude functions
+/// that are only referenced, like the methods of a vtable or a function that is turned into a
+/// function pointer.
+pub(crate) fn collect_calls<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+) -> (Vec<Instance<'tcx>>, Option<Span>) {
+    let body = tcx.instance_mir(instance.def);
+    let param_env = ty::ParamEnv::reveal_all();
+    let monomorphize =
+        |ty: Ty<'tcx>| instance.subst_mir_and_normalize_erasing_regions(tcx, param_env, ty);
+
+    let mut callees = Vec::new();
+    let mut indirect_call = None;
+    for block in body.basic_blocks() {
+        let terminator = block.terminator();
+        let source = terminator.source_info.span;
+        match terminator.kind {
+            mir::TerminatorKind::Call { ref func, .. } => {
+                let callee_ty = monomorphize(func.ty(body, tcx));
+                let ty::FnDef(def_id, substs) = *callee_ty.kind() else {
+                    // A call through a function pointer.
+                    indirect_call = indirect_call.or(Some(source));
+                    continue;
+                };
+                let callee = Instance::resolve(tcx, param_env, def_id, substs).unwrap().unwrap();
+                match callee.def {
+                    ty::InstanceDef::Virtual(..) => indirect_call = indirect_call.or(Some(source)),
+                    // Intrinsics are codegened in place.
+                    ty::InstanceDef::Intrinsic(_) => {}
+                    _ => callees.push(callee),
+                }
+            }
+            mir::TerminatorKind::Drop { ref place, .. }
+            | mir::TerminatorKind::DropAndReplace { ref place, .. } => {
+                let ty = monomorphize(place.ty(body, tcx).ty);
+                if let ty::Dynamic(..) = ty.kind() {
+                    // Trait objects are dropped through the drop glue in their vtable.
+                    indirect_call = indirect_call.or(Some(source));
+                    continue;
+                }
+                let callee = Instance::resolve_drop_in_place(tcx, ty);
+                // Don't record noop drop glue, which isn't called.
+                if let ty::InstanceDef::DropGlue(_, Some(_)) = callee.def {
+                    callees.push(callee);
+                }
+            }
+            mir::TerminatorKind::Assert { ref msg, .. } => {
+                let lang_item = match msg {
+                    mir::AssertKind::BoundsCheck { .. } => LangItem::PanicBoundsCheck,
+                    _ => LangItem::Panic,
+                };
+                callees.push(Instance::mono(tcx, tcx.require_lang_item(lang_item, Some(source))));
+            }
+            mir::TerminatorKind::Abort { .. } => {
+                let lang_item = tcx.require_lang_item(LangItem::PanicNoUnwind, Some(source));
+                callees.push(Instance::mono(tcx, lang_item));
+            }
+            _ => {}
+        }
+    }
+    (callees, indirect_call)
+}
+
 fn visit_drop_use<'tcx>(
     tcx: TyCtxt<'tcx>,
     ty: Ty<'tcx>,
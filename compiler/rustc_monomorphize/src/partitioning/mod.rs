@@ -95,16 +95,21 @@
 mod default;
 mod merging;
 
+use std::collections::VecDeque;
+use std::path::Path;
+
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync;
-use rustc_hir::def_id::DefIdSet;
+use rustc_hir::def_id::{DefIdSet, LOCAL_CRATE};
 use rustc_middle::mir;
-use rustc_middle::mir::mono::MonoItem;
-use rustc_middle::mir::mono::{CodegenUnit, Linkage};
+use rustc_middle::mir::mono::{CallGraph, CallGraphFunction, MonoItem};
+use rustc_middle::mir::mono::{CodegenUnit, InstantiationMode, Linkage};
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::query::Providers;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{InstanceDef, TyCtxt};
+use rustc_session::config::SwitchWithOptPath;
 use rustc_span::symbol::Symbol;
+use serde_json::json;
 
 use crate::collector::InliningMap;
 use crate::collector::{self, MonoItemCollectionMode};
@@ -414,6 +419,15 @@ fn collect_and_partition_mono_items<'tcx>(
         })
         .collect();
 
+    if let SwitchWithOptPath::Enabled(ref path) = tcx.sess.opts.unstable_opts.dump_mono_items {
+        let output_directory = path.as_deref().unwrap_or_else(|| Path::new("."));
+        if let Err(err) =
+            dump_mono_items_json(tcx, output_directory, &items, &inlining_map, codegen_units)
+        {
+            tcx.sess.err(&format!("failed to dump mono items: {}", err));
+        }
+    }
+
     if tcx.sess.opts.unstable_opts.print_mono_items.is_some() {
         let mut item_to_cgus: FxHashMap<_, Vec<_>> = Default::default();
 
@@ -468,6 +482,132 @@ fn collect_and_partition_mono_items<'tcx>(
     (tcx.arena.alloc(mono_items), codegen_units)
 }
 
+/// Writes `<crate>.mono_items.json` to `output_directory`, for `-Zdump-mono-items`.
+///
+/// For every mono item this records where it was placed, which items reference it, and the chain
+/// of references that first led the collector to it from one of the roots.
+fn dump_mono_items_json<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    output_directory: &Path,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    inlining_map: &InliningMap<'tcx>,
+    codegen_units: &[CodegenUnit<'tcx>],
+) -> std::io::Result<()> {
+    let item_name = |item: &MonoItem<'tcx>| with_no_trimmed_paths!(item.to_string());
+
+    let mut item_to_cgus: FxHashMap<_, Vec<_>> = Default::default();
+    for cgu in codegen_units {
+        for (&mono_item, &(linkage, visibility)) in cgu.items() {
+            item_to_cgus.entry(mono_item).or_default().push(json!({
+                "cgu": cgu.name().as_str(),
+                "linkage": format!("{:?}", linkage),
+                "visibility": format!("{:?}", visibility),
+            }));
+        }
+    }
+
+    // Invert the access graph, so each item knows what referenced it.
+    let mut accessed_by: FxHashMap<MonoItem<'tcx>, Vec<(MonoItem<'tcx>, bool)>> =
+        Default::default();
+    inlining_map.iter_accesses(|accessor, _| {
+        for (target, inlined) in inlining_map.accesses(accessor) {
+            accessed_by.entry(target).or_default().push((accessor, inlined));
+        }
+    });
+
+    // Breadth-first search from the roots, remembering the first item through which each item
+    // was reached, so that we can explain why it was instantiated with a shortest chain.
+    let roots: FxHashSet<_> = inlining_map.roots().iter().copied().collect();
+    let mut reached_from: FxHashMap<MonoItem<'tcx>, Option<MonoItem<'tcx>>> =
+        roots.iter().map(|&root| (root, None)).collect();
+    let mut queue: VecDeque<_> = inlining_map.roots().iter().copied().collect();
+    while let Some(item) = queue.pop_front() {
+        for (target, _) in inlining_map.accesses(item) {
+            if !reached_from.contains_key(&target) {
+                reached_from.insert(target, Some(item));
+                queue.push_back(target);
+            }
+        }
+    }
+    let instantiation_chain = |item: MonoItem<'tcx>| {
+        let mut chain = vec![item_name(&item)];
+        let mut current = item;
+        while let Some(&Some(parent)) = reached_from.get(&current) {
+            chain.push(item_name(&parent));
+            current = parent;
+        }
+        chain.reverse();
+        chain
+    };
+
+    let mut mono_items: Vec<_> = items
+        .iter()
+        .map(|item| {
+            let kind = match item {
+                MonoItem::Fn(_) => "fn",
+                MonoItem::Static(_) => "static",
+                MonoItem::GlobalAsm(_) => "global_asm",
+            };
+            let instantiation_mode = match item.instantiation_mode(tcx) {
+                InstantiationMode::GloballyShared { may_conflict: false } => "globally_shared",
+                InstantiationMode::GloballyShared { may_conflict: true } => {
+                    "globally_shared_may_conflict"
+                }
+                InstantiationMode::LocalCopy => "local_copy",
+            };
+            let mut referenced_by: Vec<_> = accessed_by
+                .get(item)
+                .map(|accessors| {
+                    accessors
+                        .iter()
+                        .map(|(accessor, inlined)| {
+                            json!({ "item": item_name(accessor), "inlined": inlined })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            referenced_by.sort_by_key(|edge| edge["item"].to_string());
+            (
+                item_name(item),
+                json!({
+                    "name": item_name(item),
+                    "kind": kind,
+                    "size_estimate": item.size_estimate(tcx),
+                    "instantiation_mode": instantiation_mode,
+                    "is_root": roots.contains(item),
+                    "placements": item_to_cgus.remove(item).unwrap_or_default(),
+                    "referenced_by": referenced_by,
+                    "instantiation_chain": instantiation_chain(*item),
+                }),
+            )
+        })
+        .collect();
+    mono_items.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
+
+    let codegen_units: Vec<_> = codegen_units
+        .iter()
+        .map(|cgu| {
+            json!({
+                "name": cgu.name().as_str(),
+                "size_estimate": cgu.size_estimate(),
+                "primary": cgu.is_primary(),
+                "item_count": cgu.items().len(),
+            })
+        })
+        .collect();
+
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    let json = json!({
+        "crate": crate_name.as_str(),
+        "codegen_units": codegen_units,
+        "mono_items": mono_items.into_iter().map(|(_, item)| item).collect::<Vec<_>>(),
+    });
+
+    std::fs::create_dir_all(output_directory)?;
+    let path = output_directory.join(&format!("{}.mono_items.json", crate_name));
+    std::fs::write(path, serde_json::to_string_pretty(&json)?)
+}
+
 fn codegened_and_inlined_items<'tcx>(tcx: TyCtxt<'tcx>, (): ()) -> &'tcx DefIdSet {
     let (items, cgus) = tcx.collect_and_partition_mono_items(());
     let mut visited = DefIdSet::default();
@@ -497,9 +637,74 @@ fn codegened_and_inlined_items<'tcx>(tcx: TyCtxt<'tcx>, (): ()) -> &'tcx DefIdSe
     tcx.arena.alloc(result)
 }
 
+fn call_graph<'tcx>(tcx: TyCtxt<'tcx>, (): ()) -> &'tcx CallGraph<'tcx> {
+    let (_, cgus) = tcx.collect_and_partition_mono_items(());
+    let entry_fn = tcx.entry_fn(()).map(|(def_id, _)| def_id);
+
+    let mut graph = CallGraph::default();
+    let mut exported = FxHashSet::default();
+    for cgu in cgus {
+        for (&item, &(linkage, _)) in cgu.items() {
+            let MonoItem::Fn(instance) = item else { continue };
+            let symbol_name = tcx.symbol_name(instance);
+            if linkage == Linkage::External {
+                exported.insert(symbol_name);
+            }
+            graph.functions.entry(symbol_name).or_insert_with(|| CallGraphFunction {
+                instance,
+                is_local: true,
+                is_entry_point: entry_fn == Some(instance.def_id())
+                    && matches!(instance.def, InstanceDef::Item(_)),
+                callees: Vec::new(),
+                indirect_call: None,
+            });
+        }
+    }
+
+    // Local functions that are called by other local functions aren't entry points, even if they
+    // are exported.
+    let mut called = FxHashSet::default();
+    let local_functions: Vec<_> = graph
+        .functions
+        .iter()
+        .map(|(&symbol_name, function)| (symbol_name, function.instance))
+        .collect();
+    for (symbol_name, instance) in local_functions {
+        let (callees, indirect_call) = collector::collect_calls(tcx, instance);
+        let callees: Vec<_> = callees
+            .into_iter()
+            .map(|callee| {
+                let callee_symbol_name = tcx.symbol_name(callee);
+                if callee_symbol_name != symbol_name {
+                    called.insert(callee_symbol_name);
+                }
+                graph.functions.entry(callee_symbol_name).or_insert_with(|| CallGraphFunction {
+                    instance: callee,
+                    is_local: false,
+                    is_entry_point: false,
+                    callees: Vec::new(),
+                    indirect_call: None,
+                });
+                callee_symbol_name
+            })
+            .collect();
+        let function = graph.functions.get_mut(&symbol_name).unwrap();
+        function.callees = callees;
+        function.indirect_call = indirect_call;
+    }
+    for symbol_name in exported {
+        if !called.contains(&symbol_name) {
+            graph.functions.get_mut(&symbol_name).unwrap().is_entry_point = true;
+        }
+    }
+
+    tcx.arena.alloc(graph)
+}
+
 pub fn provide(providers: &mut Providers) {
     providers.collect_and_partition_mono_items = collect_and_partition_mono_items;
     providers.codegened_and_inlined_items = codegened_and_inlined_items;
+    providers.call_graph = call_graph;
 
     providers.is_codegened_item = |tcx, def_id| {
         let (all_mono_items, _) = tcx.collect_and_partition_mono_items(());
@@ -10,11 +10,13 @@
 use rustc_serialize::opaque::{FileEncodeResult, FileEncoder};
 use smallvec::{smallvec, SmallVec};
 use std::assert_matches::assert_matches;
+use std::cell::Cell;
 use std::collections::hash_map::Entry;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::sync::atomic::Ordering::Relaxed;
+use std::time::{Duration, Instant};
 
 use super::query::DepGraphQuery;
 use super::serialized::{GraphEncoder, SerializedDepGraph, SerializedDepNodeIndex};
@@ -96,6 +98,47 @@ struct DepGraphData<K: DepKind> {
     /// a particular query result was decoded from disk
     /// (not just marked green)
     debug_loaded_from_disk: Lock<FxHashSet<DepNode<K>>>,
+
+    /// Why nodes of the previous session had to be re-executed, recorded
+    /// for `-Z incremental-explain`.
+    explanation: Option<Lock<ExplanationData>>,
+}
+
+#[derive(Default)]
+struct ExplanationData {
+    /// For each node of the previous session that could not be marked green,
+    /// the first dependency that prevented it.
+    blocked_by: FxHashMap<SerializedDepNodeIndex, SerializedDepNodeIndex>,
+    /// The nodes of the previous session that were re-executed, together with
+    /// the time spent in their task, excluding the tasks nested in it.
+    reexecuted: Vec<(SerializedDepNodeIndex, Duration)>,
+}
+
+thread_local! {
+    /// The time spent in the tasks nested in the task that is currently being
+    /// executed on this thread, when recording an explanation.
+    static NESTED_TASK_TIME: Cell<Duration> = Cell::new(Duration::ZERO);
+}
+
+/// Why the nodes of the previous session were re-executed in this one, for
+/// `-Z incremental-explain`.
+pub struct IncrementalExplanation<K: DepKind> {
+    pub previous_node_count: usize,
+    pub green_node_count: usize,
+    pub reexecuted: Vec<ReexecutedNode<K>>,
+}
+
+pub struct ReexecutedNode<K: DepKind> {
+    pub node: DepNode<K>,
+    /// Whether the result of the node changed, i.e. whether it is red.
+    pub changed: bool,
+    /// The time spent executing the node, excluding the nodes it executed.
+    pub self_time: Duration,
+    /// The node whose change started the chain of dependencies that could not
+    /// be marked green. This is either a node that changed without any of its
+    /// dependencies changing, such as a HIR owner, a node that no longer
+    /// exists, or the re-executed node itself.
+    pub root_cause: DepNode<K>,
 }
 
 pub fn hash_result<R>(hcx: &mut StableHashingContext<'_>, result: &R) -> Fingerprint
@@ -115,6 +158,7 @@ pub fn new(
         encoder: FileEncoder,
         record_graph: bool,
         record_stats: bool,
+        record_explanation: bool,
     ) -> DepGraph<K> {
         let prev_graph_node_count = prev_graph.node_count();
 
@@ -158,6 +202,7 @@ pub fn new(
                 previous: prev_graph,
                 colors,
                 debug_loaded_from_disk: Default::default(),
+                explanation: record_explanation.then(Default::default),
             })),
             virtual_dep_node_index: Lrc::new(AtomicU32::new(0)),
         }
@@ -339,7 +384,17 @@ fn with_task_impl<Ctxt: HasDepContext<DepKind = K>, A: Debug, R>(
             None => TaskDepsRef::Ignore,
         };
 
-        let result = K::with_deps(task_deps_ref, || task(cx, arg));
+        let (result, self_time) = if data.explanation.is_some() {
+            let outer_nested_time = NESTED_TASK_TIME.with(|nested| nested.replace(Duration::ZERO));
+            let start = Instant::now();
+            let result = K::with_deps(task_deps_ref, || task(cx, arg));
+            let elapsed = start.elapsed();
+            let nested_time =
+                NESTED_TASK_TIME.with(|nested| nested.replace(outer_nested_time + elapsed));
+            (result, elapsed.saturating_sub(nested_time))
+        } else {
+            (K::with_deps(task_deps_ref, || task(cx, arg)), Duration::ZERO)
+        };
         let edges = task_deps.map_or_else(|| smallvec![], |lock| lock.into_inner().reads);
 
         let dcx = cx.dep_context();
@@ -370,6 +425,14 @@ fn with_task_impl<Ctxt: HasDepContext<DepKind = K>, A: Debug, R>(
             );
 
             data.colors.insert(prev_index, color);
+
+            // Nodes that are always evaluated are not re-executed because of
+            // a change.
+            if let Some(explanation) = &data.explanation {
+                if !dcx.is_eval_always(key.kind) {
+                    explanation.lock().reexecuted.push((prev_index, self_time));
+                }
+            }
         }
 
         (result, dep_node_index)
@@ -733,7 +796,16 @@ fn try_mark_previous_green<Ctxt: QueryContext<DepKind = K>>(
         let prev_deps = data.previous.edge_targets_from(prev_dep_node_index);
 
         for &dep_dep_node_index in prev_deps {
-            self.try_mark_parent_green(tcx, data, dep_dep_node_index, dep_node)?
+            if self.try_mark_parent_green(tcx, data, dep_dep_node_index, dep_node).is_none() {
+                if let Some(explanation) = &data.explanation {
+                    explanation
+                        .lock()
+                        .blocked_by
+                        .entry(prev_dep_node_index)
+                        .or_insert(dep_dep_node_index);
+                }
+                return None;
+            }
         }
 
         // If we got here without hitting a `return` that means that all
@@ -843,6 +915,53 @@ pub fn exec_cache_promotions<Ctxt: DepContext<DepKind = K>>(&self, tcx: Ctxt) {
         }
     }
 
+    /// Explains why nodes of the previous session were re-executed, if
+    /// `-Z incremental-explain` is enabled. Nodes that are always evaluated
+    /// are not included.
+    pub fn explain<Ctxt: DepContext<DepKind = K>>(
+        &self,
+        tcx: Ctxt,
+    ) -> Option<IncrementalExplanation<K>> {
+        let data = self.data.as_ref()?;
+        let explanation = data.explanation.as_ref()?.lock();
+
+        let green_node_count = data
+            .colors
+            .values
+            .indices()
+            .filter(|&prev_index| data.colors.get(prev_index).map_or(false, |c| c.is_green()))
+            .count();
+        let reexecuted = explanation
+            .reexecuted
+            .iter()
+            .map(|&(prev_index, self_time)| {
+                // Follow the chain of blocking dependencies down to the node
+                // that changed. Nodes that are always evaluated, like the one
+                // of the whole crate's HIR, change whenever anything changes,
+                // so the node that read them is the more specific answer.
+                let mut root = prev_index;
+                while let Some(&blocker) = explanation.blocked_by.get(&root) {
+                    if tcx.is_eval_always(data.previous.index_to_node(blocker).kind) {
+                        break;
+                    }
+                    root = blocker;
+                }
+                ReexecutedNode {
+                    node: data.previous.index_to_node(prev_index),
+                    changed: data.colors.get(prev_index) == Some(DepNodeColor::Red),
+                    self_time,
+                    root_cause: data.previous.index_to_node(root),
+                }
+            })
+            .collect();
+
+        Some(IncrementalExplanation {
+            previous_node_count: data.previous.node_count(),
+            green_node_count,
+            reexecuted,
+        })
+    }
+
     pub fn print_incremental_info(&self) {
         if let Some(data) = &self.data {
             data.current.encoder.borrow().print_incremental_info(
@@ -6,7 +6,8 @@
 
 pub use dep_node::{DepNode, DepNodeParams, WorkProductId};
 pub use graph::{
-    hash_result, DepGraph, DepNodeColor, DepNodeIndex, TaskDeps, TaskDepsRef, WorkProduct,
+    hash_result, DepGraph, DepNodeColor, DepNodeIndex, IncrementalExplanation, ReexecutedNode,
+    TaskDeps, TaskDepsRef, WorkProduct,
 };
 pub use query::DepGraphQuery;
 pub use serialized::{SerializedDepGraph, SerializedDepNodeIndex};
@@ -0,0 +1,293 @@
+//! Writes the results of name resolution for `-Z dump-resolutions`.
+//!
+//! For every module of the crate the dump lists its imports: what each of
+//! them resolved to in every namespace, the re-exports the resolution went
+//! through, the names glob imports brought into scope and whether the import
+//! is used. Imports written by macros are listed in the module they were
+//! expanded into. The dump also lists the declared and the effective
+//! visibility of every item, the latter as computed by
+//! [`AccessLevelsVisitor`](crate::access_levels::AccessLevelsVisitor).
+
+use crate::imports::{Import, ImportKind};
+use crate::{names_to_string, Module, ModuleKind, ModuleOrUniformRoot, NameBinding};
+use crate::{NameBindingKind, Resolver};
+
+use rustc_hir::def::Namespace::{self, *};
+use rustc_hir::def_id::{DefId, CRATE_DEF_ID};
+use rustc_middle::middle::privacy::AccessLevel;
+use rustc_middle::ty::{self, DefIdTree};
+use rustc_session::cstore::CrateStore;
+use rustc_span::symbol::kw;
+use rustc_span::Span;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+impl<'a> Resolver<'a> {
+    pub(crate) fn dump_resolutions(&mut self) {
+        let directory = match self.session.opts.unstable_opts.dump_resolutions_dir {
+            Some(ref directory) => directory.clone(),
+            None => PathBuf::from("."),
+        };
+        let json = self.resolutions_to_json();
+        if let Err(err) = self.write_resolutions(&directory, &json) {
+            self.session.err(&format!("failed to write resolutions: {}", err));
+        }
+    }
+
+    fn write_resolutions(&self, directory: &Path, json: &Value) -> io::Result<()> {
+        let file_name = format!("{}.resolutions.json", self.crate_loader.local_crate_name());
+        fs::create_dir_all(directory)?;
+        fs::write(directory.join(file_name), serde_json::to_string_pretty(json)?)
+    }
+
+    fn resolutions_to_json(&mut self) -> Value {
+        // Imports are listed in the `mod`, enum or trait they are in, even if
+        // they are inside of a block.
+        let mut imports: BTreeMap<DefId, Vec<&'a Import<'a>>> = BTreeMap::new();
+        for &import in self.finalized_imports.iter().chain(&self.potentially_unused_imports) {
+            let module = nearest_def_module(import.parent_scope.module);
+            imports.entry(module.def_id()).or_default().push(import);
+        }
+
+        let mut modules = Vec::new();
+        for &module in self.arenas.local_modules().iter() {
+            let ModuleKind::Def(kind, def_id, _) = module.kind else { continue };
+            let mut module_imports = imports.remove(&def_id).unwrap_or_default();
+            module_imports.sort_by_key(|import| import.span);
+            let imports: Vec<_> =
+                module_imports.into_iter().map(|import| self.import_to_json(import)).collect();
+            modules.push(json!({
+                "path": self.def_path_string(def_id),
+                "kind": kind.descr(def_id),
+                "location": self.location_to_json(module.span),
+                "imports": imports,
+            }));
+        }
+
+        let visibilities: Vec<_> =
+            self.visibilities.iter().map(|(&def_id, &vis)| (def_id, vis)).collect();
+        let mut items: Vec<_> = visibilities
+            .into_iter()
+            .map(|(local_def_id, vis)| {
+                let def_id = local_def_id.to_def_id();
+                let parent_mod = self.parent_mod(def_id);
+                let access_level = self.access_levels.map.get(&local_def_id);
+                json!({
+                    "path": self.def_path_string(def_id),
+                    "location": self.opt_span(def_id).map(|span| self.location_to_json(span)),
+                    "visibility": self.visibility_to_string(vis, parent_mod),
+                    "effective_visibility": access_level.map(|level| match level {
+                        AccessLevel::Public => "public",
+                        AccessLevel::Exported => "exported",
+                        AccessLevel::Reachable | AccessLevel::ReachableFromImplTrait => {
+                            "reachable"
+                        }
+                    }),
+                })
+            })
+            .collect();
+        items.sort_by(|a, b| a["path"].as_str().cmp(&b["path"].as_str()));
+
+        json!({
+            "crate": self.crate_loader.local_crate_name().as_str(),
+            "modules": modules,
+            "items": items,
+        })
+    }
+
+    fn import_to_json(&mut self, import: &'a Import<'a>) -> Value {
+        let module = import.parent_scope.module;
+        let def_id = self.local_def_id(import.id);
+        let used = import.used.get() || self.used_imports.contains(&import.id);
+        let status = if used {
+            "used"
+        } else if self.maybe_unused_trait_imports.contains(&def_id) {
+            // Whether the trait is used by method calls is only known after
+            // type checking.
+            "unknown"
+        } else {
+            "unused"
+        };
+        let mut json = json!({
+            "path": import_to_string(import),
+            "location": self.location_to_json(import.span),
+            "from_macro": import.span.from_expansion(),
+            "visibility": self.visibility_to_string(import.vis.get(), module.nearest_parent_mod()),
+            "status": status,
+        });
+
+        match import.kind {
+            ImportKind::Single { ref source_bindings, .. } => {
+                json["kind"] = json!("single");
+                let mut resolutions = serde_json::Map::new();
+                for ns in [TypeNS, ValueNS, MacroNS] {
+                    if let Ok(binding) = source_bindings[ns].get() {
+                        let ns = namespace_to_str(ns).to_string();
+                        resolutions.insert(ns, self.binding_to_json(binding));
+                    }
+                }
+                json["resolutions"] = Value::Object(resolutions);
+            }
+            ImportKind::Glob { is_prelude, .. } => {
+                json["kind"] = json!(if is_prelude { "prelude" } else { "glob" });
+                json["module"] = match import.imported_module.get() {
+                    Some(ModuleOrUniformRoot::Module(module)) => {
+                        json!(self.def_path_string(module.def_id()))
+                    }
+                    _ => Value::Null,
+                };
+                // The names the glob brought into scope, which includes names
+                // that aren't used.
+                let mut names = Vec::new();
+                for (key, resolution) in self.resolutions(module).borrow().iter() {
+                    let Some(binding) = resolution.borrow().binding else { continue };
+                    let NameBindingKind::Import { import: glob, binding: source, .. } =
+                        binding.kind
+                    else {
+                        continue;
+                    };
+                    if ptr::eq(glob, import) {
+                        let mut name = self.binding_to_json(source);
+                        name["name"] = json!(key.ident.as_str());
+                        name["namespace"] = json!(namespace_to_str(key.ns));
+                        names.push(name);
+                    }
+                }
+                json["names"] = json!(names);
+                let mut used_names: Vec<_> = self
+                    .glob_map
+                    .get(&def_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|name| name.as_str())
+                    .collect();
+                used_names.sort_unstable();
+                json["used_names"] = json!(used_names);
+            }
+            ImportKind::ExternCrate { .. } => {
+                json["kind"] = json!("extern_crate");
+                json["crate"] = match self.extern_crate_map.get(&def_id) {
+                    Some(&cnum) => json!(self.cstore().crate_name(cnum).as_str()),
+                    None => Value::Null,
+                };
+            }
+            ImportKind::MacroUse => json["kind"] = json!("macro_use"),
+        }
+        json
+    }
+
+    /// Describes what a binding resolves to, with the imports it was
+    /// re-exported through, starting with the binding's own import.
+    fn binding_to_json(&self, mut binding: &'a NameBinding<'a>) -> Value {
+        let mut through = Vec::new();
+        while let NameBindingKind::Import { binding: source, import, .. } = binding.kind {
+            let module = nearest_def_module(import.parent_scope.module);
+            through.push(json!({
+                "module": self.def_path_string(module.def_id()),
+                "path": import_to_string(import),
+                "location": self.location_to_json(import.span),
+            }));
+            binding = source;
+        }
+        let res = binding.res();
+        json!({
+            "kind": res.descr(),
+            "path": res.opt_def_id().map(|def_id| self.def_path_string(def_id)),
+            "through": through,
+        })
+    }
+
+    /// The `mod` item a definition is declared in, which is the scope of its
+    /// private visibility.
+    fn parent_mod(&mut self, def_id: DefId) -> DefId {
+        match self.opt_parent(def_id) {
+            Some(parent) => self.get_nearest_non_block_module(parent).nearest_parent_mod(),
+            None => def_id,
+        }
+    }
+
+    fn visibility_to_string(&self, vis: ty::Visibility, parent_mod: DefId) -> String {
+        match vis {
+            ty::Visibility::Public => "pub".to_string(),
+            ty::Visibility::Restricted(def_id) if def_id == parent_mod => "private".to_string(),
+            ty::Visibility::Restricted(def_id) if def_id == CRATE_DEF_ID.to_def_id() => {
+                "pub(crate)".to_string()
+            }
+            ty::Visibility::Restricted(def_id) => {
+                format!("pub(in {})", self.def_path_string(def_id))
+            }
+            ty::Visibility::Invisible => "invisible".to_string(),
+        }
+    }
+
+    /// The path of a definition, starting with `crate` for local definitions
+    /// and with the crate name otherwise.
+    fn def_path_string(&self, def_id: DefId) -> String {
+        match def_id.as_local() {
+            Some(def_id) => {
+                format!("crate{}", self.definitions.def_path(def_id).to_string_no_crate_verbose())
+            }
+            None => format!(
+                "{}{}",
+                self.cstore().crate_name(def_id.krate),
+                self.cstore().def_path(def_id).to_string_no_crate_verbose(),
+            ),
+        }
+    }
+
+    fn location_to_json(&self, span: Span) -> Value {
+        if span.is_dummy() {
+            return Value::Null;
+        }
+        let loc = self.session.source_map().lookup_char_pos(span.source_callsite().lo());
+        json!({
+            "file": loc.file.name.prefer_remapped().to_string(),
+            "line": loc.line,
+            "column": loc.col.0 + 1,
+        })
+    }
+}
+
+/// The innermost module with a name, skipping blocks.
+fn nearest_def_module(mut module: Module<'_>) -> Module<'_> {
+    while let ModuleKind::Block = module.kind {
+        module = module.parent.expect("block module without a parent");
+    }
+    module
+}
+
+fn import_to_string(import: &Import<'_>) -> String {
+    let mut names: Vec<_> = import.module_path.iter().map(|segment| segment.ident.name).collect();
+    let last = match import.kind {
+        ImportKind::Single { source, target, .. } if source.name != target.name => {
+            format!("{} as {}", source, target)
+        }
+        ImportKind::Single { source, .. } => source.to_string(),
+        ImportKind::Glob { .. } => "*".to_string(),
+        ImportKind::ExternCrate { source: Some(source), target } => {
+            return format!("extern crate {} as {}", source, target);
+        }
+        ImportKind::ExternCrate { source: None, target } => {
+            return format!("extern crate {}", target);
+        }
+        ImportKind::MacroUse => return "#[macro_use]".to_string(),
+    };
+    names.retain(|name| *name != kw::PathRoot);
+    if names.is_empty() {
+        last
+    } else {
+        format!("{}::{}", names_to_string(&names), last)
+    }
+}
+
+fn namespace_to_str(ns: Namespace) -> &'static str {
+    match ns {
+        TypeNS => "type",
+        ValueNS => "value",
+        MacroNS => "macro",
+    }
+}
@@ -390,6 +390,10 @@ pub fn finalize_imports(&mut self) {
             // resolution for it so that later resolve stages won't complain.
             self.r.import_dummy_binding(import);
 
+            if self.r.session.opts.unstable_opts.dump_resolutions.is_some() {
+                self.r.finalized_imports.push(import);
+            }
+
             if let Some(err) = unresolved_import_error {
                 if let ImportKind::Single { source, ref source_bindings, .. } = import.kind {
                     if source.name == kw::SelfLower {
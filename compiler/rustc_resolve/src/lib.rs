@@ -74,6 +74,7 @@
 mod check_unused;
 mod def_collector;
 mod diagnostics;
+mod dump;
 mod ident;
 mod imports;
 mod late;
@@ -901,6 +902,9 @@ pub struct Resolver<'a> {
     /// All non-determined imports.
     indeterminate_imports: Vec<&'a Import<'a>>,
 
+    /// All imports after they were finalized, kept for `-Z dump-resolutions`.
+    finalized_imports: Vec<&'a Import<'a>>,
+
     // Spans for local variables found during pattern resolution.
     // Used for suggestions during error reporting.
     pat_span_map: NodeMap<Span>,
@@ -1271,6 +1275,7 @@ pub fn new(
 
             determined_imports: Vec::new(),
             indeterminate_imports: Vec::new(),
+            finalized_imports: Vec::new(),
 
             pat_span_map: Default::default(),
             partial_res_map: Default::default(),
@@ -1552,6 +1557,9 @@ pub fn resolve_crate(&mut self, krate: &Crate) {
             self.session.time("resolve_check_unused", || self.check_unused(krate));
             self.session.time("resolve_report_errors", || self.report_errors(krate));
             self.session.time("resolve_postprocess", || self.crate_loader.postprocess(krate));
+            if self.session.opts.unstable_opts.dump_resolutions.is_some() {
+                self.session.time("resolve_dump_resolutions", || self.dump_resolutions());
+            }
         });
     }
 
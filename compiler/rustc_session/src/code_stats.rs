@@ -1,8 +1,13 @@
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lock;
-use rustc_span::Symbol;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{Span, Symbol};
 use rustc_target::abi::{Align, Size};
+use serde_json::{json, Value};
 use std::cmp::{self, Ordering};
+use std::fs;
+use std::io;
+use std::path::Path;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -27,6 +32,16 @@ pub struct FieldInfo {
     pub align: u64,
 }
 
+/// The largest niche of a layout, i.e. the invalid values of a field that enums wrapping this
+/// type can use to store their discriminant.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NicheInfo {
+    pub offset: u64,
+    pub size: u64,
+    /// How many invalid values the niche has available.
+    pub available: u128,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum DataTypeKind {
     Struct,
@@ -43,12 +58,18 @@ pub struct TypeSizeInfo {
     pub overall_size: u64,
     pub packed: bool,
     pub opt_discr_size: Option<u64>,
+    pub opt_niche: Option<NicheInfo>,
     pub variants: Vec<VariantInfo>,
+    /// The definition of the type, if it has one.
+    pub span: Option<Span>,
 }
 
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    /// The number of samples of every stack of the compile-time interpreter, with the frames of
+    /// a stack joined by `;`.
+    const_eval_samples: Lock<FxHashMap<String, u64>>,
 }
 
 impl CodeStats {
@@ -60,7 +81,9 @@ pub fn record_type_size<S: ToString>(
         overall_size: Size,
         packed: bool,
         opt_discr_size: Option<Size>,
+        opt_niche: Option<NicheInfo>,
         mut variants: Vec<VariantInfo>,
+        span: Option<Span>,
     ) {
         // Sort variants so the largest ones are shown first. A stable sort is
         // used here so that source code order is preserved for all variants
@@ -73,12 +96,15 @@ pub fn record_type_size<S: ToString>(
             overall_size: overall_size.bytes(),
             packed,
             opt_discr_size: opt_discr_size.map(|s| s.bytes()),
+            opt_niche,
             variants,
+            span,
         };
         self.type_sizes.borrow_mut().insert(info);
     }
 
-    pub fn print_type_sizes(&self) {
+    /// Calls `f` with the recorded types, sorted from large to small.
+    fn with_sorted_type_sizes<R>(&self, f: impl FnOnce(Vec<&TypeSizeInfo>) -> R) -> R {
         let type_sizes = self.type_sizes.borrow();
         let mut sorted: Vec<_> = type_sizes.iter().collect();
 
@@ -92,6 +118,51 @@ pub fn print_type_sizes(&self) {
             }
         });
 
+        f(sorted)
+    }
+
+    /// Writes the recorded layouts to `path` as JSON, for `-Zprint-type-sizes=json`.
+    ///
+    /// The file holds an object with a `types` array, in the same order as the text output.
+    /// Each field carries the padding inserted before it, and each type the padding at its end,
+    /// computed the same way as for the text output.
+    pub fn write_type_sizes_json(&self, source_map: &SourceMap, path: &Path) -> io::Result<()> {
+        let types: Vec<Value> = self.with_sorted_type_sizes(|sorted| {
+            sorted.into_iter().map(|info| type_size_json(source_map, info)).collect()
+        });
+        let json = json!({ "types": types });
+        fs::write(path, serde_json::to_string_pretty(&json)?)
+    }
+
+    /// Records samples of the compile-time interpreter's stack for `-Zconst-eval-profile`. Every
+    /// stack lists its frames from the outermost one in.
+    pub fn record_const_eval_samples(&self, samples: impl IntoIterator<Item = (Vec<String>, u64)>) {
+        let mut const_eval_samples = self.const_eval_samples.lock();
+        for (stack, count) in samples {
+            // `;` separates the frames of folded stacks, but it is also part of array types.
+            let stack: Vec<_> = stack.iter().map(|frame| frame.replace(';', ":")).collect();
+            *const_eval_samples.entry(stack.join(";")).or_default() += count;
+        }
+    }
+
+    /// Writes the recorded const-eval samples to `path` in the folded stacks format that
+    /// flamegraph tools read: a line for every stack, followed by its number of samples.
+    pub fn write_const_eval_profile(&self, path: &Path) -> io::Result<()> {
+        let const_eval_samples = self.const_eval_samples.lock();
+        let mut stacks: Vec<_> = const_eval_samples.iter().collect();
+        stacks.sort();
+        let mut folded = String::new();
+        for (stack, count) in stacks {
+            folded.push_str(&format!("{} {}\n", stack, count));
+        }
+        fs::write(path, folded)
+    }
+
+    pub fn print_type_sizes(&self) {
+        self.with_sorted_type_sizes(Self::print_sorted_type_sizes)
+    }
+
+    fn print_sorted_type_sizes(sorted: Vec<&TypeSizeInfo>) {
         for info in sorted {
             let TypeSizeInfo { type_description, overall_size, align, kind, variants, .. } = info;
             println!(
@@ -180,3 +251,82 @@ pub fn print_type_sizes(&self) {
         }
     }
 }
+
+fn type_size_json(source_map: &SourceMap, info: &TypeSizeInfo) -> Value {
+    let discr_size = info.opt_discr_size.unwrap_or(0);
+    let mut max_variant_size = discr_size;
+
+    let variants: Vec<Value> = info
+        .variants
+        .iter()
+        .map(|variant| {
+            max_variant_size = cmp::max(max_variant_size, variant.size);
+
+            // Same ordering as the text output, see `print_type_sizes`.
+            let mut fields = variant.fields.clone();
+            fields.sort_by_key(|f| (f.offset, f.size));
+
+            let mut min_offset = discr_size;
+            let fields: Vec<Value> = fields
+                .iter()
+                .map(|field| {
+                    let padding = field.offset.saturating_sub(min_offset);
+                    min_offset = cmp::max(min_offset, field.offset + field.size);
+                    json!({
+                        "name": field.name.as_str(),
+                        "offset": field.offset,
+                        "size": field.size,
+                        "align": field.align,
+                        "padding_before": padding,
+                    })
+                })
+                .collect();
+
+            json!({
+                "name": variant.name.map(|name| name.to_string()),
+                "size": variant.size,
+                "align": variant.align,
+                "size_kind": match variant.kind {
+                    SizeKind::Exact => "exact",
+                    SizeKind::Min => "min",
+                },
+                "fields": fields,
+            })
+        })
+        .collect();
+
+    let span = info.span.map(|span| {
+        let lo = source_map.lookup_char_pos(span.lo());
+        let hi = source_map.lookup_char_pos(span.hi());
+        json!({
+            "file": lo.file.name.prefer_remapped().to_string(),
+            "line_start": lo.line,
+            "column_start": lo.col.0 + 1,
+            "line_end": hi.line,
+            "column_end": hi.col.0 + 1,
+        })
+    });
+
+    json!({
+        "type": info.type_description,
+        "kind": match info.kind {
+            DataTypeKind::Struct => "struct",
+            DataTypeKind::Union => "union",
+            DataTypeKind::Enum => "enum",
+            DataTypeKind::Closure => "closure",
+        },
+        "size": info.overall_size,
+        "align": info.align,
+        "packed": info.packed,
+        "discriminant_size": info.opt_discr_size,
+        "niche": info.opt_niche.map(|niche| json!({
+            "offset": niche.offset,
+            "size": niche.size,
+            // Serialized as a string, since JSON numbers can't represent all `u128` values.
+            "available": niche.available.to_string(),
+        })),
+        "variants": variants,
+        "end_padding": info.overall_size.saturating_sub(max_variant_size),
+        "span": span,
+    })
+}
@@ -140,6 +140,25 @@ pub enum MirSpanview {
     Block,
 }
 
+/// The different settings that the `-Z print-type-sizes` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum PrintTypeSizes {
+    /// Default `-Z print-type-sizes` or `-Z print-type-sizes=text`: print the layouts on stdout.
+    Text,
+    /// `-Z print-type-sizes=json`: write the layouts to `<crate>.type-sizes.json` in the output
+    /// directory.
+    Json,
+}
+
+/// The different settings that the `-Z apply-suggestions` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum ApplySuggestions {
+    /// Default `-Z apply-suggestions`: write the changed source files.
+    Write,
+    /// `-Z apply-suggestions=dry-run`: print the changes as a unified diff on stdout instead.
+    DryRun,
+}
+
 /// The different settings that the `-C instrument-coverage` flag can have.
 ///
 /// Coverage instrumentation now supports combining `-C instrument-coverage`
@@ -374,6 +393,8 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A single SARIF log, consumed by code scanning tools, written once compilation is done.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -791,6 +812,7 @@ pub fn diagnostic_handler_flags(&self, can_emit_warnings: bool) -> HandlerFlags
             report_delayed_bugs: self.report_delayed_bugs,
             macro_backtrace: self.macro_backtrace,
             deduplicate_diagnostics: self.deduplicate_diagnostics,
+            collect_suggestions: self.apply_suggestions.is_some(),
         }
     }
 }
@@ -1586,6 +1608,7 @@ pub fn parse_error_format(
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
+            Some("sarif") => ErrorOutputType::Sarif,
 
             Some(arg) => early_error(
                 ErrorOutputType::HumanReadable(HumanReadableErrorType::Default(color)),
@@ -1668,6 +1691,12 @@ fn check_error_format_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_error(
+                ErrorOutputType::Json { pretty: false, json_rendered },
+                "`--error-format=sarif` is unstable",
+            );
+        }
     }
 }
 
@@ -2763,7 +2792,7 @@ pub(crate) mod dep_tracking {
     };
     use std::collections::hash_map::DefaultHasher;
     use std::collections::BTreeMap;
-    use std::hash::Hash;
+    use std::hash::{Hash, Hasher};
     use std::num::NonZeroUsize;
     use std::path::PathBuf;
 
@@ -2936,6 +2965,26 @@ pub(crate) fn stable_hash(
             sub_hash.hash(hasher, error_format, for_crate_hash);
         }
     }
+
+    /// Hashes each option in `sub_hashes` on its own into `hashes`, keyed by
+    /// its name. Options of the `-C` and `-Z` groups are keyed by how they are
+    /// passed on the command line, e.g. `-C opt-level`.
+    pub(crate) fn option_hashes(
+        sub_hashes: BTreeMap<&'static str, &dyn DepTrackingHash>,
+        prefix: Option<&str>,
+        error_format: ErrorOutputType,
+        hashes: &mut BTreeMap<String, u64>,
+    ) {
+        for (key, sub_hash) in sub_hashes {
+            let mut hasher = DefaultHasher::new();
+            sub_hash.hash(&mut hasher, error_format, false);
+            let key = match prefix {
+                Some(prefix) => format!("-{} {}", prefix, key.replace('_', "-")),
+                None => key.to_owned(),
+            };
+            hashes.insert(key, hasher.finish());
+        }
+    }
 }
 
 /// Default behavior to use in out-of-memory situations.
@@ -2959,6 +3008,12 @@ pub fn should_panic(self) -> u8 {
     }
 }
 
+/// The output format of `-Z dump-resolutions`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum DumpResolutionsFormat {
+    Json,
+}
+
 /// How to run proc-macro code when building this crate
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
 pub enum ProcMacroExecutionStrategy {
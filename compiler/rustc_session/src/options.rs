@@ -59,6 +59,15 @@ macro_rules! hash_substruct {
     };
 }
 
+macro_rules! hash_substruct_options {
+    ($opt_name:ident, $opt_expr:expr, $error_format:expr, $hashes:expr, [UNTRACKED]) => {{}};
+    ($opt_name:ident, $opt_expr:expr, $error_format:expr, $hashes:expr, [TRACKED]) => {{}};
+    ($opt_name:ident, $opt_expr:expr, $error_format:expr, $hashes:expr, [TRACKED_NO_CRATE_HASH]) => {{}};
+    ($opt_name:ident, $opt_expr:expr, $error_format:expr, $hashes:expr, [SUBSTRUCT]) => {
+        $opt_expr.dep_tracking_hashes($error_format, $hashes);
+    };
+}
+
 macro_rules! top_level_options {
     ( $( #[$top_level_attr:meta] )* pub struct Options { $(
         $( #[$attr:meta] )*
@@ -98,6 +107,33 @@ pub fn dep_tracking_hash(&self, for_crate_hash: bool) -> u64 {
                 })*
                 hasher.finish()
             }
+
+            /// The hash of each tracked option on its own, keyed by its name. These
+            /// are used to tell which options changed between sessions.
+            pub fn dep_tracking_hashes(&self) -> BTreeMap<String, u64> {
+                let for_crate_hash = false;
+                let mut sub_hashes = BTreeMap::new();
+                $({
+                    hash_opt!($opt,
+                                &self.$opt,
+                                &mut sub_hashes,
+                                for_crate_hash,
+                                [$dep_tracking_marker]);
+                })*
+                let mut hashes = BTreeMap::new();
+                dep_tracking::option_hashes(sub_hashes,
+                                            None,
+                                            self.error_format,
+                                            &mut hashes);
+                $({
+                    hash_substruct_options!($opt,
+                        &self.$opt,
+                        self.error_format,
+                        &mut hashes,
+                        [$dep_tracking_marker]);
+                })*
+                hashes
+            }
         }
     );
 }
@@ -265,6 +301,23 @@ fn dep_tracking_hash(&self, for_crate_hash: bool, error_format: ErrorOutputType)
                                         );
             hasher.finish()
         }
+
+        fn dep_tracking_hashes(
+            &self,
+            error_format: ErrorOutputType,
+            hashes: &mut BTreeMap<String, u64>,
+        ) {
+            let for_crate_hash = false;
+            let mut sub_hashes = BTreeMap::new();
+            $({
+                hash_opt!($opt,
+                            &self.$opt,
+                            &mut sub_hashes,
+                            for_crate_hash,
+                            [$dep_tracking_marker]);
+            })*
+            dep_tracking::option_hashes(sub_hashes, Some($prefix), error_format, hashes);
+        }
     }
 
     pub const $stat: OptionDescrs<$struct_name> =
@@ -385,6 +438,8 @@ mod desc {
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
     pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
+    pub const parse_print_type_sizes: &str = "either no value, `text` (default), or `json`";
+    pub const parse_apply_suggestions: &str = "either no value, `write` (default), or `dry-run`";
     pub const parse_instrument_coverage: &str =
         "`all` (default), `except-unused-generics`, `except-unused-functions`, or `off`";
     pub const parse_unpretty: &str = "`string` or `string=string`";
@@ -416,6 +471,7 @@ mod desc {
         "a `,` separated combination of `bti`, `b-key`, `pac-ret`, or `leaf`";
     pub const parse_proc_macro_execution_strategy: &str =
         "one of supported execution strategies (`same-thread`, or `cross-thread`)";
+    pub const parse_dump_resolutions: &str = "`json`";
 }
 
 mod parse {
@@ -827,6 +883,46 @@ pub(crate) fn parse_mir_spanview(slot: &mut Option<MirSpanview>, v: Option<&str>
         true
     }
 
+    pub(crate) fn parse_print_type_sizes(
+        slot: &mut Option<PrintTypeSizes>,
+        v: Option<&str>,
+    ) -> bool {
+        if v.is_some() {
+            let mut bool_arg = None;
+            if parse_opt_bool(&mut bool_arg, v) {
+                *slot = if bool_arg.unwrap() { Some(PrintTypeSizes::Text) } else { None };
+                return true;
+            }
+        }
+
+        *slot = Some(match v {
+            None | Some("text") => PrintTypeSizes::Text,
+            Some("json") => PrintTypeSizes::Json,
+            Some(_) => return false,
+        });
+        true
+    }
+
+    pub(crate) fn parse_apply_suggestions(
+        slot: &mut Option<ApplySuggestions>,
+        v: Option<&str>,
+    ) -> bool {
+        if v.is_some() {
+            let mut bool_arg = None;
+            if parse_opt_bool(&mut bool_arg, v) {
+                *slot = if bool_arg.unwrap() { Some(ApplySuggestions::Write) } else { None };
+                return true;
+            }
+        }
+
+        *slot = Some(match v {
+            None | Some("write") => ApplySuggestions::Write,
+            Some("dry-run") => ApplySuggestions::DryRun,
+            Some(_) => return false,
+        });
+        true
+    }
+
     pub(crate) fn parse_instrument_coverage(
         slot: &mut Option<InstrumentCoverage>,
         v: Option<&str>,
@@ -1078,6 +1174,17 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         };
         true
     }
+
+    pub(crate) fn parse_dump_resolutions(
+        slot: &mut Option<DumpResolutionsFormat>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = match v {
+            Some("json") => Some(DumpResolutionsFormat::Json),
+            _ => return false,
+        };
+        true
+    }
 }
 
 options! {
@@ -1224,6 +1331,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "only allow the listed language features to be enabled in code (space separated)"),
     always_encode_mir: bool = (false, parse_bool, [TRACKED],
         "encode MIR of all functions into the crate metadata (default: no)"),
+    apply_suggestions: Option<ApplySuggestions> = (None, parse_apply_suggestions, [UNTRACKED],
+        "apply the machine-applicable suggestions of all diagnostics to the source files, \
+        or print them as a diff with `-Z apply-suggestions=dry-run` (default: no)"),
     assume_incomplete_release: bool = (false, parse_bool, [TRACKED],
         "make cfg(version) treat the current version as incomplete (default: no)"),
     #[cfg_attr(not(bootstrap), rustc_lint_opt_deny_field_access("use `Session::asm_comments` instead of this field"))]
@@ -1250,6 +1360,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "the backend to use"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
         "combine CGUs into a single one"),
+    const_eval_profile: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "sample the stack of the compile-time interpreter every `val` steps and write the \
+        samples as folded stacks for flamegraph tools to `<output>.const-eval.folded`"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
     debug_info_for_profiling: bool = (false, parse_bool, [TRACKED],
@@ -1278,6 +1391,11 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         (default: no)"),
     dump_drop_tracking_cfg: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "dump drop-tracking control-flow graph as a `.dot` file (default: no)"),
+    dump_expansion_trace: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "write every macro invocation with its input and output, the time it took to expand \
+        and the expansion it created as JSON to `<crate>.expansion-trace.json` in the given \
+        directory, or the current one"),
     dump_mir: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "dump MIR state to file.
         `val` is used to select which passes and functions to dump. For example:
@@ -1302,6 +1420,27 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         computed `block` spans (one span encompassing a block's terminator and \
         all statements). If `-Z instrument-coverage` is also enabled, create \
         an additional `.html` file showing the computed coverage spans."),
+    dump_mono_items: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "write the monomorphization and codegen unit partitioning results as JSON to \
+        `<crate>.mono_items.json` in the given directory, or the current one"),
+    dump_obligation_tree: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "dump the obligation tree of every trait bound that fails to hold, with the candidates \
+        selection considered and why each was rejected, as JSON.
+        `val` selects the items whose bounds are dumped, like with `-Z dump-mir`: \
+        `all` matches all items, `foo | bar` items whose path contains 'foo' or 'bar'."),
+    dump_obligation_tree_dir: String = ("obligation_tree_dump".to_string(),
+        parse_string, [UNTRACKED],
+        "the directory obligation trees are dumped into (default: `obligation_tree_dump`)"),
+    dump_obligation_tree_graphviz: bool = (false, parse_bool, [UNTRACKED],
+        "in addition to `.json` files, create graphviz `.dot` files of obligation trees \
+        (default: no)"),
+    dump_resolutions: Option<DumpResolutionsFormat> = (None, parse_dump_resolutions,
+        [UNTRACKED],
+        "write the imports of every module and what they resolved to, which imports are unused, \
+        and the effective visibility of every item to `<crate>.resolutions.json`"),
+    dump_resolutions_dir: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "the directory `-Z dump-resolutions` writes into (default: the current directory)"),
     dwarf_version: Option<u32> = (None, parse_opt_number, [TRACKED],
         "version of DWARF debug information to emit (default: 2 or 4, depending on platform)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
@@ -1334,6 +1473,10 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "generate human-readable, predictable names for codegen units (default: no)"),
     identify_regions: bool = (false, parse_bool, [UNTRACKED],
         "display unnamed regions as `'<id>`, using a non-ident unique id (default: no)"),
+    incremental_explain: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "write a report of why incremental compilation had to re-execute queries as JSON to \
+        `<crate>.incremental-explain.json` in the given directory, or the current one"),
     incremental_ignore_spans: bool = (false, parse_bool, [UNTRACKED],
         "ignore spans during ICH computation -- used for testing (default: no)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
@@ -1341,6 +1484,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         (default: no)"),
     incremental_relative_spans: bool = (false, parse_bool, [TRACKED],
         "hash spans relative to their parent item for incr. comp. (default: no)"),
+    incremental_shared_cache: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "share incremental compilation sessions between checkouts through a content-addressed \
+        store in the given directory"),
     incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
         "verify incr. comp. hashes of green query instances (default: no)"),
     inline_mir: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1371,6 +1517,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    lint_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "suppress the lint warnings recorded in this file and only report new ones \
+        (see also `-Z write-lint-baseline`)"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
@@ -1470,8 +1619,12 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "print the LLVM optimization passes being run (default: no)"),
     print_mono_items: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "print the result of the monomorphization collection pass"),
-    print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
-        "print layout information for each type encountered (default: no)"),
+    print_stack_usage: bool = (false, parse_bool, [TRACKED],
+        "print the worst-case stack usage of each entry point of the crate, computed from the \
+        frame sizes LLVM reports and the calls between functions (ELF targets only) (default: no)"),
+    print_type_sizes: Option<PrintTypeSizes> = (None, parse_print_type_sizes, [UNTRACKED],
+        "print layout information for each type encountered, either as text on stdout or \
+        as a JSON file next to the other outputs (`-Zprint-type-sizes=json`) (default: no)"),
     proc_macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
          "show backtraces for panics during proc-macro execution (default: no)"),
     proc_macro_execution_strategy: ProcMacroExecutionStrategy = (ProcMacroExecutionStrategy::SameThread,
@@ -1525,7 +1678,8 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "specify the events recorded by the self profiler;
         for example: `-Z self-profile-events=default,query-keys`
         all options: none, all, default, generic-activity, query-provider, query-cache-hit
-                     query-blocked, incr-cache-load, incr-result-hashing, query-keys, function-args, args, llvm, artifact-sizes"),
+                     query-blocked, incr-cache-load, incr-result-hashing, query-keys, function-args, args, llvm, artifact-sizes,
+                     llvm-functions"),
     self_profile_counter: String = ("wall-time".to_string(), parse_string, [UNTRACKED],
         "counter used by the self profiler (default: `wall-time`), one of:
         `wall-time` (monotonic clock, i.e. `std::time::Instant`)
@@ -1546,6 +1700,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
     #[cfg_attr(not(bootstrap), rustc_lint_opt_deny_field_access("use `Session::stack_protector` instead of this field"))]
     stack_protector: StackProtector = (StackProtector::None, parse_stack_protector, [TRACKED],
         "control stack smash protection strategy (`rustc --print stack-protector-strategies` for details)"),
+    stack_usage_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "emit an error for each entry point of the crate whose worst-case stack usage \
+        exceeds `val` bytes, and a warning for those whose stack usage is unbounded"),
     strict_init_checks: bool = (false, parse_bool, [TRACKED],
         "control if mem::uninitialized and mem::zeroed panic on more UB"),
     strip: Strip = (Strip::None, parse_strip, [UNTRACKED],
@@ -1654,6 +1811,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         Requires `-Clto[=[fat,yes]]`"),
     wasi_exec_model: Option<WasiExecModel> = (None, parse_wasi_exec_model, [TRACKED],
         "whether to build a wasi command or reactor"),
+    write_lint_baseline: bool = (false, parse_bool, [UNTRACKED],
+        "record all lint warnings to the file given with `-Z lint-baseline` instead of \
+        suppressing the ones in it (default: no)"),
 
     // This list is in alphabetical order.
     //
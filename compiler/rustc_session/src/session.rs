@@ -1,6 +1,6 @@
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use crate::code_stats::CodeStats;
-pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+pub use crate::code_stats::{DataTypeKind, FieldInfo, NicheInfo, SizeKind, VariantInfo};
 use crate::config::{self, CrateType, InstrumentCoverage, OptLevel, OutputType, SwitchWithOptPath};
 use crate::parse::{add_feature_diagnostics, ParseSess};
 use crate::search_paths::{PathKind, SearchPath};
@@ -18,7 +18,9 @@
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitterWriter;
 use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
+use rustc_errors::lint_baseline::LintBaseline;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
     fallback_fluent_bundle, DiagnosticBuilder, DiagnosticId, DiagnosticMessage, EmissionGuarantee,
     ErrorGuaranteed, FluentBundle, LazyFallbackBundle, MultiSpan,
@@ -255,10 +257,26 @@ fn check_miri_unleashed_features(&self) {
     /// Invoked all the way at the end to finish off diagnostics printing.
     pub fn finish_diagnostics(&self, registry: &Registry) {
         self.check_miri_unleashed_features();
+        self.write_lint_baseline();
         self.diagnostic().print_error_count(registry);
         self.emit_future_breakage();
     }
 
+    fn write_lint_baseline(&self) {
+        // A compilation that failed may not have gotten far enough to emit all warnings, so its
+        // baseline would be incomplete. Errors of denied lints don't count, as they are recorded
+        // like warnings.
+        if self.has_errors().is_some() {
+            if self.opts.unstable_opts.write_lint_baseline {
+                self.warn("not writing the lint baseline because compilation failed");
+            }
+            return;
+        }
+        if let Some((path, Err(e))) = self.diagnostic().write_lint_baseline() {
+            self.err(&format!("failed to write lint baseline `{}`: {e}", path.display()));
+        }
+    }
+
     fn emit_future_breakage(&self) {
         if !self.opts.json_future_incompat {
             return;
@@ -544,7 +562,7 @@ pub fn delay_span_bug<S: Into<MultiSpan>>(
     /// warnings or errors are emitted. If no messages are emitted ("good path"), then
     /// it's likely a bug.
     pub fn delay_good_path_bug(&self, msg: impl Into<DiagnosticMessage>) {
-        if self.opts.unstable_opts.print_type_sizes
+        if self.opts.unstable_opts.print_type_sizes.is_some()
             || self.opts.unstable_opts.query_dep_graph
             || self.opts.unstable_opts.dump_mir.is_some()
             || self.opts.unstable_opts.unpretty.is_some()
@@ -924,6 +942,13 @@ pub fn print_llvm_passes(&self) -> bool {
         self.opts.unstable_opts.print_llvm_passes
     }
 
+    /// Whether the stack usage of the crate's entry points is analyzed after codegen, which needs
+    /// the frame sizes of its functions.
+    pub fn analyze_stack_usage(&self) -> bool {
+        self.opts.unstable_opts.print_stack_usage
+            || self.opts.unstable_opts.stack_usage_limit.is_some()
+    }
+
     pub fn binary_dep_depinfo(&self) -> bool {
         self.opts.unstable_opts.binary_dep_depinfo
     }
@@ -1251,6 +1276,21 @@ fn default_emitter(
             )
             .ui_testing(sopts.unstable_opts.ui_testing),
         ),
+        (config::ErrorOutputType::Sarif, None) => Box::new(SarifEmitter::stderr(
+            Some(registry),
+            source_map,
+            bundle,
+            fallback_bundle,
+            sopts.crate_name.clone(),
+        )),
+        (config::ErrorOutputType::Sarif, Some(dst)) => Box::new(SarifEmitter::new(
+            dst,
+            Some(registry),
+            source_map,
+            bundle,
+            fallback_bundle,
+            sopts.crate_name.clone(),
+        )),
     }
 }
 
@@ -1329,6 +1369,22 @@ pub fn build_session(
         sopts.unstable_opts.diagnostic_handler_flags(can_emit_warnings),
     );
 
+    if let Some(path) = &sopts.unstable_opts.lint_baseline {
+        let baseline = if sopts.unstable_opts.write_lint_baseline {
+            LintBaseline::record(path.clone())
+        } else {
+            LintBaseline::load(path.clone()).unwrap_or_else(|e| {
+                early_error(
+                    sopts.error_format,
+                    &format!("failed to read lint baseline `{}`: {e}", path.display()),
+                )
+            })
+        };
+        span_diagnostic.set_lint_baseline(baseline);
+    } else if sopts.unstable_opts.write_lint_baseline {
+        early_error(sopts.error_format, "`-Z write-lint-baseline` requires `-Z lint-baseline`");
+    }
+
     let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.unstable_opts.self_profile
     {
         let directory =
@@ -1582,6 +1638,7 @@ fn early_error_handler(output: config::ErrorOutputType) -> rustc_errors::Handler
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, fallback_bundle, None, false))
         }
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::basic(None, fallback_bundle)),
     };
     rustc_errors::Handler::with_emitter(true, None, emitter)
 }
@@ -14,4 +14,10 @@
 
 pub mod mir;
 
+pub mod rustc_internal;
+pub mod stable_mir;
+
+// Make this module private for now since external users should not call these directly.
+mod rustc_smir;
+
 pub mod very_unstable;
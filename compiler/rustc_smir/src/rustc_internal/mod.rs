@@ -0,0 +1,38 @@
+//! Module that implements the bridge between Stable MIR and internal compiler MIR.
+//!
+//! For that, we define APIs that will temporarily be public to 3P that exposes rustc internal APIs
+//! until stable MIR is complete.
+
+use crate::stable_mir;
+use rustc_middle::ty::TyCtxt;
+pub use rustc_span::def_id::{CrateNum, DefId};
+
+use crate::rustc_smir::Tables;
+
+fn with_tables<R>(mut f: impl FnMut(&mut Tables<'_>) -> R) -> R {
+    let mut ret = None;
+    stable_mir::with(|tables| tables.rustc_tables(&mut |t| ret = Some(f(t))));
+    ret.unwrap()
+}
+
+pub fn item_def_id(item: &stable_mir::CrateItem) -> DefId {
+    with_tables(|t| t.item_def_id(item))
+}
+
+pub fn fn_def_id(def: &stable_mir::ty::FnDef) -> DefId {
+    with_tables(|t| t.def_ids[def.0 .0])
+}
+
+pub fn crate_item(did: DefId) -> stable_mir::CrateItem {
+    with_tables(|t| t.crate_item(did))
+}
+
+pub fn crate_num(item: &stable_mir::Crate) -> CrateNum {
+    CrateNum::from_usize(item.id)
+}
+
+/// Run `f` with the stable MIR context for `tcx` installed, so that the functions in
+/// `stable_mir` can be called from within it.
+pub fn run(tcx: TyCtxt<'_>, f: impl FnOnce()) {
+    crate::stable_mir::run(Tables::new(tcx), f);
+}
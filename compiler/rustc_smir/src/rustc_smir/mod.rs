@@ -0,0 +1,585 @@
+//! Module that implements what will become the rustc side of Stable MIR.
+//!
+//! This module is responsible for building Stable MIR components from internal components.
+//!
+//! This module is not intended to be invoked directly by users. It will eventually
+//! become the public API of rustc that will be invoked by the `stable_mir` crate.
+//!
+//! For now, we are developing everything inside `rustc`, thus, we keep this module private.
+
+use std::collections::HashMap;
+
+use crate::stable_mir::ty::{
+    AdtDef, ClosureDef, FloatTy, FnDef, ForeignDef, GeneratorDef, GenericArgs, IntTy, RigidTy,
+    TyKind, UintTy,
+};
+use crate::stable_mir::{self, mir, Context};
+use rustc_hir as hir;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_middle::mir as rustc_mir;
+use rustc_middle::ty::{self, ParamEnv, SubstsRef, Ty, TyCtxt};
+use rustc_span::Span;
+
+/// Maps between the compiler's interned data structures and the indices handed out to
+/// `stable_mir` users.
+pub struct Tables<'tcx> {
+    pub tcx: TyCtxt<'tcx>,
+    pub def_ids: Vec<DefId>,
+    pub types: Vec<Ty<'tcx>>,
+    def_id_indices: HashMap<DefId, usize>,
+    type_indices: HashMap<Ty<'tcx>, usize>,
+}
+
+impl<'tcx> Tables<'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>) -> Self {
+        Tables {
+            tcx,
+            def_ids: Vec::new(),
+            types: Vec::new(),
+            def_id_indices: HashMap::new(),
+            type_indices: HashMap::new(),
+        }
+    }
+
+    pub fn item_def_id(&self, item: &stable_mir::CrateItem) -> DefId {
+        self.def_ids[item.0 .0]
+    }
+
+    pub fn crate_item(&mut self, did: DefId) -> stable_mir::CrateItem {
+        stable_mir::CrateItem(self.create_def_id(did))
+    }
+
+    pub fn create_def_id(&mut self, did: DefId) -> stable_mir::DefId {
+        let def_ids = &mut self.def_ids;
+        let idx = *self.def_id_indices.entry(did).or_insert_with(|| {
+            def_ids.push(did);
+            def_ids.len() - 1
+        });
+        stable_mir::DefId(idx)
+    }
+
+    pub fn intern_ty(&mut self, ty: Ty<'tcx>) -> stable_mir::ty::Ty {
+        let types = &mut self.types;
+        let idx = *self.type_indices.entry(ty).or_insert_with(|| {
+            types.push(ty);
+            types.len() - 1
+        });
+        stable_mir::ty::Ty(idx)
+    }
+
+    fn span(&self, span: Span) -> stable_mir::Span {
+        let source_map = self.tcx.sess.source_map();
+        let lo = source_map.lookup_char_pos(span.lo());
+        let hi = source_map.lookup_char_pos(span.hi());
+        stable_mir::Span {
+            file: lo.file.name.prefer_local().to_string(),
+            lo_line: lo.line,
+            lo_col: lo.col.0,
+            hi_line: hi.line,
+            hi_col: hi.col.0,
+        }
+    }
+
+    fn generic_args(&mut self, substs: SubstsRef<'tcx>) -> GenericArgs {
+        GenericArgs(substs.types().map(|ty| self.intern_ty(ty)).collect())
+    }
+
+    fn rustc_ty_to_ty(&mut self, ty: Ty<'tcx>) -> TyKind {
+        match ty.kind() {
+            ty::Bool => TyKind::RigidTy(RigidTy::Bool),
+            ty::Char => TyKind::RigidTy(RigidTy::Char),
+            ty::Int(int_ty) => TyKind::RigidTy(RigidTy::Int(match int_ty {
+                ty::IntTy::Isize => IntTy::Isize,
+                ty::IntTy::I8 => IntTy::I8,
+                ty::IntTy::I16 => IntTy::I16,
+                ty::IntTy::I32 => IntTy::I32,
+                ty::IntTy::I64 => IntTy::I64,
+                ty::IntTy::I128 => IntTy::I128,
+            })),
+            ty::Uint(uint_ty) => TyKind::RigidTy(RigidTy::Uint(match uint_ty {
+                ty::UintTy::Usize => UintTy::Usize,
+                ty::UintTy::U8 => UintTy::U8,
+                ty::UintTy::U16 => UintTy::U16,
+                ty::UintTy::U32 => UintTy::U32,
+                ty::UintTy::U64 => UintTy::U64,
+                ty::UintTy::U128 => UintTy::U128,
+            })),
+            ty::Float(float_ty) => TyKind::RigidTy(RigidTy::Float(match float_ty {
+                ty::FloatTy::F32 => FloatTy::F32,
+                ty::FloatTy::F64 => FloatTy::F64,
+            })),
+            ty::Adt(adt_def, substs) => TyKind::RigidTy(RigidTy::Adt(
+                AdtDef(self.create_def_id(adt_def.did())),
+                self.generic_args(substs),
+            )),
+            ty::Foreign(def_id) => {
+                TyKind::RigidTy(RigidTy::Foreign(ForeignDef(self.create_def_id(*def_id))))
+            }
+            ty::Str => TyKind::RigidTy(RigidTy::Str),
+            ty::Array(elem, len) => TyKind::RigidTy(RigidTy::Array(
+                self.intern_ty(*elem),
+                len.try_eval_usize(self.tcx, ParamEnv::reveal_all()),
+            )),
+            ty::Slice(elem) => TyKind::RigidTy(RigidTy::Slice(self.intern_ty(*elem))),
+            ty::RawPtr(ty::TypeAndMut { ty, mutbl }) => {
+                TyKind::RigidTy(RigidTy::RawPtr(self.intern_ty(*ty), mutability(*mutbl)))
+            }
+            ty::Ref(_, ty, mutbl) => {
+                TyKind::RigidTy(RigidTy::Ref(self.intern_ty(*ty), mutability(*mutbl)))
+            }
+            ty::FnDef(def_id, substs) => TyKind::RigidTy(RigidTy::FnDef(
+                FnDef(self.create_def_id(*def_id)),
+                self.generic_args(substs),
+            )),
+            ty::FnPtr(_) => TyKind::RigidTy(RigidTy::FnPtr),
+            ty::Dynamic(..) => TyKind::RigidTy(RigidTy::Dynamic),
+            ty::Closure(def_id, substs) => TyKind::RigidTy(RigidTy::Closure(
+                ClosureDef(self.create_def_id(*def_id)),
+                self.generic_args(substs),
+            )),
+            ty::Generator(def_id, substs, _) => TyKind::RigidTy(RigidTy::Generator(
+                GeneratorDef(self.create_def_id(*def_id)),
+                self.generic_args(substs),
+            )),
+            ty::Never => TyKind::RigidTy(RigidTy::Never),
+            ty::Tuple(fields) => TyKind::RigidTy(RigidTy::Tuple(
+                fields.iter().map(|ty| self.intern_ty(ty)).collect(),
+            )),
+            ty::Param(param) => TyKind::Param(param.name.to_string()),
+            ty::GeneratorWitness(_)
+            | ty::Projection(_)
+            | ty::Opaque(..)
+            | ty::Bound(..)
+            | ty::Placeholder(_)
+            | ty::Infer(_)
+            | ty::Error(_) => TyKind::Unsupported(ty.to_string()),
+        }
+    }
+}
+
+impl<'tcx> Context for Tables<'tcx> {
+    fn local_crate(&self) -> stable_mir::Crate {
+        smir_crate(self.tcx, LOCAL_CRATE)
+    }
+
+    fn external_crates(&self) -> Vec<stable_mir::Crate> {
+        self.tcx.crates(()).iter().map(|crate_num| smir_crate(self.tcx, *crate_num)).collect()
+    }
+
+    fn find_crate(&self, name: &str) -> Option<stable_mir::Crate> {
+        [LOCAL_CRATE].iter().chain(self.tcx.crates(()).iter()).find_map(|crate_num| {
+            let crate_name = self.tcx.crate_name(*crate_num).to_string();
+            (name == crate_name).then(|| smir_crate(self.tcx, *crate_num))
+        })
+    }
+
+    fn all_local_items(&mut self) -> stable_mir::CrateItems {
+        let tcx = self.tcx;
+        tcx.mir_keys(()).iter().map(|item| self.crate_item(item.to_def_id())).collect()
+    }
+
+    fn entry_fn(&mut self) -> Option<stable_mir::CrateItem> {
+        Some(self.crate_item(self.tcx.entry_fn(())?.0))
+    }
+
+    fn mir_body(&mut self, item: &stable_mir::CrateItem) -> stable_mir::mir::Body {
+        let def_id = self.item_def_id(item);
+        // Const-like items have no optimized MIR, only the MIR used to evaluate them.
+        let mir = match self.tcx.def_kind(def_id) {
+            DefKind::Const
+            | DefKind::AssocConst
+            | DefKind::AnonConst
+            | DefKind::InlineConst
+            | DefKind::Static(_) => self.tcx.mir_for_ctfe(def_id),
+            _ => self.tcx.optimized_mir(def_id),
+        };
+        stable_mir::mir::Body {
+            blocks: mir
+                .basic_blocks()
+                .iter()
+                .map(|block| stable_mir::mir::BasicBlock {
+                    statements: block
+                        .statements
+                        .iter()
+                        .map(|statement| self.rustc_statement_to_statement(statement))
+                        .collect(),
+                    terminator: self.rustc_terminator_to_terminator(block.terminator()),
+                    is_cleanup: block.is_cleanup,
+                })
+                .collect(),
+            locals: mir.local_decls.iter().map(|decl| self.intern_ty(decl.ty)).collect(),
+            arg_count: mir.arg_count,
+            span: self.span(mir.span),
+        }
+    }
+
+    fn item_name(&mut self, item: &stable_mir::CrateItem) -> String {
+        self.tcx.def_path_str(self.item_def_id(item))
+    }
+
+    fn item_span(&mut self, item: &stable_mir::CrateItem) -> stable_mir::Span {
+        self.span(self.tcx.def_span(self.item_def_id(item)))
+    }
+
+    fn ty_kind(&mut self, ty: crate::stable_mir::ty::Ty) -> TyKind {
+        // Handles are only created by `intern_ty`, but one can outlive the tables it came from.
+        let ty = *self.types.get(ty.0).unwrap_or_else(|| {
+            rustc_middle::bug!("`Ty({})` was not created by this `stable_mir` context", ty.0)
+        });
+        self.rustc_ty_to_ty(ty)
+    }
+
+    fn rustc_tables(&mut self, f: &mut dyn FnMut(&mut Tables<'_>)) {
+        f(self)
+    }
+}
+
+/// Build a stable mir crate from a given crate number.
+fn smir_crate(tcx: TyCtxt<'_>, crate_num: CrateNum) -> stable_mir::Crate {
+    let crate_name = tcx.crate_name(crate_num).to_string();
+    let is_local = crate_num == LOCAL_CRATE;
+    stable_mir::Crate { id: crate_num.as_usize(), name: crate_name, is_local }
+}
+
+fn mutability(mutbl: hir::Mutability) -> stable_mir::ty::Mutability {
+    match mutbl {
+        hir::Mutability::Not => stable_mir::ty::Mutability::Not,
+        hir::Mutability::Mut => stable_mir::ty::Mutability::Mut,
+    }
+}
+
+fn rustc_bin_op_to_bin_op(bin_op: &rustc_mir::BinOp) -> mir::BinOp {
+    match bin_op {
+        rustc_mir::BinOp::Add => mir::BinOp::Add,
+        rustc_mir::BinOp::Sub => mir::BinOp::Sub,
+        rustc_mir::BinOp::Mul => mir::BinOp::Mul,
+        rustc_mir::BinOp::Div => mir::BinOp::Div,
+        rustc_mir::BinOp::Rem => mir::BinOp::Rem,
+        rustc_mir::BinOp::BitXor => mir::BinOp::BitXor,
+        rustc_mir::BinOp::BitAnd => mir::BinOp::BitAnd,
+        rustc_mir::BinOp::BitOr => mir::BinOp::BitOr,
+        rustc_mir::BinOp::Shl => mir::BinOp::Shl,
+        rustc_mir::BinOp::Shr => mir::BinOp::Shr,
+        rustc_mir::BinOp::Eq => mir::BinOp::Eq,
+        rustc_mir::BinOp::Lt => mir::BinOp::Lt,
+        rustc_mir::BinOp::Le => mir::BinOp::Le,
+        rustc_mir::BinOp::Ne => mir::BinOp::Ne,
+        rustc_mir::BinOp::Ge => mir::BinOp::Ge,
+        rustc_mir::BinOp::Gt => mir::BinOp::Gt,
+        rustc_mir::BinOp::Offset => mir::BinOp::Offset,
+    }
+}
+
+fn rustc_generator_to_generator(generator: &hir::GeneratorKind) -> mir::GeneratorKind {
+    match generator {
+        hir::GeneratorKind::Async(async_gen) => mir::GeneratorKind::Async(match async_gen {
+            hir::AsyncGeneratorKind::Block => mir::AsyncGeneratorKind::Block,
+            hir::AsyncGeneratorKind::Closure => mir::AsyncGeneratorKind::Closure,
+            hir::AsyncGeneratorKind::Fn => mir::AsyncGeneratorKind::Fn,
+        }),
+        hir::GeneratorKind::Gen => mir::GeneratorKind::Gen,
+    }
+}
+
+impl<'tcx> Tables<'tcx> {
+    fn rustc_statement_to_statement(
+        &mut self,
+        s: &rustc_mir::Statement<'tcx>,
+    ) -> stable_mir::mir::Statement {
+        use rustc_mir::StatementKind::*;
+        match &s.kind {
+            Assign(assign) => stable_mir::mir::Statement::Assign(
+                self.rustc_place_to_place(&assign.0),
+                self.rustc_rvalue_to_rvalue(&assign.1),
+            ),
+            FakeRead(fake_read) => {
+                stable_mir::mir::Statement::FakeRead(self.rustc_place_to_place(&fake_read.1))
+            }
+            SetDiscriminant { place, variant_index } => {
+                stable_mir::mir::Statement::SetDiscriminant {
+                    place: self.rustc_place_to_place(place),
+                    variant_index: variant_index.as_usize(),
+                }
+            }
+            Deinit(place) => stable_mir::mir::Statement::Deinit(self.rustc_place_to_place(place)),
+            StorageLive(local) => stable_mir::mir::Statement::StorageLive(local.as_usize()),
+            StorageDead(local) => stable_mir::mir::Statement::StorageDead(local.as_usize()),
+            Retag(_, place) => stable_mir::mir::Statement::Retag(self.rustc_place_to_place(place)),
+            AscribeUserType(ascription, _) => stable_mir::mir::Statement::AscribeUserType(
+                self.rustc_place_to_place(&ascription.0),
+            ),
+            Coverage(_) => stable_mir::mir::Statement::Coverage,
+            CopyNonOverlapping(copy) => stable_mir::mir::Statement::CopyNonOverlapping {
+                src: self.rustc_op_to_op(&copy.src),
+                dst: self.rustc_op_to_op(&copy.dst),
+                count: self.rustc_op_to_op(&copy.count),
+            },
+            Nop => stable_mir::mir::Statement::Nop,
+        }
+    }
+
+    fn rustc_rvalue_to_rvalue(&mut self, rvalue: &rustc_mir::Rvalue<'tcx>) -> mir::Rvalue {
+        use rustc_mir::Rvalue::*;
+        match rvalue {
+            Use(op) => mir::Rvalue::Use(self.rustc_op_to_op(op)),
+            Repeat(op, len) => mir::Rvalue::Repeat(
+                self.rustc_op_to_op(op),
+                len.try_eval_usize(self.tcx, ParamEnv::reveal_all()),
+            ),
+            Ref(_, kind, place) => {
+                let kind = match kind {
+                    rustc_mir::BorrowKind::Shared => mir::BorrowKind::Shared,
+                    rustc_mir::BorrowKind::Shallow => mir::BorrowKind::Shallow,
+                    rustc_mir::BorrowKind::Unique => mir::BorrowKind::Unique,
+                    rustc_mir::BorrowKind::Mut { allow_two_phase_borrow } => {
+                        mir::BorrowKind::Mut { allow_two_phase_borrow: *allow_two_phase_borrow }
+                    }
+                };
+                mir::Rvalue::Ref(kind, self.rustc_place_to_place(place))
+            }
+            ThreadLocalRef(def_id) => mir::Rvalue::ThreadLocalRef(self.crate_item(*def_id)),
+            AddressOf(mutbl, place) => {
+                mir::Rvalue::AddressOf(mutability(*mutbl), self.rustc_place_to_place(place))
+            }
+            Len(place) => mir::Rvalue::Len(self.rustc_place_to_place(place)),
+            Cast(kind, op, ty) => {
+                let kind = match kind {
+                    rustc_mir::CastKind::PointerExposeAddress => {
+                        mir::CastKind::PointerExposeAddress
+                    }
+                    rustc_mir::CastKind::PointerFromExposedAddress => {
+                        mir::CastKind::PointerFromExposedAddress
+                    }
+                    rustc_mir::CastKind::Pointer(_) => mir::CastKind::Pointer,
+                    rustc_mir::CastKind::Misc => mir::CastKind::Misc,
+                };
+                mir::Rvalue::Cast(kind, self.rustc_op_to_op(op), self.intern_ty(*ty))
+            }
+            BinaryOp(bin_op, ops) => mir::Rvalue::BinaryOp(
+                rustc_bin_op_to_bin_op(bin_op),
+                self.rustc_op_to_op(&ops.0),
+                self.rustc_op_to_op(&ops.1),
+            ),
+            CheckedBinaryOp(bin_op, ops) => mir::Rvalue::CheckedBinaryOp(
+                rustc_bin_op_to_bin_op(bin_op),
+                self.rustc_op_to_op(&ops.0),
+                self.rustc_op_to_op(&ops.1),
+            ),
+            NullaryOp(null_op, ty) => {
+                let null_op = match null_op {
+                    rustc_mir::NullOp::SizeOf => mir::NullOp::SizeOf,
+                    rustc_mir::NullOp::AlignOf => mir::NullOp::AlignOf,
+                };
+                mir::Rvalue::NullaryOp(null_op, self.intern_ty(*ty))
+            }
+            UnaryOp(un_op, op) => {
+                let un_op = match un_op {
+                    rustc_mir::UnOp::Not => mir::UnOp::Not,
+                    rustc_mir::UnOp::Neg => mir::UnOp::Neg,
+                };
+                mir::Rvalue::UnaryOp(un_op, self.rustc_op_to_op(op))
+            }
+            Discriminant(place) => mir::Rvalue::Discriminant(self.rustc_place_to_place(place)),
+            Aggregate(kind, ops) => {
+                let kind = match &**kind {
+                    rustc_mir::AggregateKind::Array(ty) => {
+                        mir::AggregateKind::Array(self.intern_ty(*ty))
+                    }
+                    rustc_mir::AggregateKind::Tuple => mir::AggregateKind::Tuple,
+                    rustc_mir::AggregateKind::Adt(def_id, variant, substs, _, active_field) => {
+                        mir::AggregateKind::Adt(
+                            self.crate_item(*def_id),
+                            variant.as_usize(),
+                            self.generic_args(substs),
+                            *active_field,
+                        )
+                    }
+                    rustc_mir::AggregateKind::Closure(def_id, substs) => {
+                        mir::AggregateKind::Closure(
+                            self.crate_item(def_id.to_def_id()),
+                            self.generic_args(substs),
+                        )
+                    }
+                    rustc_mir::AggregateKind::Generator(def_id, substs, _) => {
+                        mir::AggregateKind::Generator(
+                            self.crate_item(def_id.to_def_id()),
+                            self.generic_args(substs),
+                        )
+                    }
+                };
+                mir::Rvalue::Aggregate(kind, ops.iter().map(|op| self.rustc_op_to_op(op)).collect())
+            }
+            ShallowInitBox(op, ty) => {
+                mir::Rvalue::ShallowInitBox(self.rustc_op_to_op(op), self.intern_ty(*ty))
+            }
+            CopyForDeref(place) => mir::Rvalue::CopyForDeref(self.rustc_place_to_place(place)),
+        }
+    }
+
+    fn rustc_op_to_op(&mut self, op: &rustc_mir::Operand<'tcx>) -> mir::Operand {
+        use rustc_mir::Operand::*;
+        match op {
+            Copy(place) => mir::Operand::Copy(self.rustc_place_to_place(place)),
+            Move(place) => mir::Operand::Move(self.rustc_place_to_place(place)),
+            Constant(c) => mir::Operand::Constant(mir::Constant {
+                ty: self.intern_ty(c.literal.ty()),
+                literal: c.to_string(),
+            }),
+        }
+    }
+
+    fn rustc_place_to_place(&mut self, place: &rustc_mir::Place<'tcx>) -> mir::Place {
+        let projection = place
+            .projection
+            .iter()
+            .map(|elem| match elem {
+                rustc_mir::ProjectionElem::Deref => mir::ProjectionElem::Deref,
+                rustc_mir::ProjectionElem::Field(field, ty) => {
+                    mir::ProjectionElem::Field(field.as_usize(), self.intern_ty(ty))
+                }
+                rustc_mir::ProjectionElem::Index(local) => {
+                    mir::ProjectionElem::Index(local.as_usize())
+                }
+                rustc_mir::ProjectionElem::ConstantIndex { offset, min_length, from_end } => {
+                    mir::ProjectionElem::ConstantIndex { offset, min_length, from_end }
+                }
+                rustc_mir::ProjectionElem::Subslice { from, to, from_end } => {
+                    mir::ProjectionElem::Subslice { from, to, from_end }
+                }
+                rustc_mir::ProjectionElem::Downcast(_, variant) => {
+                    mir::ProjectionElem::Downcast(variant.as_usize())
+                }
+            })
+            .collect();
+        mir::Place { local: place.local.as_usize(), projection }
+    }
+
+    fn rustc_assert_msg_to_msg(
+        &mut self,
+        assert_message: &rustc_mir::AssertMessage<'tcx>,
+    ) -> mir::AssertMessage {
+        use rustc_mir::AssertKind;
+        match assert_message {
+            AssertKind::BoundsCheck { len, index } => mir::AssertMessage::BoundsCheck {
+                len: self.rustc_op_to_op(len),
+                index: self.rustc_op_to_op(index),
+            },
+            AssertKind::Overflow(bin_op, op1, op2) => mir::AssertMessage::Overflow(
+                rustc_bin_op_to_bin_op(bin_op),
+                self.rustc_op_to_op(op1),
+                self.rustc_op_to_op(op2),
+            ),
+            AssertKind::OverflowNeg(op) => mir::AssertMessage::OverflowNeg(self.rustc_op_to_op(op)),
+            AssertKind::DivisionByZero(op) => {
+                mir::AssertMessage::DivisionByZero(self.rustc_op_to_op(op))
+            }
+            AssertKind::RemainderByZero(op) => {
+                mir::AssertMessage::RemainderByZero(self.rustc_op_to_op(op))
+            }
+            AssertKind::ResumedAfterReturn(generator) => {
+                mir::AssertMessage::ResumedAfterReturn(rustc_generator_to_generator(generator))
+            }
+            AssertKind::ResumedAfterPanic(generator) => {
+                mir::AssertMessage::ResumedAfterPanic(rustc_generator_to_generator(generator))
+            }
+        }
+    }
+
+    fn rustc_terminator_to_terminator(
+        &mut self,
+        terminator: &rustc_mir::Terminator<'tcx>,
+    ) -> stable_mir::mir::Terminator {
+        use rustc_mir::TerminatorKind::*;
+        use stable_mir::mir::Terminator;
+        match &terminator.kind {
+            Goto { target } => Terminator::Goto { target: target.as_usize() },
+            SwitchInt { discr, targets, .. } => Terminator::SwitchInt {
+                discr: self.rustc_op_to_op(discr),
+                targets: targets
+                    .iter()
+                    .map(|(value, target)| mir::SwitchTarget { value, target: target.as_usize() })
+                    .collect(),
+                otherwise: targets.otherwise().as_usize(),
+            },
+            Resume => Terminator::Resume,
+            Abort => Terminator::Abort,
+            Return => Terminator::Return,
+            Unreachable => Terminator::Unreachable,
+            Drop { place, target, unwind } => Terminator::Drop {
+                place: self.rustc_place_to_place(place),
+                target: target.as_usize(),
+                unwind: unwind.map(|block| block.as_usize()),
+            },
+            DropAndReplace { place, value, target, unwind } => Terminator::DropAndReplace {
+                place: self.rustc_place_to_place(place),
+                value: self.rustc_op_to_op(value),
+                target: target.as_usize(),
+                unwind: unwind.map(|block| block.as_usize()),
+            },
+            Call { func, args, destination, target, cleanup, from_hir_call: _, fn_span: _ } => {
+                Terminator::Call {
+                    func: self.rustc_op_to_op(func),
+                    args: args.iter().map(|arg| self.rustc_op_to_op(arg)).collect(),
+                    destination: self.rustc_place_to_place(destination),
+                    target: target.map(|block| block.as_usize()),
+                    cleanup: cleanup.map(|block| block.as_usize()),
+                }
+            }
+            Assert { cond, expected, msg, target, cleanup } => Terminator::Assert {
+                cond: self.rustc_op_to_op(cond),
+                expected: *expected,
+                msg: self.rustc_assert_msg_to_msg(msg),
+                target: target.as_usize(),
+                cleanup: cleanup.map(|block| block.as_usize()),
+            },
+            Yield { value, resume, resume_arg, drop } => Terminator::Yield {
+                value: self.rustc_op_to_op(value),
+                resume: resume.as_usize(),
+                resume_arg: self.rustc_place_to_place(resume_arg),
+                drop: drop.map(|block| block.as_usize()),
+            },
+            GeneratorDrop => Terminator::GeneratorDrop,
+            // These only differ from a `Goto` for borrowck, so expose them as one.
+            FalseEdge { real_target, .. } | FalseUnwind { real_target, .. } => {
+                Terminator::Goto { target: real_target.as_usize() }
+            }
+            InlineAsm { template, operands, options, line_spans, destination, cleanup } => {
+                Terminator::InlineAsm {
+                    template: format!("{:?}", template),
+                    operands: operands
+                        .iter()
+                        .map(|operand| self.rustc_asm_operand_to_asm_operand(operand))
+                        .collect(),
+                    options: format!("{:?}", options),
+                    line_spans: format!("{:?}", line_spans),
+                    destination: destination.map(|block| block.as_usize()),
+                    cleanup: cleanup.map(|block| block.as_usize()),
+                }
+            }
+        }
+    }
+
+    fn rustc_asm_operand_to_asm_operand(
+        &mut self,
+        operand: &rustc_mir::InlineAsmOperand<'tcx>,
+    ) -> mir::InlineAsmOperand {
+        use rustc_mir::InlineAsmOperand;
+
+        let (in_value, out_place) = match operand {
+            InlineAsmOperand::In { value, .. } => (Some(self.rustc_op_to_op(value)), None),
+            InlineAsmOperand::Out { place, .. } => {
+                (None, place.map(|place| self.rustc_place_to_place(&place)))
+            }
+            InlineAsmOperand::InOut { in_value, out_place, .. } => (
+                Some(self.rustc_op_to_op(in_value)),
+                out_place.map(|place| self.rustc_place_to_place(&place)),
+            ),
+            InlineAsmOperand::Const { .. }
+            | InlineAsmOperand::SymFn { .. }
+            | InlineAsmOperand::SymStatic { .. } => (None, None),
+        };
+
+        mir::InlineAsmOperand { in_value, out_place, raw_rpr: format!("{:?}", operand) }
+    }
+}
@@ -0,0 +1,277 @@
+use crate::stable_mir::ty::{GenericArgs, Mutability, Ty};
+use crate::stable_mir::{CrateItem, Span};
+
+/// The body of a function, constant or static, as a control flow graph of basic blocks.
+#[derive(Clone, Debug)]
+pub struct Body {
+    pub blocks: Vec<BasicBlock>,
+    /// The types of all locals. The return place is `locals[0]`, followed by the arguments.
+    pub locals: Vec<Ty>,
+    /// The number of arguments this body takes.
+    pub arg_count: usize,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub struct BasicBlock {
+    pub statements: Vec<Statement>,
+    pub terminator: Terminator,
+    /// Whether this block is only reached while unwinding.
+    pub is_cleanup: bool,
+}
+
+/// An index into [`Body::blocks`].
+pub type BasicBlockIdx = usize;
+
+/// An index into [`Body::locals`].
+pub type Local = usize;
+
+#[derive(Clone, Debug)]
+pub enum Terminator {
+    Goto {
+        target: BasicBlockIdx,
+    },
+    SwitchInt {
+        discr: Operand,
+        targets: Vec<SwitchTarget>,
+        otherwise: BasicBlockIdx,
+    },
+    Resume,
+    Abort,
+    Return,
+    Unreachable,
+    Drop {
+        place: Place,
+        target: BasicBlockIdx,
+        unwind: Option<BasicBlockIdx>,
+    },
+    DropAndReplace {
+        place: Place,
+        value: Operand,
+        target: BasicBlockIdx,
+        unwind: Option<BasicBlockIdx>,
+    },
+    Call {
+        func: Operand,
+        args: Vec<Operand>,
+        destination: Place,
+        target: Option<BasicBlockIdx>,
+        cleanup: Option<BasicBlockIdx>,
+    },
+    Assert {
+        cond: Operand,
+        expected: bool,
+        msg: AssertMessage,
+        target: BasicBlockIdx,
+        cleanup: Option<BasicBlockIdx>,
+    },
+    Yield {
+        value: Operand,
+        resume: BasicBlockIdx,
+        resume_arg: Place,
+        drop: Option<BasicBlockIdx>,
+    },
+    GeneratorDrop,
+    InlineAsm {
+        template: String,
+        operands: Vec<InlineAsmOperand>,
+        options: String,
+        line_spans: String,
+        destination: Option<BasicBlockIdx>,
+        cleanup: Option<BasicBlockIdx>,
+    },
+}
+
+impl Terminator {
+    /// The blocks control flow may continue at after this terminator, including unwind paths.
+    pub fn successors(&self) -> Vec<BasicBlockIdx> {
+        use Terminator::*;
+        match self {
+            Goto { target } => vec![*target],
+            SwitchInt { targets, otherwise, .. } => {
+                targets.iter().map(|t| t.target).chain(Some(*otherwise)).collect()
+            }
+            Resume | Abort | Return | Unreachable | GeneratorDrop => vec![],
+            Drop { target, unwind, .. } | DropAndReplace { target, unwind, .. } => {
+                Some(*target).into_iter().chain(*unwind).collect()
+            }
+            Call { target, cleanup, .. } | InlineAsm { destination: target, cleanup, .. } => {
+                target.iter().copied().chain(*cleanup).collect()
+            }
+            Assert { target, cleanup, .. } => Some(*target).into_iter().chain(*cleanup).collect(),
+            Yield { resume, drop, .. } => Some(*resume).into_iter().chain(*drop).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SwitchTarget {
+    pub value: u128,
+    pub target: BasicBlockIdx,
+}
+
+#[derive(Clone, Debug)]
+pub struct InlineAsmOperand {
+    pub in_value: Option<Operand>,
+    pub out_place: Option<Place>,
+    /// The compiler's textual rendering of the operand, which contains information such as the
+    /// register that is not yet exposed through the stable API.
+    pub raw_rpr: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum AssertMessage {
+    BoundsCheck { len: Operand, index: Operand },
+    Overflow(BinOp, Operand, Operand),
+    OverflowNeg(Operand),
+    DivisionByZero(Operand),
+    RemainderByZero(Operand),
+    ResumedAfterReturn(GeneratorKind),
+    ResumedAfterPanic(GeneratorKind),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    BitXor,
+    BitAnd,
+    BitOr,
+    Shl,
+    Shr,
+    Eq,
+    Lt,
+    Le,
+    Ne,
+    Ge,
+    Gt,
+    Offset,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnOp {
+    Not,
+    Neg,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeneratorKind {
+    Async(AsyncGeneratorKind),
+    Gen,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsyncGeneratorKind {
+    Block,
+    Closure,
+    Fn,
+}
+
+#[derive(Clone, Debug)]
+pub enum Statement {
+    Assign(Place, Rvalue),
+    FakeRead(Place),
+    SetDiscriminant { place: Place, variant_index: usize },
+    Deinit(Place),
+    StorageLive(Local),
+    StorageDead(Local),
+    Retag(Place),
+    AscribeUserType(Place),
+    Coverage,
+    CopyNonOverlapping { src: Operand, dst: Operand, count: Operand },
+    Nop,
+}
+
+#[derive(Clone, Debug)]
+pub enum Rvalue {
+    /// Yields the operand unchanged.
+    Use(Operand),
+    /// Creates an array where each element is the value of the operand, `count` times.
+    Repeat(Operand, Option<u64>),
+    /// Creates a reference of the indicated kind to the place.
+    Ref(BorrowKind, Place),
+    /// Creates a pointer to the thread-local static with the given definition.
+    ThreadLocalRef(CrateItem),
+    /// Creates a raw pointer with the indicated mutability to the place.
+    AddressOf(Mutability, Place),
+    /// Yields the length of the place, which must be an array or slice.
+    Len(Place),
+    Cast(CastKind, Operand, Ty),
+    BinaryOp(BinOp, Operand, Operand),
+    /// Same as `BinaryOp`, but yields `(T, bool)` with the `bool` indicating overflow.
+    CheckedBinaryOp(BinOp, Operand, Operand),
+    NullaryOp(NullOp, Ty),
+    UnaryOp(UnOp, Operand),
+    /// Reads the discriminant of an enum place.
+    Discriminant(Place),
+    Aggregate(AggregateKind, Vec<Operand>),
+    ShallowInitBox(Operand, Ty),
+    CopyForDeref(Place),
+}
+
+#[derive(Clone, Debug)]
+pub enum AggregateKind {
+    Array(Ty),
+    Tuple,
+    /// An ADT with its definition, the index of the variant being built and its generic
+    /// arguments. For unions, the field being initialized is also recorded.
+    Adt(CrateItem, usize, GenericArgs, Option<usize>),
+    Closure(CrateItem, GenericArgs),
+    Generator(CrateItem, GenericArgs),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorrowKind {
+    Shared,
+    Shallow,
+    Unique,
+    Mut { allow_two_phase_borrow: bool },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CastKind {
+    PointerExposeAddress,
+    PointerFromExposedAddress,
+    Pointer,
+    Misc,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NullOp {
+    SizeOf,
+    AlignOf,
+}
+
+#[derive(Clone, Debug)]
+pub enum Operand {
+    Copy(Place),
+    Move(Place),
+    Constant(Constant),
+}
+
+/// A constant operand. Constant values are not exposed yet beyond their type and the compiler's
+/// textual rendering.
+#[derive(Clone, Debug)]
+pub struct Constant {
+    pub ty: Ty,
+    pub literal: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Place {
+    pub local: Local,
+    pub projection: Vec<ProjectionElem>,
+}
+
+#[derive(Clone, Debug)]
+pub enum ProjectionElem {
+    Deref,
+    Field(usize, Ty),
+    Index(Local),
+    ConstantIndex { offset: u64, min_length: u64, from_end: bool },
+    Subslice { from: u64, to: u64, from_end: bool },
+    Downcast(usize),
+}
@@ -0,0 +1,169 @@
+//! Module that implements the public interface to the Stable MIR.
+//!
+//! This module shall contain all type definitions and APIs that we expect 3P tools to invoke to
+//! interact with the compiler.
+//!
+//! The goal is to eventually move this module to its own crate which shall be published on
+//! [crates.io](https://crates.io).
+//!
+//! ## Note:
+//!
+//! There shouldn't be any direct references to internal compiler constructs in this module.
+//! If you need an internal construct, consider using `rustc_internal` or `rustc_smir`.
+
+use std::cell::Cell;
+
+use crate::rustc_smir::Tables;
+
+use self::ty::{Ty, TyKind};
+
+pub mod mir;
+pub mod ty;
+
+/// The version of the data model exposed by this module.
+///
+/// This is bumped whenever a type in `stable_mir` changes in a way that tools may observe, so
+/// that tools can check which shape of data they are consuming.
+pub const VERSION: u32 = 1;
+
+/// Use String for now but we should replace it.
+pub type Symbol = String;
+
+/// The number that identifies a crate.
+pub type CrateNum = usize;
+
+/// A unique identification number for each item accessible for the current compilation unit.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DefId(pub(crate) usize);
+
+/// A list of crate items.
+pub type CrateItems = Vec<CrateItem>;
+
+/// Holds information about a crate.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Crate {
+    pub(crate) id: CrateNum,
+    pub name: Symbol,
+    pub is_local: bool,
+}
+
+/// Holds information about an item in the crate.
+/// For now, it only stores the item DefId. Use functions inside `rustc_internal` module to
+/// use this item.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CrateItem(pub(crate) DefId);
+
+impl CrateItem {
+    /// Return the body of this item, after all MIR optimizations ran.
+    pub fn body(&self) -> mir::Body {
+        with(|cx| cx.mir_body(self))
+    }
+
+    /// Return the path of this item, as printed by the compiler in diagnostics.
+    pub fn name(&self) -> String {
+        with(|cx| cx.item_name(self))
+    }
+
+    /// Return the source location of this item's definition.
+    pub fn span(&self) -> Span {
+        with(|cx| cx.item_span(self))
+    }
+}
+
+/// A source location, resolved to file, line and column so it stays meaningful without access
+/// to the compiler's source map.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Span {
+    pub file: String,
+    /// The 1-based line of the first character.
+    pub lo_line: usize,
+    /// The 0-based column of the first character.
+    pub lo_col: usize,
+    /// The 1-based line of the last character.
+    pub hi_line: usize,
+    /// The 0-based column of the last character.
+    pub hi_col: usize,
+}
+
+/// Return the function where execution starts if the current
+/// crate defines that. This is usually `main`, but could be
+/// `start` if the crate is a no-std crate.
+pub fn entry_fn() -> Option<CrateItem> {
+    with(|cx| cx.entry_fn())
+}
+
+/// Access to the local crate.
+pub fn local_crate() -> Crate {
+    with(|cx| cx.local_crate())
+}
+
+/// Try to find a crate with the given name.
+pub fn find_crate(name: &str) -> Option<Crate> {
+    with(|cx| cx.find_crate(name))
+}
+
+/// Retrieve all the crates the local crate depends on.
+pub fn external_crates() -> Vec<Crate> {
+    with(|cx| cx.external_crates())
+}
+
+/// Retrieve all items in the local crate that have a MIR associated with them.
+pub fn all_local_items() -> CrateItems {
+    with(|cx| cx.all_local_items())
+}
+
+/// The interface the compiler implements to answer queries made through `stable_mir`.
+///
+/// All conversions from the compiler's internal representation happen lazily, when a tool asks
+/// for a particular piece of information.
+pub trait Context {
+    fn entry_fn(&mut self) -> Option<CrateItem>;
+    /// Retrieve all items of the local crate that have a MIR associated with them.
+    fn all_local_items(&mut self) -> CrateItems;
+    fn mir_body(&mut self, item: &CrateItem) -> mir::Body;
+    /// The path of the item, as printed in diagnostics.
+    fn item_name(&mut self, item: &CrateItem) -> String;
+    fn item_span(&mut self, item: &CrateItem) -> Span;
+    /// Get information about the local crate.
+    fn local_crate(&self) -> Crate;
+    /// Retrieve a list of all external crates.
+    fn external_crates(&self) -> Vec<Crate>;
+
+    /// Find a crate with the given name.
+    fn find_crate(&self, name: &str) -> Option<Crate>;
+
+    /// Obtain the representation of a type.
+    fn ty_kind(&mut self, ty: Ty) -> TyKind;
+
+    /// HACK: Until we have fully stable consumers, we need an escape hatch
+    /// to get `DefId`s out of `CrateItem`s.
+    fn rustc_tables(&mut self, f: &mut dyn FnMut(&mut Tables<'_>));
+}
+
+thread_local! {
+    /// A thread local variable that stores a pointer to the tables mapping between TyCtxt
+    /// datastructures and stable MIR datastructures.
+    static TLV: Cell<*mut ()> = const { Cell::new(std::ptr::null_mut()) };
+}
+
+pub fn run(mut context: impl Context, f: impl FnOnce()) {
+    assert!(TLV.with(|tlv| tlv.get().is_null()));
+    fn g<'a>(mut context: &mut (dyn Context + 'a), f: impl FnOnce()) {
+        let ptr: *mut () = &mut context as *mut &mut _ as _;
+        TLV.with(|tlv| tlv.set(ptr));
+        f();
+        TLV.with(|tlv| tlv.set(std::ptr::null_mut()));
+    }
+    g(&mut context, f);
+}
+
+/// Loads the current context and calls a function with it.
+/// Do not nest these, as that will ICE.
+pub(crate) fn with<R>(f: impl FnOnce(&mut dyn Context) -> R) -> R {
+    let ptr = TLV.with(|tlv| tlv.get());
+    assert!(!ptr.is_null());
+    let wrapper = ptr as *mut &mut dyn Context;
+    // SAFETY: `run` keeps the context alive for as long as the pointer is stored in `TLV`.
+    let leaf: &mut dyn Context = unsafe { &mut **wrapper };
+    f(leaf)
+}
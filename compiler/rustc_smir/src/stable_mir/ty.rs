@@ -0,0 +1,95 @@
+use super::{with, DefId};
+
+/// A handle to a type, interned by the compiler. Use [`Ty::kind`] to inspect it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ty(pub(crate) usize);
+
+impl Ty {
+    pub fn kind(&self) -> TyKind {
+        with(|context| context.ty_kind(*self))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum TyKind {
+    RigidTy(RigidTy),
+    /// A generic parameter of the enclosing item, e.g. `T`.
+    Param(String),
+    /// A type the stable API cannot represent yet. Holds the compiler's textual rendering.
+    Unsupported(String),
+}
+
+#[derive(Clone, Debug)]
+pub enum RigidTy {
+    Bool,
+    Char,
+    Int(IntTy),
+    Uint(UintTy),
+    Float(FloatTy),
+    Adt(AdtDef, GenericArgs),
+    Foreign(ForeignDef),
+    Str,
+    Array(Ty, Option<u64>),
+    Slice(Ty),
+    RawPtr(Ty, Mutability),
+    Ref(Ty, Mutability),
+    FnDef(FnDef, GenericArgs),
+    FnPtr,
+    Closure(ClosureDef, GenericArgs),
+    Generator(GeneratorDef, GenericArgs),
+    Dynamic,
+    Never,
+    Tuple(Vec<Ty>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntTy {
+    Isize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UintTy {
+    Usize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatTy {
+    F32,
+    F64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mutability {
+    Not,
+    Mut,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ForeignDef(pub(crate) DefId);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FnDef(pub(crate) DefId);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ClosureDef(pub(crate) DefId);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GeneratorDef(pub(crate) DefId);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AdtDef(pub(crate) DefId);
+
+/// The generic arguments an item was instantiated with. Lifetimes and constants are not
+/// represented yet, so only the type arguments are listed, in declaration order.
+#[derive(Clone, Debug, Default)]
+pub struct GenericArgs(pub Vec<Ty>);
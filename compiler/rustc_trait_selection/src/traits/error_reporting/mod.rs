@@ -1,3 +1,4 @@
+mod obligation_tree;
 pub mod on_unimplemented;
 pub mod suggestions;
 
@@ -332,6 +333,7 @@ fn report_selection_error(
                         {
                             return;
                         }
+                        obligation_tree::dump_obligation_tree(self, &obligation, root_obligation);
                         let trait_ref = trait_predicate.to_poly_trait_ref();
                         let (post_message, pre_message, type_def) = self
                             .get_parent_trait_ref(obligation.cause.code())
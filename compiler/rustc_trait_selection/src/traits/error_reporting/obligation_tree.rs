@@ -0,0 +1,200 @@
+//! Writing the obligation tree of trait bounds that fail to hold to files,
+//! for `-Z dump-obligation-tree`.
+
+use crate::infer::InferCtxt;
+use crate::traits::select::{CandidateNode, ObligationNode, Outcome};
+use crate::traits::{PredicateObligation, SelectionContext, TraitQueryMode};
+use rustc_graphviz as dot;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::source_map::SourceMap;
+use serde_json::json;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Explains why `obligation` does not hold and writes the explanation to
+/// `<crate>.<item>.<line>_<col>.obligations.json` (and `.dot`) in the dump
+/// directory, if its item was selected with `-Z dump-obligation-tree`.
+pub(super) fn dump_obligation_tree<'tcx>(
+    infcx: &InferCtxt<'_, 'tcx>,
+    obligation: &PredicateObligation<'tcx>,
+    root_obligation: &PredicateObligation<'tcx>,
+) {
+    let tcx = infcx.tcx;
+    let Some(filters) = &tcx.sess.opts.unstable_opts.dump_obligation_tree else { return };
+    let def_id = obligation.cause.body_id.owner.to_def_id();
+    let item_path = ty::print::with_forced_impl_filename_line!(tcx.def_path_str(def_id));
+    if !filters.split('|').any(|filter| filter.trim() == "all" || item_path.contains(filter.trim()))
+    {
+        return;
+    }
+
+    // The root obligation is the bound the failing one was derived from, so
+    // explaining it shows every step in between. That doesn't work if the
+    // root isn't a trait bound, e.g. when it is a well-formedness check.
+    let explained = match root_obligation.predicate.kind().skip_binder() {
+        ty::PredicateKind::Trait(_) => root_obligation,
+        _ => obligation,
+    };
+    let (failed_predicate, tree) = with_no_trimmed_paths!({
+        // In canonical mode, overflow ends up in the tree instead of being a
+        // fatal error.
+        let mut selcx = SelectionContext::with_query_mode(infcx, TraitQueryMode::Canonical);
+        let failed_predicate = infcx.resolve_vars_if_possible(obligation.predicate).to_string();
+        (failed_predicate, selcx.explain_obligation(explained))
+    });
+
+    let loc = tcx.sess.source_map().lookup_char_pos(obligation.cause.span.lo());
+    let basename = format!(
+        "{}.{}.{}_{}.obligations",
+        tcx.crate_name(LOCAL_CRATE),
+        tcx.def_path(def_id).to_filename_friendly_no_crate(),
+        loc.line,
+        loc.col.0 + 1,
+    );
+    let directory = Path::new(&tcx.sess.opts.unstable_opts.dump_obligation_tree_dir);
+    if let Err(err) =
+        write_obligation_tree(tcx, directory, &basename, &item_path, &failed_predicate, &tree)
+    {
+        tcx.sess.err(&format!("failed to dump obligation tree: {}", err));
+    }
+}
+
+fn write_obligation_tree(
+    tcx: TyCtxt<'_>,
+    directory: &Path,
+    basename: &str,
+    item_path: &str,
+    failed_predicate: &str,
+    tree: &ObligationNode,
+) -> io::Result<()> {
+    fs::create_dir_all(directory)?;
+
+    let sm = tcx.sess.source_map();
+    let json = json!({
+        "item": item_path,
+        "failed_obligation": failed_predicate,
+        "tree": obligation_json(sm, tree),
+    });
+    fs::write(directory.join(format!("{}.json", basename)), serde_json::to_string_pretty(&json)?)?;
+
+    if tcx.sess.opts.unstable_opts.dump_obligation_tree_graphviz {
+        let mut graph = ObligationGraph { nodes: Vec::new(), edges: Vec::new() };
+        graph.add_obligation(tree);
+        let mut dot = Vec::new();
+        dot::render(&graph, &mut dot)?;
+        fs::write(directory.join(format!("{}.dot", basename)), dot)?;
+    }
+    Ok(())
+}
+
+fn obligation_json(sm: &SourceMap, node: &ObligationNode) -> serde_json::Value {
+    json!({
+        "predicate": node.predicate,
+        "location": sm.span_to_embeddable_string(node.span),
+        "result": node.result.as_str(),
+        "note": node.note,
+        "candidates": node.candidates.iter().map(|c| candidate_json(sm, c)).collect::<Vec<_>>(),
+    })
+}
+
+fn candidate_json(sm: &SourceMap, candidate: &CandidateNode) -> serde_json::Value {
+    json!({
+        "kind": candidate.kind,
+        "description": candidate.description,
+        "location": candidate.span.map(|span| sm.span_to_embeddable_string(span)),
+        "result": candidate.result.as_str(),
+        "rejection": candidate.rejection,
+        "nested": candidate.nested.iter().map(|o| obligation_json(sm, o)).collect::<Vec<_>>(),
+    })
+}
+
+/// The obligation tree flattened into a graph, with obligations and
+/// candidates as nodes.
+struct ObligationGraph {
+    /// The label of each node, whether it is a candidate, and its outcome.
+    nodes: Vec<(String, bool, Outcome)>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl ObligationGraph {
+    fn add_obligation(&mut self, node: &ObligationNode) -> usize {
+        let mut label = format!("{}\n{}", node.predicate, node.result.as_str());
+        if let Some(note) = node.note {
+            label.push('\n');
+            label.push_str(note);
+        }
+        let index = self.push_node(label, false, node.result);
+        for candidate in &node.candidates {
+            let candidate_index = self.add_candidate(candidate);
+            self.edges.push((index, candidate_index));
+        }
+        index
+    }
+
+    fn add_candidate(&mut self, candidate: &CandidateNode) -> usize {
+        let mut label =
+            format!("{}: {}\n{}", candidate.kind, candidate.description, candidate.result.as_str());
+        if let Some(rejection) = &candidate.rejection {
+            label.push('\n');
+            label.push_str(rejection);
+        }
+        let index = self.push_node(label, true, candidate.result);
+        for nested in &candidate.nested {
+            let nested_index = self.add_obligation(nested);
+            self.edges.push((index, nested_index));
+        }
+        index
+    }
+
+    fn push_node(&mut self, label: String, is_candidate: bool, result: Outcome) -> usize {
+        self.nodes.push((label, is_candidate, result));
+        self.nodes.len() - 1
+    }
+}
+
+impl<'a> dot::GraphWalk<'a> for ObligationGraph {
+    type Node = usize;
+    type Edge = (usize, usize);
+    fn nodes(&self) -> dot::Nodes<'_, usize> {
+        (0..self.nodes.len()).collect()
+    }
+    fn edges(&self) -> dot::Edges<'_, (usize, usize)> {
+        self.edges[..].into()
+    }
+    fn source(&self, edge: &(usize, usize)) -> usize {
+        edge.0
+    }
+    fn target(&self, edge: &(usize, usize)) -> usize {
+        edge.1
+    }
+}
+
+impl<'a> dot::Labeller<'a> for ObligationGraph {
+    type Node = usize;
+    type Edge = (usize, usize);
+    fn graph_id(&self) -> dot::Id<'_> {
+        dot::Id::new("ObligationTree").unwrap()
+    }
+    fn node_id(&self, n: &usize) -> dot::Id<'_> {
+        dot::Id::new(format!("n{}", n)).unwrap()
+    }
+    fn node_label(&self, n: &usize) -> dot::LabelText<'_> {
+        dot::LabelText::label(self.nodes[*n].0.clone())
+    }
+    fn node_shape(&self, n: &usize) -> Option<dot::LabelText<'_>> {
+        let (_, is_candidate, _) = self.nodes[*n];
+        is_candidate.then(|| dot::LabelText::label("box"))
+    }
+    fn node_style(&self, n: &usize) -> dot::Style {
+        // Make what failed stand out.
+        let (_, _, result) = self.nodes[*n];
+        if result == Outcome::Holds {
+            dot::Style::None
+        } else {
+            dot::Style::Bold
+        }
+    }
+}
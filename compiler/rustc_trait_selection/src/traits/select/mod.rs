@@ -51,6 +51,9 @@
 
 mod candidate_assembly;
 mod confirmation;
+mod obligation_tree;
+
+pub use obligation_tree::{CandidateNode, ObligationNode, Outcome};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum IntercrateAmbiguityCause {
@@ -0,0 +1,318 @@
+//! Explaining the outcome of selection as a tree of obligations, for
+//! `-Z dump-obligation-tree`.
+//!
+//! For a trait obligation, this assembles the candidates just like selection
+//! does, evaluates each of them, and recursively explains the nested
+//! obligations of the ones that could be confirmed. Impls of the trait that
+//! were not even assembled as candidates are listed with the reason their
+//! header does not match. Everything happens in a probe, so explaining an
+//! obligation has no effect on inference.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{self, TypeVisitable};
+use rustc_span::Span;
+
+use super::EvaluationResult::{self, *};
+use super::{
+    OverflowError, PredicateObligation, ProvisionalEvaluationCache, SelectionCandidate,
+    SelectionContext, SelectionError, TraitObligation, TraitObligationStackList,
+};
+use crate::traits::specialize::to_pretty_impl_header;
+
+/// An obligation, together with how selection tried to prove it.
+#[derive(Debug)]
+pub struct ObligationNode {
+    pub predicate: String,
+    pub span: Span,
+    pub result: Outcome,
+    /// Why the candidates of this obligation were not explained, if they weren't.
+    pub note: Option<&'static str>,
+    pub candidates: Vec<CandidateNode>,
+}
+
+/// A way of proving a trait obligation that selection considered.
+#[derive(Debug)]
+pub struct CandidateNode {
+    /// The kind of candidate, e.g. `impl` or `where-clause`.
+    pub kind: &'static str,
+    pub description: String,
+    pub span: Option<Span>,
+    pub result: Outcome,
+    /// Why the candidate could not be confirmed, if it couldn't.
+    pub rejection: Option<String>,
+    /// The obligations the candidate requires to hold. Only the ones that
+    /// don't are explained further.
+    pub nested: Vec<ObligationNode>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Holds,
+    Ambiguous,
+    Fails,
+    Overflow,
+}
+
+impl Outcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Holds => "holds",
+            Outcome::Ambiguous => "ambiguous",
+            Outcome::Fails => "fails",
+            Outcome::Overflow => "overflow",
+        }
+    }
+
+    fn from_evaluation(result: Result<EvaluationResult, OverflowError>) -> Outcome {
+        match result {
+            Ok(EvaluatedToOk | EvaluatedToOkModuloRegions | EvaluatedToOkModuloOpaqueTypes) => {
+                Outcome::Holds
+            }
+            Ok(EvaluatedToAmbig | EvaluatedToUnknown) => Outcome::Ambiguous,
+            Ok(EvaluatedToRecur | EvaluatedToErr) => Outcome::Fails,
+            Err(_) => Outcome::Overflow,
+        }
+    }
+}
+
+impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
+    /// Explains how selection tries to prove `obligation`. The context
+    /// should be in canonical query mode, so that overflow is reported in the
+    /// tree instead of aborting compilation.
+    pub fn explain_obligation(&mut self, obligation: &PredicateObligation<'tcx>) -> ObligationNode {
+        self.infcx.probe(|_| self.explain_obligation_recursively(obligation, &mut Vec::new()))
+    }
+
+    fn explain_obligation_recursively(
+        &mut self,
+        obligation: &PredicateObligation<'tcx>,
+        explaining: &mut Vec<ty::Predicate<'tcx>>,
+    ) -> ObligationNode {
+        let predicate = self.infcx.resolve_vars_if_possible(obligation.predicate);
+        let mut node = ObligationNode {
+            predicate: predicate.to_string(),
+            span: obligation.cause.span,
+            result: Outcome::from_evaluation(self.evaluate_root_obligation(obligation)),
+            note: None,
+            candidates: Vec::new(),
+        };
+
+        let bound_predicate = predicate.kind();
+        let ty::PredicateKind::Trait(trait_predicate) = bound_predicate.skip_binder() else {
+            return node;
+        };
+        if explaining.contains(&predicate) {
+            node.note = Some("cycle: the obligation is already being explained");
+            return node;
+        }
+        if !self.tcx().recursion_limit().value_within_limit(explaining.len()) {
+            node.note = Some("the recursion limit was reached");
+            return node;
+        }
+
+        let trait_obligation = obligation.with(bound_predicate.rebind(trait_predicate));
+        explaining.push(predicate);
+        node.candidates = self.explain_candidates(&trait_obligation, explaining, &mut node.note);
+        explaining.pop();
+        node
+    }
+
+    fn explain_candidates(
+        &mut self,
+        obligation: &TraitObligation<'tcx>,
+        explaining: &mut Vec<ty::Predicate<'tcx>>,
+        note: &mut Option<&'static str>,
+    ) -> Vec<CandidateNode> {
+        let pec = ProvisionalEvaluationCache::default();
+        let stack = self.push_stack(TraitObligationStackList::empty(&pec), obligation);
+        let candidate_set = match self.assemble_candidates(&stack) {
+            Ok(candidate_set) => candidate_set,
+            Err(_) => {
+                *note = Some("assembling the candidates failed");
+                return Vec::new();
+            }
+        };
+        if candidate_set.ambiguous {
+            *note = Some("the self type is not yet known, so no candidates were assembled");
+        }
+
+        let mut candidates = Vec::new();
+        let mut assembled_impls = Vec::new();
+        for candidate in candidate_set.vec {
+            if let SelectionCandidate::ImplCandidate(impl_def_id) = candidate {
+                assembled_impls.push(impl_def_id);
+            }
+            let (kind, description, span) = self.describe_candidate(&candidate);
+            let result = Outcome::from_evaluation(self.evaluate_candidate(&stack, &candidate));
+            let (rejection, nested) =
+                self.infcx.probe(|_| match self.confirm_candidate(obligation, candidate) {
+                    Ok(selection) => {
+                        let nested = selection
+                            .nested_obligations()
+                            .iter()
+                            .map(|nested| self.explain_nested_obligation(nested, explaining))
+                            .collect();
+                        (None, nested)
+                    }
+                    Err(error) => (Some(self.describe_selection_error(&error)), Vec::new()),
+                });
+            candidates.push(CandidateNode { kind, description, span, result, rejection, nested });
+        }
+
+        candidates.extend(self.explain_unmatched_impls(obligation, &assembled_impls));
+        candidates
+    }
+
+    /// Explains a nested obligation of a candidate, but only goes into its
+    /// candidates if it doesn't hold, so that the tree stays focused on what
+    /// went wrong.
+    fn explain_nested_obligation(
+        &mut self,
+        obligation: &PredicateObligation<'tcx>,
+        explaining: &mut Vec<ty::Predicate<'tcx>>,
+    ) -> ObligationNode {
+        let result = Outcome::from_evaluation(self.evaluate_root_obligation(obligation));
+        if result == Outcome::Holds {
+            ObligationNode {
+                predicate: self.infcx.resolve_vars_if_possible(obligation.predicate).to_string(),
+                span: obligation.cause.span,
+                result,
+                note: None,
+                candidates: Vec::new(),
+            }
+        } else {
+            self.explain_obligation_recursively(obligation, explaining)
+        }
+    }
+
+    /// Lists the impls of the trait that may apply to the self type of
+    /// `obligation` but were not assembled as candidates, because their
+    /// header does not match the obligation.
+    fn explain_unmatched_impls(
+        &mut self,
+        obligation: &TraitObligation<'tcx>,
+        assembled_impls: &[DefId],
+    ) -> Vec<CandidateNode> {
+        let mut unmatched = Vec::new();
+        if obligation.predicate.references_error()
+            || obligation.predicate.skip_binder().self_ty().is_ty_var()
+        {
+            return unmatched;
+        }
+
+        let tcx = self.tcx();
+        tcx.for_each_relevant_impl(
+            obligation.predicate.def_id(),
+            obligation.predicate.skip_binder().self_ty(),
+            |impl_def_id| {
+                if assembled_impls.contains(&impl_def_id) {
+                    return;
+                }
+                let impl_trait_ref = tcx.bound_impl_trait_ref(impl_def_id).unwrap();
+                let matches = self
+                    .infcx
+                    .probe(|_| self.match_impl(impl_def_id, impl_trait_ref, obligation).is_ok());
+                if matches {
+                    // The impl wasn't assembled for another reason, e.g.
+                    // because the trait is implemented by the compiler.
+                    return;
+                }
+                let rejection = if tcx.impl_polarity(impl_def_id) == ty::ImplPolarity::Reservation {
+                    "reservation impls never apply outside of coherence checking".to_owned()
+                } else {
+                    format!(
+                        "the impl is for `{}`, which does not match `{}`",
+                        impl_trait_ref.0,
+                        obligation.predicate.skip_binder().trait_ref,
+                    )
+                };
+                let (kind, description, span) =
+                    self.describe_candidate(&SelectionCandidate::ImplCandidate(impl_def_id));
+                unmatched.push(CandidateNode {
+                    kind,
+                    description,
+                    span,
+                    result: Outcome::Fails,
+                    rejection: Some(rejection),
+                    nested: Vec::new(),
+                });
+            },
+        );
+        unmatched
+    }
+
+    fn describe_candidate(
+        &self,
+        candidate: &SelectionCandidate<'tcx>,
+    ) -> (&'static str, String, Option<Span>) {
+        let tcx = self.tcx();
+        let local_span = |def_id: DefId| def_id.as_local().map(|_| tcx.def_span(def_id));
+        match *candidate {
+            SelectionCandidate::ImplCandidate(def_id) => {
+                let header =
+                    to_pretty_impl_header(tcx, def_id).unwrap_or_else(|| tcx.def_path_str(def_id));
+                ("impl", header, Some(tcx.def_span(def_id)))
+            }
+            SelectionCandidate::ParamCandidate(bound) => {
+                ("where-clause", format!("where clause `{}`", bound), None)
+            }
+            SelectionCandidate::AutoImplCandidate(def_id) => (
+                "auto-impl",
+                format!("automatic implementation of `{}`", tcx.def_path_str(def_id)),
+                None,
+            ),
+            SelectionCandidate::ProjectionCandidate(index) => (
+                "projection-bound",
+                format!("bound #{} on the definition of the associated type", index),
+                None,
+            ),
+            SelectionCandidate::ObjectCandidate(index) => (
+                "object",
+                format!("supertrait #{} of the trait object's principal trait", index),
+                None,
+            ),
+            SelectionCandidate::TraitAliasCandidate(def_id) => (
+                "trait-alias",
+                format!("trait alias `{}`", tcx.def_path_str(def_id)),
+                local_span(def_id),
+            ),
+            SelectionCandidate::ConstDestructCandidate(Some(def_id)) => (
+                "impl",
+                format!("`Drop` impl `{}`", tcx.def_path_str(def_id)),
+                Some(tcx.def_span(def_id)),
+            ),
+            SelectionCandidate::BuiltinCandidate { .. }
+            | SelectionCandidate::ClosureCandidate
+            | SelectionCandidate::GeneratorCandidate
+            | SelectionCandidate::FnPointerCandidate { .. }
+            | SelectionCandidate::DiscriminantKindCandidate
+            | SelectionCandidate::PointeeCandidate
+            | SelectionCandidate::TraitUpcastingUnsizeCandidate(_)
+            | SelectionCandidate::BuiltinObjectCandidate
+            | SelectionCandidate::BuiltinUnsizeCandidate
+            | SelectionCandidate::ConstDestructCandidate(None) => {
+                ("builtin", format!("{:?}", candidate), None)
+            }
+        }
+    }
+
+    fn describe_selection_error(&self, error: &SelectionError<'tcx>) -> String {
+        match error {
+            SelectionError::Unimplemented => "the trait is not implemented".to_owned(),
+            SelectionError::OutputTypeParameterMismatch(expected, found, _) => format!(
+                "expected `{}`, found `{}`",
+                expected.print_only_trait_path(),
+                found.print_only_trait_path(),
+            ),
+            SelectionError::TraitNotObjectSafe(def_id) => {
+                format!("the trait `{}` is not object safe", self.tcx().def_path_str(*def_id))
+            }
+            SelectionError::NotConstEvaluatable(_) => {
+                "a constant could not be evaluated".to_owned()
+            }
+            SelectionError::Overflow(_) => "overflow evaluating the candidate".to_owned(),
+            SelectionError::ErrorReporting => "an error has already been reported".to_owned(),
+            SelectionError::Ambiguous(_) => "multiple impls apply".to_owned(),
+        }
+    }
+}
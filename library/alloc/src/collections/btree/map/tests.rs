@@ -2336,3 +2336,235 @@ fn from_array() {
     let unordered_duplicates = BTreeMap::from([(3, 4), (1, 2), (1, 2)]);
     assert_eq!(map, unordered_duplicates);
 }
+
+#[test]
+fn test_cursor_empty() {
+    let mut map: BTreeMap<i32, i32> = BTreeMap::new();
+    let mut cur = map.lower_bound(Unbounded);
+    assert_eq!(cur.key_value(), None);
+    assert_eq!(cur.peek_next(), None);
+    assert_eq!(cur.peek_prev(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+    cur.move_prev();
+    assert_eq!(cur.value(), None);
+
+    let mut cur = map.upper_bound_mut(Included(&3));
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.remove_current(), None);
+    cur.insert_after(1, 10);
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_next(), Some((&1, &mut 10)));
+    cur.insert_before(2, 20);
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_prev(), Some((&2, &mut 20)));
+    map.check();
+    assert_eq!(map, BTreeMap::from([(1, 10), (2, 20)]));
+}
+
+#[test]
+fn test_cursor() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let map = BTreeMap::from_iter((0..size).map(|i| (i, i * 10)));
+    assert_eq!(map.height(), Some(2));
+
+    let mut cur = map.lower_bound(Unbounded);
+    for i in 0..size {
+        assert_eq!(cur.key_value(), Some((&i, &(i * 10))));
+        assert_eq!(cur.peek_prev().map(|(k, _)| *k), i.checked_sub(1));
+        cur.move_next();
+    }
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_next(), Some((&0, &0)));
+    assert_eq!(cur.peek_prev(), Some((&(size - 1), &((size - 1) * 10))));
+
+    let mut cur = map.upper_bound(Unbounded);
+    for i in (0..size).rev() {
+        assert_eq!(cur.key(), Some(&i));
+        cur.move_prev();
+    }
+    assert_eq!(cur.key(), None);
+    cur.move_prev();
+    assert_eq!(cur.key(), Some(&(size - 1)));
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&0));
+}
+
+#[test]
+fn test_cursor_bounds() {
+    let map = BTreeMap::from_iter((0..MIN_INSERTS_HEIGHT_2).map(|i| (i * 2, ())));
+    let last = (MIN_INSERTS_HEIGHT_2 - 1) * 2;
+    for i in 0..=last + 1 {
+        let odd = i % 2 == 1;
+        let next = if odd { i + 1 } else { i };
+        let prev = if odd { i - 1 } else { i };
+        let expected = |k: usize| if k <= last { Some(k) } else { None };
+        assert_eq!(map.lower_bound(Included(&i)).key().copied(), expected(next));
+        assert_eq!(map.lower_bound(Excluded(&i)).key().copied(), expected(prev + 2));
+        assert_eq!(map.upper_bound(Included(&i)).key().copied(), Some(prev));
+        assert_eq!(
+            map.upper_bound(Excluded(&i)).key().copied(),
+            if odd { Some(prev) } else { i.checked_sub(2) }
+        );
+    }
+    assert_eq!(map.lower_bound(Unbounded).key(), Some(&0));
+    assert_eq!(map.upper_bound(Unbounded).key(), Some(&last));
+}
+
+#[test]
+fn test_cursor_mut_values() {
+    let mut map = BTreeMap::from_iter((0..MIN_INSERTS_HEIGHT_1).map(|i| (i, i)));
+    let mut cur = map.lower_bound_mut(Excluded(&2));
+    assert_eq!(cur.key_value(), Some((&3, &3)));
+    *cur.value_mut().unwrap() += 100;
+    *cur.peek_next().unwrap().1 += 100;
+    *cur.peek_prev().unwrap().1 += 100;
+    let (k, v) = cur.key_value_mut().unwrap();
+    *v += *k;
+    assert_eq!(cur.as_cursor().peek_next(), Some((&4, &104)));
+    cur.move_next();
+    cur.move_next();
+    assert_eq!(cur.as_cursor().key_value(), Some((&5, &5)));
+    assert_eq!(map[&2], 102);
+    assert_eq!(map[&3], 106);
+    assert_eq!(map[&4], 104);
+
+    let mut cur = map.lower_bound_mut(Unbounded);
+    let mut count = 0;
+    while let Some(v) = cur.value_mut() {
+        *v = 0;
+        count += 1;
+        cur.move_next();
+    }
+    assert_eq!(count, MIN_INSERTS_HEIGHT_1);
+    assert!(map.values().all(|&v| v == 0));
+}
+
+#[test]
+fn test_cursor_mut_key() {
+    let mut map = BTreeMap::from([(1, 'a'), (3, 'b'), (5, 'c')]);
+    let mut cur = map.lower_bound_mut(Included(&3));
+    unsafe { *cur.key_mut_unchecked().unwrap() = 4 };
+    map.check();
+    assert_eq!(map, BTreeMap::from([(1, 'a'), (4, 'b'), (5, 'c')]));
+}
+
+#[test]
+fn test_cursor_mut_insert_after() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map = BTreeMap::from([(0, 0)]);
+    let mut cur = map.lower_bound_mut(Unbounded);
+    for i in (1..size).rev() {
+        cur.insert_after(i, i * 10);
+        assert_eq!(cur.key_value(), Some((&0, &0)));
+        assert_eq!(cur.peek_next(), Some((&i, &mut (i * 10))));
+    }
+    map.check();
+    assert!(map.height() >= Some(2));
+    assert!(map.into_iter().eq((0..size).map(|i| (i, i * 10))));
+}
+
+#[test]
+fn test_cursor_mut_insert_before() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Unbounded);
+    for i in 0..size {
+        cur.insert_before(i, ());
+        assert_eq!(cur.key(), None);
+        assert_eq!(cur.peek_prev().map(|(k, _)| *k), Some(i));
+    }
+    map.check();
+    assert_eq!(map.height(), Some(2));
+    assert!(map.into_keys().eq(0..size));
+}
+
+#[test]
+fn test_cursor_mut_insert_around() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map = BTreeMap::from_iter((0..size).map(|i| (i * 4 + 2, ())));
+    let mut cur = map.lower_bound_mut(Unbounded);
+    while let Some(&k) = cur.key() {
+        cur.insert_before(k - 1, ());
+        cur.insert_after(k + 1, ());
+        assert_eq!(cur.key(), Some(&k));
+        cur.move_next();
+        cur.move_next();
+    }
+    map.check();
+    assert_eq!(map.len(), size * 3);
+    assert!(map.into_keys().eq((0..size).flat_map(|i| [i * 4 + 1, i * 4 + 2, i * 4 + 3])));
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the current element")]
+fn test_cursor_mut_insert_after_panic_current() {
+    let mut map = BTreeMap::from([(1, ()), (3, ())]);
+    map.lower_bound_mut(Included(&1)).insert_after(1, ());
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered below the next element")]
+fn test_cursor_mut_insert_after_panic_next() {
+    let mut map = BTreeMap::from([(1, ()), (3, ())]);
+    map.lower_bound_mut(Included(&1)).insert_after(4, ());
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered below the current element")]
+fn test_cursor_mut_insert_before_panic_current() {
+    let mut map = BTreeMap::from([(1, ()), (3, ())]);
+    map.lower_bound_mut(Included(&3)).insert_before(3, ());
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the previous element")]
+fn test_cursor_mut_insert_before_panic_prev() {
+    let mut map = BTreeMap::from([(1, ()), (3, ())]);
+    map.lower_bound_mut(Included(&3)).insert_before(0, ());
+}
+
+#[test]
+fn test_cursor_mut_remove() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map = BTreeMap::from_iter((0..size).map(|i| (i, i)));
+    let mut cur = map.lower_bound_mut(Unbounded);
+    let mut removed = Vec::new();
+    while cur.key().is_some() {
+        removed.push(cur.remove_current().unwrap().0);
+        cur.move_next();
+    }
+    assert_eq!(cur.remove_current(), None);
+    map.check();
+    assert!(removed.into_iter().eq((0..size).step_by(2)));
+    assert!(map.keys().copied().eq((1..size).step_by(2)));
+
+    let mut cur = map.upper_bound_mut(Unbounded);
+    while let Some((k, v)) = cur.remove_current_and_move_back() {
+        assert_eq!(k, v);
+        assert_eq!(cur.peek_next(), None);
+    }
+    map.check();
+    assert!(map.is_empty());
+
+    map.lower_bound_mut(Unbounded).insert_after(1, 1);
+    map.check();
+    assert_eq!(map, BTreeMap::from([(1, 1)]));
+}
+
+#[test]
+fn test_cursor_mut_remove_drop() {
+    let a = CrashTestDummy::new(0);
+    let b = CrashTestDummy::new(1);
+    let mut map = BTreeMap::new();
+    map.insert(a.spawn(Panic::Never), ());
+    map.insert(b.spawn(Panic::Never), ());
+    let mut cur = map.lower_bound_mut(Unbounded);
+    drop(cur.remove_current());
+    assert_eq!(a.dropped(), 1);
+    assert_eq!(b.dropped(), 0);
+    drop(map);
+    assert_eq!(b.dropped(), 1);
+}
@@ -5,7 +5,7 @@
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
-use std::ops::Bound::{Excluded, Included};
+use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 
 #[test]
@@ -854,3 +854,52 @@ fn test_range_panic_2() {
 
     let _invalid_range = set.range((Excluded(&5), Excluded(&5)));
 }
+
+#[test]
+fn test_cursor() {
+    let set = BTreeSet::from([1, 3, 5]);
+    let mut cur = set.lower_bound(Included(&2));
+    assert_eq!(cur.value(), Some(&3));
+    assert_eq!(cur.peek_prev(), Some(&1));
+    assert_eq!(cur.peek_next(), Some(&5));
+    cur.move_next();
+    cur.move_next();
+    assert_eq!(cur.value(), None);
+    assert_eq!(cur.peek_next(), Some(&1));
+    cur.move_prev();
+    assert_eq!(cur.value(), Some(&5));
+
+    let cur = set.upper_bound(Excluded(&1));
+    assert_eq!(cur.value(), None);
+    assert_eq!(cur.peek_prev(), Some(&5));
+    assert_eq!(set.upper_bound(Unbounded).value(), Some(&5));
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut set = BTreeSet::from([2, 4, 6]);
+    let mut cur = set.lower_bound_mut(Included(&4));
+    cur.insert_before(3);
+    cur.insert_after(5);
+    assert_eq!(cur.value(), Some(&4));
+    assert_eq!(cur.as_cursor().peek_next(), Some(&5));
+    assert_eq!(cur.remove_current(), Some(4));
+    assert_eq!(cur.value(), Some(&5));
+    assert_eq!(cur.remove_current_and_move_back(), Some(5));
+    assert_eq!(cur.value(), Some(&3));
+    cur.move_prev();
+    cur.move_prev();
+    assert_eq!(cur.value(), None);
+    cur.insert_after(1);
+    cur.insert_before(7);
+    assert_eq!(cur.peek_next(), Some(&1));
+    assert_eq!(cur.peek_prev(), Some(&7));
+    assert!(set.iter().eq(&[1, 2, 3, 6, 7]));
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered below the next element")]
+fn test_cursor_mut_insert_unordered() {
+    let mut set = BTreeSet::from([1, 3]);
+    set.lower_bound_mut(Included(&1)).insert_after(3);
+}
@@ -111,6 +111,9 @@
 #![feature(const_pin)]
 #![feature(cstr_from_bytes_until_nul)]
 #![feature(dispatch_from_dyn)]
+#![feature(error_generic_member_access)]
+#![feature(error_in_core)]
+#![feature(error_iter)]
 #![feature(exact_size_is_empty)]
 #![feature(extend_one)]
 #![feature(fmt_internals)]
@@ -127,6 +130,7 @@
 #![feature(nonnull_slice_from_raw_parts)]
 #![feature(pattern)]
 #![feature(pointer_byte_offsets)]
+#![feature(provide_any)]
 #![feature(ptr_internals)]
 #![feature(ptr_metadata)]
 #![feature(ptr_sub_ptr)]
@@ -177,6 +181,7 @@
 #![cfg_attr(test, feature(test))]
 #![feature(unboxed_closures)]
 #![feature(unsized_fn_params)]
+#![feature(with_negative_coherence)]
 #![feature(c_unwind)]
 //
 // Rustdoc features:
@@ -218,6 +223,8 @@ mod boxed {
 }
 pub mod borrow;
 pub mod collections;
+#[cfg(not(test))]
+pub mod error;
 #[cfg(not(no_global_oom_handling))]
 pub mod ffi;
 pub mod fmt;
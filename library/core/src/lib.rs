@@ -315,6 +315,8 @@ pub mod assert_matches {
 pub mod async_iter;
 pub mod cell;
 pub mod char;
+#[unstable(feature = "error_in_core", issue = "none")]
+pub mod error;
 pub mod ffi;
 pub mod iter;
 #[unstable(feature = "once_cell", issue = "74465")]
@@ -192,3 +192,28 @@ fn test_provider_intermediate() {
     let obj: &dyn OtherTrait = &SomeConcreteType { some_string: "hello".to_owned() };
     assert_eq!(obj.get_ref::<str>().unwrap(), "hello");
 }
+
+// Test that a provider can find out which type is being requested.
+#[test]
+fn test_would_be_satisfied() {
+    struct Probe;
+
+    impl Provider for Probe {
+        fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
+            assert!(!demand.would_be_satisfied_by_value_of::<u32>());
+            if demand.would_be_satisfied_by_value_of::<u8>() {
+                demand.provide_value::<u8>(|| 1);
+                assert!(!demand.would_be_satisfied_by_value_of::<u8>());
+            }
+            if demand.would_be_satisfied_by_ref_of::<str>() {
+                demand.provide_ref::<str>("probe");
+                assert!(!demand.would_be_satisfied_by_ref_of::<str>());
+            }
+            assert!(!demand.would_be_satisfied_by_ref_of::<String>());
+        }
+    }
+
+    assert_eq!(request_value::<u8>(&Probe), Some(1));
+    assert_eq!(request_ref::<str>(&Probe), Some("probe"));
+    assert_eq!(request_value::<u16>(&Probe), None);
+}
@@ -0,0 +1,57 @@
+use core::error::Error;
+use core::fmt;
+
+#[derive(Debug, PartialEq)]
+struct Leaf;
+
+#[derive(Debug)]
+struct Wrapper(Leaf);
+
+impl fmt::Display for Leaf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("leaf")
+    }
+}
+
+impl fmt::Display for Wrapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("wrapper")
+    }
+}
+
+impl Error for Leaf {}
+
+impl Error for Wrapper {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[test]
+fn downcast_ref() {
+    let mut leaf = Leaf;
+    let err = &mut leaf as &mut (dyn Error + 'static);
+    assert!(err.is::<Leaf>());
+    assert_eq!(err.downcast_ref::<Leaf>(), Some(&Leaf));
+    assert!(err.downcast_ref::<Wrapper>().is_none());
+    assert_eq!(err.downcast_mut::<Leaf>(), Some(&mut Leaf));
+}
+
+#[test]
+fn chain() {
+    let wrapper = Wrapper(Leaf);
+    let messages: Vec<_> = (&wrapper as &dyn Error).chain().map(|e| e.to_string()).collect();
+    assert_eq!(messages, ["wrapper", "leaf"]);
+}
+
+#[test]
+fn core_type_impls() {
+    fn assert_error<E: Error>(_: &E) {}
+
+    assert_error(&"x".parse::<u32>().unwrap_err());
+    assert_error(&"x".parse::<bool>().unwrap_err());
+    assert_error(&u8::try_from(300_u32).unwrap_err());
+    assert_error(&char::try_from(0xD800_u32).unwrap_err());
+    assert_error(&fmt::Error);
+    assert_error(&&fmt::Error);
+}
@@ -26,6 +26,8 @@
 #![feature(div_duration)]
 #![feature(duration_consts_float)]
 #![feature(duration_constants)]
+#![feature(error_in_core)]
+#![feature(error_iter)]
 #![feature(exact_size_is_empty)]
 #![feature(extern_types)]
 #![feature(flt2dec)]
@@ -113,6 +115,7 @@
 mod cmp;
 mod const_ptr;
 mod convert;
+mod error;
 mod fmt;
 mod future;
 mod hash;
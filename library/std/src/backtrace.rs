@@ -9,6 +9,50 @@
 //! implementing `std::error::Error`) to get a causal chain of where an error
 //! was generated.
 //!
+//! ## Attaching a backtrace to an error
+//!
+//! An error type makes its backtrace available by providing it from
+//! [`Error::provide`]. Code that only has a `dyn Error` can then retrieve it with
+//! `request_ref::<Backtrace>()`, without knowing the concrete error type. This is
+//! how [`Report::show_backtrace`] finds the backtrace to print, so wrapping errors,
+//! `Box<dyn Error>` and [`io::Error`] pass it through.
+//!
+//! ```
+//! #![feature(backtrace)]
+//! #![feature(provide_any)]
+//! #![feature(error_generic_member_access)]
+//! use std::any::Demand;
+//! use std::backtrace::Backtrace;
+//! use std::error::Error;
+//! use std::fmt;
+//!
+//! #[derive(Debug)]
+//! struct MyError {
+//!     backtrace: Backtrace,
+//! }
+//!
+//! impl fmt::Display for MyError {
+//!     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+//!         f.write_str("something went wrong")
+//!     }
+//! }
+//!
+//! impl Error for MyError {
+//!     fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
+//!         demand.provide_ref::<Backtrace>(&self.backtrace);
+//!     }
+//! }
+//!
+//! let err: Box<dyn Error> = Box::new(MyError { backtrace: Backtrace::capture() });
+//! if let Some(backtrace) = err.request_ref::<Backtrace>() {
+//!     eprintln!("{err}\n{backtrace}");
+//! }
+//! ```
+//!
+//! [`Error::provide`]: crate::error::Error::provide
+//! [`Report::show_backtrace`]: crate::error::Report::show_backtrace
+//! [`io::Error`]: crate::io::Error
+//!
 //! > **Note**: this module is unstable and is designed in [RFC 2504], and you
 //! > can learn more about its status in the [tracking issue].
 //!
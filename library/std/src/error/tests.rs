@@ -441,3 +441,33 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let actual = report.to_string();
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn provide_through_wrappers() {
+    #[derive(Debug)]
+    struct Status(u16);
+
+    impl fmt::Display for Status {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "status {}", self.0)
+        }
+    }
+
+    impl Error for Status {
+        fn provide<'a>(&'a self, req: &mut Demand<'a>) {
+            req.provide_value::<u16>(|| self.0).provide_ref::<Status>(self);
+        }
+    }
+
+    let boxed: Box<dyn Error> = Box::new(Box::new(Status(404)));
+    assert_eq!(boxed.request_value::<u16>(), Some(404));
+    assert_eq!(boxed.request_ref::<Status>().map(|s| s.0), Some(404));
+
+    let io = crate::io::Error::new(crate::io::ErrorKind::Other, Status(503));
+    let io = &io as &dyn Error;
+    assert_eq!(io.request_value::<u16>(), Some(503));
+    assert_eq!(io.request_value::<u32>(), None);
+
+    let io = crate::io::Error::from(crate::io::ErrorKind::Other);
+    assert_eq!((&io as &dyn Error).request_value::<u16>(), None);
+}
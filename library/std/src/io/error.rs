@@ -11,6 +11,7 @@
 #[cfg(not(target_pointer_width = "64"))]
 use repr_unpacked::Repr;
 
+use crate::any::Demand;
 use crate::convert::From;
 use crate::error;
 use crate::fmt;
@@ -952,6 +953,12 @@ fn source(&self) -> Option<&(dyn error::Error + 'static)> {
             ErrorData::Custom(c) => c.error.source(),
         }
     }
+
+    fn provide<'a>(&'a self, req: &mut Demand<'a>) {
+        if let ErrorData::Custom(c) = self.repr.data() {
+            c.error.provide(req);
+        }
+    }
 }
 
 fn _assert_error_is_sync_send() {
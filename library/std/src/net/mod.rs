@@ -29,6 +29,8 @@
 pub use self::ip::{IpAddr, Ipv4Addr, Ipv6Addr, Ipv6MulticastScope};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::parser::AddrParseError;
+#[unstable(feature = "net_socket", issue = "none")]
+pub use self::socket::{Socket, TcpKeepalive};
 #[unstable(feature = "tcplistener_into_incoming", issue = "88339")]
 pub use self::tcp::IntoIncoming;
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -39,6 +41,7 @@
 mod addr;
 mod ip;
 mod parser;
+mod socket;
 mod tcp;
 #[cfg(test)]
 mod test;
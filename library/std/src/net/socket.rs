@@ -0,0 +1,453 @@
+#![unstable(feature = "net_socket", issue = "none")]
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests;
+
+use crate::fmt;
+use crate::io;
+use crate::net::{SocketAddr, TcpListener, TcpStream};
+use crate::sys_common::net as net_imp;
+use crate::sys_common::FromInner;
+use crate::time::Duration;
+
+/// A TCP socket that has not been connected or put into listening mode yet.
+///
+/// `TcpListener::bind` and `TcpStream::connect` create, configure and connect
+/// a socket in one step, which leaves no room to set options that only take
+/// effect if applied before `bind` or `connect`, such as address reuse or
+/// buffer sizes. A `Socket` splits these steps up: create it with
+/// [`tcp_v4`] or [`tcp_v6`], set the options you need, then either
+/// [`bind`] it and turn it into a [`TcpListener`] with [`listen`], or turn it
+/// into a [`TcpStream`] with [`connect`].
+///
+/// The socket will be closed when the value is dropped.
+///
+/// [`tcp_v4`]: Socket::tcp_v4
+/// [`tcp_v6`]: Socket::tcp_v6
+/// [`bind`]: Socket::bind
+/// [`listen`]: Socket::listen
+/// [`connect`]: Socket::connect
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(net_socket)]
+/// use std::net::Socket;
+///
+/// fn main() -> std::io::Result<()> {
+///     let socket = Socket::tcp_v4()?;
+///     socket.set_reuse_address(true)?;
+///     socket.set_recv_buffer_size(1 << 20)?;
+///     socket.bind(&"127.0.0.1:8080".parse().unwrap())?;
+///     let listener = socket.listen(1024)?;
+///
+///     for stream in listener.incoming() {
+///         // ...
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Socket(net_imp::TcpSocket);
+
+/// Parameters for TCP keepalive probes, see [`Socket::set_tcp_keepalive`].
+///
+/// Parameters that are not set keep their system default.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(net_socket)]
+/// use std::net::TcpKeepalive;
+/// use std::time::Duration;
+///
+/// let keepalive = TcpKeepalive::new()
+///     .with_time(Duration::from_secs(60))
+///     .with_interval(Duration::from_secs(10))
+///     .with_retries(5);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TcpKeepalive {
+    time: Option<Duration>,
+    interval: Option<Duration>,
+    retries: Option<u32>,
+}
+
+impl TcpKeepalive {
+    /// Returns a new `TcpKeepalive` that leaves every parameter at its system default.
+    #[must_use]
+    pub const fn new() -> TcpKeepalive {
+        TcpKeepalive { time: None, interval: None, retries: None }
+    }
+
+    /// Sets how long the connection must be idle before the first keepalive probe is sent.
+    ///
+    /// The value is rounded down to whole seconds.
+    #[must_use]
+    pub const fn with_time(self, time: Duration) -> TcpKeepalive {
+        TcpKeepalive { time: Some(time), ..self }
+    }
+
+    /// Sets the time between two keepalive probes.
+    ///
+    /// The value is rounded down to whole seconds.
+    #[must_use]
+    pub const fn with_interval(self, interval: Duration) -> TcpKeepalive {
+        TcpKeepalive { interval: Some(interval), ..self }
+    }
+
+    /// Sets how many unanswered keepalive probes are sent before the connection is dropped.
+    #[must_use]
+    pub const fn with_retries(self, retries: u32) -> TcpKeepalive {
+        TcpKeepalive { retries: Some(retries), ..self }
+    }
+}
+
+impl Socket {
+    /// Creates a new TCP socket for IPv4 addresses.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket)]
+    /// use std::net::Socket;
+    ///
+    /// let socket = Socket::tcp_v4().expect("couldn't create socket");
+    /// ```
+    pub fn tcp_v4() -> io::Result<Socket> {
+        net_imp::TcpSocket::new_v4().map(Socket)
+    }
+
+    /// Creates a new TCP socket for IPv6 addresses.
+    ///
+    /// Whether the socket also accepts IPv4 traffic through IPv4-mapped IPv6
+    /// addresses depends on the platform, see [`set_only_v6`].
+    ///
+    /// [`set_only_v6`]: Socket::set_only_v6
+    pub fn tcp_v6() -> io::Result<Socket> {
+        net_imp::TcpSocket::new_v6().map(Socket)
+    }
+
+    /// Binds the socket to the given local address.
+    ///
+    /// Binding to port 0 requests that the OS assigns a port to this socket. The
+    /// port allocated can be queried via [`local_addr`].
+    ///
+    /// The address must be of the same family as the socket.
+    ///
+    /// [`local_addr`]: Socket::local_addr
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        self.0.bind(addr)
+    }
+
+    /// Starts listening for incoming connections, turning this socket into a
+    /// [`TcpListener`].
+    ///
+    /// `backlog` is the maximum number of connections that may be waiting to be
+    /// accepted. The OS may silently cap it, e.g. to `net.core.somaxconn` on Linux.
+    ///
+    /// If the socket hasn't been bound yet, most platforms bind it to an
+    /// unspecified address and a port chosen by the OS.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket)]
+    /// use std::net::Socket;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = Socket::tcp_v6()?;
+    ///     socket.set_only_v6(true)?;
+    ///     socket.bind(&"[::1]:8080".parse().unwrap())?;
+    ///     let listener = socket.listen(4096)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.0.listen(backlog).map(TcpListener::from_inner)
+    }
+
+    /// Opens a TCP connection to a remote host, turning this socket into a
+    /// [`TcpStream`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket)]
+    /// use std::net::Socket;
+    /// use std::time::Duration;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = Socket::tcp_v4()?;
+    ///     socket.set_keepalive(true)?;
+    ///     socket.set_send_buffer_size(64 * 1024)?;
+    ///     let stream = socket.connect(&"127.0.0.1:8080".parse().unwrap())?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        self.0.connect(addr).map(TcpStream::from_inner)
+    }
+
+    /// Opens a TCP connection to a remote host with a timeout, turning this
+    /// socket into a [`TcpStream`].
+    ///
+    /// It is an error to pass a zero `Duration` to this function.
+    ///
+    /// See [`TcpStream::connect_timeout`] for more details.
+    pub fn connect_timeout(self, addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.0.connect_timeout(addr, timeout).map(TcpStream::from_inner)
+    }
+
+    /// Returns the local address this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.socket_addr()
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On Unix, this allows binding to an address that still has connections in
+    /// the `TIME_WAIT` state, which [`TcpListener::bind`] always enables. On
+    /// Windows it allows binding to an address that another socket is actively
+    /// using, so it should not be set by servers there.
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        self.0.set_reuse_address(reuse)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_reuse_address`].
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        self.0.reuse_address()
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to bind to the same address and port. On
+    /// Linux, the kernel then balances incoming connections across all of
+    /// them.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This option is not available on Windows and some Unix platforms, where
+    /// this function returns an error of kind [`Unsupported`].
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.0.set_reuse_port(reuse)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_reuse_port`].
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.0.reuse_port()
+    }
+
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// This enables sending keepalive probes on an otherwise idle connection.
+    /// Use [`set_tcp_keepalive`] to also configure when they are sent.
+    ///
+    /// [`set_tcp_keepalive`]: Socket::set_tcp_keepalive
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_keepalive`].
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0.keepalive()
+    }
+
+    /// Enables keepalive probes on this socket and configures when they are sent.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This sets the `TCP_KEEPIDLE` (`TCP_KEEPALIVE` on Apple platforms and
+    /// Windows), `TCP_KEEPINTVL` and `TCP_KEEPCNT` options. On Windows they
+    /// require Windows 10 version 1709 or later. On platforms that don't allow
+    /// configuring them per socket this function returns an error of kind
+    /// [`Unsupported`], after keepalive has been enabled.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket)]
+    /// use std::net::{Socket, TcpKeepalive};
+    /// use std::time::Duration;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let socket = Socket::tcp_v4()?;
+    ///     let keepalive = TcpKeepalive::new()
+    ///         .with_time(Duration::from_secs(30))
+    ///         .with_interval(Duration::from_secs(5));
+    ///     socket.set_tcp_keepalive(&keepalive)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_tcp_keepalive(&self, params: &TcpKeepalive) -> io::Result<()> {
+        self.0.set_keepalive(true)?;
+        if *params == TcpKeepalive::new() {
+            return Ok(());
+        }
+        self.0.set_keepalive_params(params.time, params.interval, params.retries)
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is the size of the buffer the OS uses for outgoing data. The OS may
+    /// adjust the value, e.g. Linux doubles it to account for bookkeeping
+    /// overhead, so [`send_buffer_size`] may return a different value.
+    ///
+    /// [`send_buffer_size`]: Socket::send_buffer_size
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_send_buffer_size`].
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is the size of the buffer the OS uses for incoming data, which also
+    /// bounds the TCP receive window. Set it before [`listen`] or [`connect`]
+    /// for it to be taken into account during the handshake. The OS may adjust
+    /// the value, so [`recv_buffer_size`] may return a different value.
+    ///
+    /// [`listen`]: Socket::listen
+    /// [`connect`]: Socket::connect
+    /// [`recv_buffer_size`]: Socket::recv_buffer_size
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_recv_buffer_size`].
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// See [`TcpStream::set_nodelay`] for more details.
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.0.set_nodelay(nodelay)
+    }
+
+    /// Gets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_nodelay`].
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.0.nodelay()
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket.
+    ///
+    /// See [`TcpStream::set_ttl`] for more details.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.0.set_ttl(ttl)
+    }
+
+    /// Gets the value of the `IP_TTL` option for this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_ttl`].
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.0.ttl()
+    }
+
+    /// Sets the value for the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true` then the socket is restricted to sending and
+    /// receiving IPv6 packets only. In this case two IPv4 and IPv6 applications
+    /// can bind the same port at the same time.
+    ///
+    /// If this is set to `false` then the socket can be used to send and
+    /// receive packets from an IPv4-mapped IPv6 address.
+    ///
+    /// This option can only be set before the socket is bound, and only on
+    /// sockets created with [`tcp_v6`].
+    ///
+    /// [`tcp_v6`]: Socket::tcp_v6
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.0.set_only_v6(only_v6)
+    }
+
+    /// Gets the value of the `IPV6_V6ONLY` option for this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_only_v6`].
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0.only_v6()
+    }
+
+    /// Sets the value for the `IPV6_UNICAST_HOPS` option on this socket.
+    ///
+    /// This is the hop limit of outgoing unicast IPv6 packets, the IPv6
+    /// counterpart of [`set_ttl`]. Only sockets created with [`tcp_v6`] support
+    /// it.
+    ///
+    /// [`set_ttl`]: Socket::set_ttl
+    /// [`tcp_v6`]: Socket::tcp_v6
+    pub fn set_unicast_hops_v6(&self, hops: u32) -> io::Result<()> {
+        self.0.set_unicast_hops_v6(hops)
+    }
+
+    /// Gets the value of the `IPV6_UNICAST_HOPS` option for this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_unicast_hops_v6`].
+    pub fn unicast_hops_v6(&self) -> io::Result<u32> {
+        self.0.unicast_hops_v6()
+    }
+
+    /// Sets the value of the `IPV6_TCLASS` option on this socket.
+    ///
+    /// This is the traffic class of outgoing IPv6 packets, which carries their
+    /// DSCP and ECN bits like the type of service field of IPv4 packets. Only
+    /// sockets created with [`tcp_v6`] support it.
+    ///
+    /// The traffic class and the flow label make up the flow information of
+    /// IPv6 packets. The flow label can't be set: Linux picks one for each
+    /// connection itself, and choosing one requires reserving it through the
+    /// Linux-specific `IPV6_FLOWLABEL_MGR` interface, which no other platform
+    /// has and recent kernels may refuse.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This option is available on Linux, Android, FreeBSD and Apple platforms.
+    /// Elsewhere this function returns an error of kind [`Unsupported`].
+    ///
+    /// [`tcp_v6`]: Socket::tcp_v6
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    pub fn set_tclass_v6(&self, tclass: u32) -> io::Result<()> {
+        self.0.set_tclass_v6(tclass)
+    }
+
+    /// Gets the value of the `IPV6_TCLASS` option for this socket.
+    ///
+    /// For more information about this option, see [`Socket::set_tclass_v6`].
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        self.0.tclass_v6()
+    }
+
+    /// Gets the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing
+    /// the field in the process. This can be useful for checking errors between
+    /// calls.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0.take_error()
+    }
+}
+
+impl fmt::Debug for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
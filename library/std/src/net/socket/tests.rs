@@ -0,0 +1,161 @@
+use crate::io::prelude::*;
+use crate::net::test::{next_test_ip4, next_test_ip6};
+use crate::net::*;
+use crate::thread;
+use crate::time::Duration;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(t) => t,
+            Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+        }
+    };
+}
+
+fn socket_for(addr: &SocketAddr) -> Socket {
+    match addr {
+        SocketAddr::V4(..) => t!(Socket::tcp_v4()),
+        SocketAddr::V6(..) => t!(Socket::tcp_v6()),
+    }
+}
+
+#[test]
+fn listen_and_connect() {
+    for addr in [next_test_ip4(), next_test_ip6()] {
+        let socket = socket_for(&addr);
+        t!(socket.bind(&addr));
+        assert_eq!(t!(socket.local_addr()), addr);
+        let listener = t!(socket.listen(16));
+
+        let t = thread::spawn(move || {
+            let mut stream = t!(socket_for(&addr).connect(&addr));
+            t!(stream.write_all(&[42]));
+        });
+
+        let mut stream = t!(listener.accept()).0;
+        let mut buf = [0];
+        t!(stream.read_exact(&mut buf));
+        assert_eq!(buf[0], 42);
+        t.join().unwrap();
+    }
+}
+
+#[test]
+fn connect_timeout() {
+    let addr = next_test_ip4();
+    let listener = t!(TcpListener::bind(&addr));
+    let stream = t!(t!(Socket::tcp_v4()).connect_timeout(&addr, Duration::from_secs(10)));
+    assert_eq!(t!(stream.peer_addr()), addr);
+    drop(listener);
+}
+
+#[test]
+fn bind_port_zero() {
+    let socket = t!(Socket::tcp_v4());
+    t!(socket.bind(&"127.0.0.1:0".parse().unwrap()));
+    assert_ne!(t!(socket.local_addr()).port(), 0);
+}
+
+#[test]
+fn reuse_address() {
+    let socket = t!(Socket::tcp_v4());
+    t!(socket.set_reuse_address(true));
+    assert!(t!(socket.reuse_address()));
+    t!(socket.set_reuse_address(false));
+    assert!(!t!(socket.reuse_address()));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd", target_os = "macos"))]
+fn reuse_port() {
+    let addr = next_test_ip4();
+    let first = t!(Socket::tcp_v4());
+    t!(first.set_reuse_port(true));
+    assert!(t!(first.reuse_port()));
+    t!(first.bind(&addr));
+    let _first = t!(first.listen(1));
+
+    let second = t!(Socket::tcp_v4());
+    t!(second.set_reuse_port(true));
+    t!(second.bind(&addr));
+    let _second = t!(second.listen(1));
+}
+
+#[test]
+fn keepalive() {
+    let socket = t!(Socket::tcp_v4());
+    assert!(!t!(socket.keepalive()));
+    t!(socket.set_keepalive(true));
+    assert!(t!(socket.keepalive()));
+
+    let socket = t!(Socket::tcp_v4());
+    t!(socket.set_tcp_keepalive(&TcpKeepalive::new()));
+    assert!(t!(socket.keepalive()));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd", target_os = "macos"))]
+fn keepalive_params() {
+    let socket = t!(Socket::tcp_v4());
+    let keepalive = TcpKeepalive::new()
+        .with_time(Duration::from_secs(30))
+        .with_interval(Duration::from_secs(5))
+        .with_retries(3);
+    t!(socket.set_tcp_keepalive(&keepalive));
+    assert!(t!(socket.keepalive()));
+}
+
+#[test]
+fn buffer_sizes() {
+    let socket = t!(Socket::tcp_v4());
+    t!(socket.set_send_buffer_size(64 * 1024));
+    assert!(t!(socket.send_buffer_size()) >= 64 * 1024);
+    t!(socket.set_recv_buffer_size(64 * 1024));
+    assert!(t!(socket.recv_buffer_size()) >= 64 * 1024);
+}
+
+#[test]
+fn options_v6() {
+    let socket = t!(Socket::tcp_v6());
+    t!(socket.set_only_v6(true));
+    assert!(t!(socket.only_v6()));
+    t!(socket.set_unicast_hops_v6(42));
+    assert_eq!(t!(socket.unicast_hops_v6()), 42);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd", target_os = "macos"))]
+fn tclass_v6() {
+    let socket = t!(Socket::tcp_v6());
+    t!(socket.set_tclass_v6(0x28));
+    assert_eq!(t!(socket.tclass_v6()), 0x28);
+}
+
+#[test]
+fn options_carry_over() {
+    let addr = next_test_ip4();
+    let socket = t!(Socket::tcp_v4());
+    t!(socket.set_nodelay(true));
+    t!(socket.set_ttl(100));
+    assert!(t!(socket.nodelay()));
+    assert_eq!(t!(socket.ttl()), 100);
+    t!(socket.bind(&addr));
+    let listener = t!(socket.listen(1));
+    assert_eq!(t!(listener.ttl()), 100);
+
+    let socket = t!(Socket::tcp_v4());
+    t!(socket.set_nodelay(true));
+    let stream = t!(socket.connect(&addr));
+    assert!(t!(stream.nodelay()));
+    assert!(t!(stream.take_error()).is_none());
+}
+
+#[test]
+fn debug() {
+    let socket = t!(Socket::tcp_v4());
+    let addr = next_test_ip4();
+    t!(socket.bind(&addr));
+    let debug = format!("{:?}", socket);
+    assert!(debug.contains(&addr.to_string()), "{}", debug);
+}
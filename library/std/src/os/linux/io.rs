@@ -0,0 +1,154 @@
+//! Linux-specific extensions to the [`std::io`] module.
+//!
+//! [`std::io`]: crate::io
+
+#![unstable(feature = "linux_io_uring", issue = "none")]
+
+#[cfg(test)]
+mod tests;
+
+use crate::fmt;
+use crate::fs::File;
+use crate::io::{self, IoSlice};
+use crate::os::unix::io::AsFd;
+use crate::sys::io_uring::{self, Op};
+
+/// A batch of reads and writes at explicit offsets in [`File`]s, submitted together.
+///
+/// On Linux 5.6 and later the whole batch is handed to the kernel through [io_uring] with a
+/// single system call per 32 operations, and the operations may run concurrently. If io_uring is
+/// not available, for example because the kernel is too old or io_uring has been disabled, they
+/// are performed one after another with `pread(2)` and `pwrite(2)`, so the same code works on
+/// every kernel. [`io_uring_available`] tells which of the two is used.
+///
+/// Like [`FileExt::read_at`] and [`FileExt::write_at`], these operations neither use nor change
+/// the cursor of the files, and an operation at an offset larger than `i64::MAX` fails with an
+/// error of kind [`InvalidInput`]. Because they may run in any order, a batch should not contain
+/// two operations that touch the same bytes of a file if at least one of them writes to it.
+///
+/// The first batch submitted on a thread sets up an io_uring instance for that thread, which is
+/// reused by later batches and only released when the thread exits. It holds a file descriptor
+/// and a few pages of memory, which count against `RLIMIT_MEMLOCK` before Linux 5.12.
+///
+/// [`InvalidInput`]: io::ErrorKind::InvalidInput
+/// [io_uring]: https://man7.org/linux/man-pages/man7/io_uring.7.html
+/// [`FileExt::read_at`]: crate::os::unix::fs::FileExt::read_at
+/// [`FileExt::write_at`]: crate::os::unix::fs::FileExt::write_at
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_io_uring)]
+/// use std::fs::File;
+/// use std::os::linux::io::Batch;
+///
+/// fn main() -> std::io::Result<()> {
+///     let file = File::open("foo.txt")?;
+///     let (mut head, mut tail) = ([0; 16], [0; 16]);
+///
+///     let mut batch = Batch::new();
+///     batch.read_at(&file, &mut head, 0);
+///     batch.read_at(&file, &mut tail, 4096);
+///     for result in batch.submit() {
+///         println!("read {} bytes", result?);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Batch<'a> {
+    ops: Vec<Op<'a>>,
+}
+
+impl<'a> Batch<'a> {
+    /// Creates an empty batch.
+    pub fn new() -> Batch<'a> {
+        Batch { ops: Vec::new() }
+    }
+
+    /// Creates an empty batch with room for `capacity` operations.
+    pub fn with_capacity(capacity: usize) -> Batch<'a> {
+        Batch { ops: Vec::with_capacity(capacity) }
+    }
+
+    /// Adds a read from `file` at `offset` into `buf`.
+    ///
+    /// Its result is the number of bytes read, which may be less than the length of `buf`.
+    pub fn read_at(&mut self, file: &'a File, buf: &'a mut [u8], offset: u64) -> &mut Self {
+        self.ops.push(Op::Read { fd: file.as_fd(), buf, offset });
+        self
+    }
+
+    /// Adds a write of `buf` to `file` at `offset`.
+    ///
+    /// Its result is the number of bytes written, which may be less than the length of `buf`.
+    pub fn write_at(&mut self, file: &'a File, buf: &'a [u8], offset: u64) -> &mut Self {
+        self.ops.push(Op::Write { fd: file.as_fd(), buf, offset });
+        self
+    }
+
+    /// Adds a write of the contents of `bufs` to `file`, starting at `offset`.
+    ///
+    /// Its result is the number of bytes written, which may be less than the total length of
+    /// `bufs`. At most 1024 buffers are written by a single operation.
+    pub fn write_vectored_at(
+        &mut self,
+        file: &'a File,
+        bufs: &'a [IoSlice<'a>],
+        offset: u64,
+    ) -> &mut Self {
+        self.ops.push(Op::WriteVectored { fd: file.as_fd(), bufs, offset });
+        self
+    }
+
+    /// Returns the number of operations in the batch.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Returns `true` if the batch contains no operations.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Performs all operations of the batch and returns their results, in the order in which
+    /// they were added.
+    ///
+    /// Every operation is performed even if others fail. Buffers of reads are only guaranteed to
+    /// hold the data once this returns.
+    pub fn submit(mut self) -> Vec<io::Result<usize>> {
+        io_uring::submit_or_fallback(&mut self.ops)
+    }
+}
+
+impl Default for Batch<'_> {
+    fn default() -> Self {
+        Batch::new()
+    }
+}
+
+impl fmt::Debug for Batch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Batch").field("len", &self.ops.len()).finish_non_exhaustive()
+    }
+}
+
+/// Returns whether [`Batch`]es are submitted through io_uring on this thread.
+///
+/// This is `false` if the kernel doesn't support io_uring or lacks the operations used by
+/// [`Batch`], which were added in Linux 5.6, or if io_uring is blocked, e.g. by the
+/// `kernel.io_uring_disabled` sysctl or a seccomp filter. Batches still work in that case, but
+/// their operations are performed one at a time.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(linux_io_uring)]
+/// use std::os::linux::io::io_uring_available;
+///
+/// if io_uring_available() {
+///     println!("using io_uring");
+/// }
+/// ```
+pub fn io_uring_available() -> bool {
+    io_uring::is_available()
+}
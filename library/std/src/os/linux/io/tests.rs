@@ -0,0 +1,35 @@
+use super::Batch;
+use crate::fs::{self, OpenOptions};
+use crate::io::IoSlice;
+use crate::sys_common::io::test::tmpdir;
+
+#[test]
+fn batch_across_files() {
+    let tmp = tmpdir();
+    let a = OpenOptions::new().read(true).write(true).create(true).open(tmp.join("a")).unwrap();
+    let b = OpenOptions::new().write(true).create(true).open(tmp.join("b")).unwrap();
+
+    let bufs = [IoSlice::new(b"vec"), IoSlice::new(b"tored")];
+    let mut batch = Batch::with_capacity(3);
+    assert!(batch.is_empty());
+    batch.write_at(&a, b"hello", 0).write_at(&a, b"world", 5).write_vectored_at(&b, &bufs, 2);
+    assert_eq!(batch.len(), 3);
+    let results: Vec<_> = batch.submit().into_iter().map(Result::unwrap).collect();
+    assert_eq!(results, [5, 5, 8]);
+    assert_eq!(fs::read(tmp.join("a")).unwrap(), b"helloworld");
+    assert_eq!(fs::read(tmp.join("b")).unwrap(), b"\0\0vectored");
+
+    let (mut first, mut second) = ([0; 4], [0; 4]);
+    let mut batch = Batch::new();
+    batch.read_at(&a, &mut first, 1).read_at(&b, &mut second, 6);
+    let results = batch.submit();
+    assert_eq!(*results[0].as_ref().unwrap(), 4);
+    // `b` is write-only.
+    assert_eq!(results[1].as_ref().unwrap_err().raw_os_error(), Some(libc::EBADF));
+    assert_eq!(&first, b"ello");
+}
+
+#[test]
+fn empty_batch() {
+    assert!(Batch::default().submit().is_empty());
+}
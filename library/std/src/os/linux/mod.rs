@@ -4,5 +4,9 @@
 #![doc(cfg(target_os = "linux"))]
 
 pub mod fs;
+// Relies on `sys::io_uring`, which only exists when actually targeting Linux.
+#[cfg(target_os = "linux")]
+pub mod io;
 pub mod process;
 pub mod raw;
+pub mod thread;
@@ -155,6 +155,37 @@ pub trait CommandExt: Sealed {
     /// [`pidfd`]: fn@ChildExt::pidfd
     /// [`take_pidfd`]: ChildExt::take_pidfd
     fn create_pidfd(&mut self, val: bool) -> &mut process::Command;
+
+    /// Asks the kernel to send `signal` to the child process when its parent
+    /// exits. Equivalent to a `prctl(PR_SET_PDEATHSIG)` call in the child
+    /// process.
+    ///
+    /// This makes sure that helper processes don't outlive the process that
+    /// started them, even if it is killed. The signal is sent right away if
+    /// the parent exited before the child could request it.
+    ///
+    /// Note that "parent" refers to the thread that spawned the child: the
+    /// signal is also sent if that thread exits while the rest of the parent
+    /// process keeps running. The request is cleared when the child executes
+    /// a set-user-ID or set-group-ID program.
+    ///
+    /// `posix_spawn` has no way to request the signal, so spawning such a
+    /// command always uses `fork` and `exec`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_pdeathsig)]
+    /// use std::os::linux::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// // SIGKILL
+    /// Command::new("helper").parent_death_signal(9).spawn()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "linux_pdeathsig", issue = "none")]
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command;
 }
 
 impl CommandExt for process::Command {
@@ -162,4 +193,9 @@ fn create_pidfd(&mut self, val: bool) -> &mut process::Command {
         self.as_inner_mut().create_pidfd(val);
         self
     }
+
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command {
+        self.as_inner_mut().pdeathsig(signal);
+        self
+    }
 }
@@ -0,0 +1,170 @@
+//! Linux-specific extensions to primitives in the [`std::thread`] module.
+//!
+//! [`std::thread`]: crate::thread
+
+#![unstable(feature = "linux_thread_builder_ext", issue = "none")]
+
+use crate::sealed::Sealed;
+use crate::sys_common::AsInnerMut;
+use crate::thread;
+
+/// A scheduling policy for a new thread, see [`BuilderExt::scheduler`].
+///
+/// Refer to the man page of [`sched(7)`] for a description of each policy.
+///
+/// [`sched(7)`]: https://man7.org/linux/man-pages/man7/sched.7.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SchedPolicy {
+    /// The default time-sharing policy, `SCHED_OTHER`.
+    Other,
+    /// Time-sharing for CPU-intensive, non-interactive work, `SCHED_BATCH`.
+    Batch,
+    /// Runs only when nothing else wants the CPU, `SCHED_IDLE`.
+    Idle,
+    /// First-in, first-out real-time scheduling, `SCHED_FIFO`.
+    Fifo,
+    /// Round-robin real-time scheduling, `SCHED_RR`.
+    RoundRobin,
+}
+
+impl SchedPolicy {
+    fn as_raw(self) -> libc::c_int {
+        match self {
+            SchedPolicy::Other => libc::SCHED_OTHER,
+            SchedPolicy::Batch => libc::SCHED_BATCH,
+            SchedPolicy::Idle => libc::SCHED_IDLE,
+            SchedPolicy::Fifo => libc::SCHED_FIFO,
+            SchedPolicy::RoundRobin => libc::SCHED_RR,
+        }
+    }
+}
+
+/// Os-specific extensions for [`thread::Builder`].
+///
+/// All of these settings are applied when the thread is created, before any of the closure
+/// passed to [`spawn`] runs. If one of them cannot be applied, [`spawn`] returns the error and
+/// no thread is started.
+///
+/// [`spawn`]: thread::Builder::spawn
+pub trait BuilderExt: Sealed {
+    /// Restricts the new thread to run only on the given CPUs.
+    ///
+    /// CPUs are identified by their index, as in `/proc/cpuinfo`. Calling this method again
+    /// replaces the previous set. [`available_parallelism`] called from the new thread takes
+    /// this mask into account.
+    ///
+    /// If none of the given CPUs are available to the process, [`spawn`] returns an error. If an
+    /// index is beyond the number of CPUs the kernel supports, the error is of kind
+    /// [`InvalidInput`].
+    /// Setting the affinity at spawn time requires glibc; on other C libraries [`spawn`] returns
+    /// an error of kind [`Unsupported`].
+    ///
+    /// Refer to the man page of [`sched_setaffinity(2)`] for further details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_thread_builder_ext)]
+    /// use std::os::linux::thread::BuilderExt;
+    /// use std::thread;
+    ///
+    /// let handle = thread::Builder::new()
+    ///     .affinity([0, 1])
+    ///     .spawn(|| {
+    ///         // runs on CPU 0 or CPU 1 only
+    ///     })
+    ///     .unwrap();
+    /// handle.join().unwrap();
+    /// ```
+    ///
+    /// [`available_parallelism`]: thread::available_parallelism
+    /// [`spawn`]: thread::Builder::spawn
+    /// [`InvalidInput`]: crate::io::ErrorKind::InvalidInput
+    /// [`Unsupported`]: crate::io::ErrorKind::Unsupported
+    /// [`sched_setaffinity(2)`]: https://man7.org/linux/man-pages/man2/sched_setaffinity.2.html
+    fn affinity<I: IntoIterator<Item = usize>>(self, cpus: I) -> thread::Builder;
+
+    /// Sets the scheduling policy and static priority of the new thread.
+    ///
+    /// By default a new thread inherits the policy and priority of the thread spawning it.
+    ///
+    /// The priority must be 0 for [`SchedPolicy::Other`], [`SchedPolicy::Batch`] and
+    /// [`SchedPolicy::Idle`], and between 1 and 99 for the real-time policies. Using a
+    /// real-time policy usually requires the `CAP_SYS_NICE` capability or a suitable
+    /// `RLIMIT_RTPRIO`. If the policy cannot be applied, [`spawn`] returns an error.
+    ///
+    /// Refer to the man page of [`sched(7)`] for further details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_thread_builder_ext)]
+    /// use std::os::linux::thread::{BuilderExt, SchedPolicy};
+    /// use std::thread;
+    ///
+    /// let handle = thread::Builder::new()
+    ///     .scheduler(SchedPolicy::Fifo, 10)
+    ///     .spawn(|| {
+    ///         // latency-sensitive work
+    ///     })
+    ///     .unwrap();
+    /// handle.join().unwrap();
+    /// ```
+    ///
+    /// [`spawn`]: thread::Builder::spawn
+    /// [`sched(7)`]: https://man7.org/linux/man-pages/man7/sched.7.html
+    fn scheduler(self, policy: SchedPolicy, priority: i32) -> thread::Builder;
+
+    /// Registers a hook to run on the new thread before the closure passed to [`spawn`].
+    ///
+    /// The hook runs after the thread's name has been set, so it can use
+    /// [`thread::current`], for example to register the thread with a profiler or to apply
+    /// settings that are not covered by this trait. Hooks run in the order they were added.
+    ///
+    /// A panic in a hook is reported through [`JoinHandle::join`] like a panic in the closure
+    /// itself, and the closure does not run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(linux_thread_builder_ext)]
+    /// use std::os::linux::thread::BuilderExt;
+    /// use std::thread;
+    ///
+    /// let handle = thread::Builder::new()
+    ///     .name("worker".into())
+    ///     .spawn_hook(|| println!("starting {:?}", thread::current().name()))
+    ///     .spawn(|| {
+    ///         // thread code
+    ///     })
+    ///     .unwrap();
+    /// handle.join().unwrap();
+    /// ```
+    ///
+    /// [`spawn`]: thread::Builder::spawn
+    /// [`JoinHandle::join`]: thread::JoinHandle::join
+    fn spawn_hook<F>(self, hook: F) -> thread::Builder
+    where
+        F: FnOnce() + Send + 'static;
+}
+
+impl BuilderExt for thread::Builder {
+    fn affinity<I: IntoIterator<Item = usize>>(mut self, cpus: I) -> thread::Builder {
+        self.as_inner_mut().affinity(cpus);
+        self
+    }
+
+    fn scheduler(mut self, policy: SchedPolicy, priority: i32) -> thread::Builder {
+        self.as_inner_mut().scheduler(policy.as_raw(), priority);
+        self
+    }
+
+    fn spawn_hook<F>(mut self, hook: F) -> thread::Builder
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.as_inner_mut().spawn_hook(Box::new(hook));
+        self
+    }
+}
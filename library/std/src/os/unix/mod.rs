@@ -81,6 +81,8 @@ mod platform {
 pub mod net;
 pub mod process;
 pub mod raw;
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "emscripten")))]
+pub mod signal;
 pub mod thread;
 
 #[unstable(feature = "peer_credentials_unix_socket", issue = "42839", reason = "unstable")]
@@ -179,6 +179,146 @@ fn arg0<S>(&mut self, arg: S) -> &mut process::Command
     /// ```
     #[stable(feature = "process_set_process_group", since = "1.64.0")]
     fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Runs the child process in a new session. Equivalent to a `setsid`
+    /// call in the child process.
+    ///
+    /// The child becomes the leader of a new session and of a new process
+    /// group in it, and is detached from the controlling terminal of the
+    /// parent. It won't receive signals sent by that terminal, for example
+    /// when it is closed or Ctrl-C is pressed.
+    ///
+    /// A new session can't be combined with [`process_group`]: spawning such
+    /// a command fails with an error of kind [`InvalidInput`].
+    ///
+    /// With glibc 2.26 or later and on macOS, the child is still started with
+    /// `posix_spawn`. Elsewhere, spawning falls back to `fork` and `exec`, or
+    /// fails with an error of kind [`Unsupported`] on platforms without
+    /// sessions, such as VxWorks. The same applies to
+    /// [`controlling_terminal`].
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    /// [`controlling_terminal`]: CommandExt::controlling_terminal
+    ///
+    /// [`process_group`]: CommandExt::process_group
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_session_limits)]
+    /// use std::process::Command;
+    /// use std::os::unix::process::CommandExt;
+    ///
+    /// Command::new("my-daemon").setsid(true).spawn()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "process_session_limits", issue = "none")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+
+    /// Makes the terminal connected to the standard input of the child
+    /// process its controlling terminal. Equivalent to a `TIOCSCTTY` ioctl on
+    /// standard input in the child process.
+    ///
+    /// This is how terminal emulators and remote shells hand a pseudoterminal
+    /// over to the process running in it. It requires starting a new session
+    /// with [`setsid`], otherwise spawning fails with an error of kind
+    /// [`InvalidInput`]. It also fails if the terminal already is the
+    /// controlling terminal of another session.
+    ///
+    /// `posix_spawn` has no way to acquire a controlling terminal, so
+    /// spawning such a command always uses `fork` and `exec`.
+    ///
+    /// [`setsid`]: CommandExt::setsid
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    #[unstable(feature = "process_session_limits", issue = "none")]
+    fn controlling_terminal(&mut self, acquire: bool) -> &mut process::Command;
+
+    /// Sets a resource limit of the child process. Equivalent to a
+    /// `setrlimit` call in the child process.
+    ///
+    /// `soft` is the limit enforced by the kernel, `hard` the ceiling up to
+    /// which the child can raise the soft limit itself. [`u64::MAX`] means no
+    /// limit. Calling this again for the same resource replaces the previous
+    /// limits. Raising a hard limit above the current one requires privileges,
+    /// and spawning fails if they are missing.
+    ///
+    /// `posix_spawn` has no way to set resource limits in the child, so
+    /// spawning a command with limits always uses `fork` and `exec`. On
+    /// platforms without resource limits, such as VxWorks, spawning fails
+    /// with an error of kind [`Unsupported`].
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// Prevent a process from creating more than 64 files, or core dumps:
+    ///
+    /// ```no_run
+    /// #![feature(process_session_limits)]
+    /// use std::process::Command;
+    /// use std::os::unix::process::{CommandExt, Resource};
+    ///
+    /// Command::new("untrusted")
+    ///     .rlimit(Resource::NoFile, 64, 64)
+    ///     .rlimit(Resource::Core, 0, 0)
+    ///     .spawn()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "process_session_limits", issue = "none")]
+    fn rlimit(&mut self, resource: Resource, soft: u64, hard: u64) -> &mut process::Command;
+}
+
+/// A system resource whose use can be limited, see [`CommandExt::rlimit`].
+///
+/// Refer to the man page of [`getrlimit(2)`] for how each limit is enforced.
+///
+/// [`getrlimit(2)`]: https://man7.org/linux/man-pages/man2/getrlimit.2.html
+#[unstable(feature = "process_session_limits", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Resource {
+    /// The size of the virtual address space in bytes, `RLIMIT_AS`.
+    AddressSpace,
+    /// The size of core dumps in bytes, `RLIMIT_CORE`.
+    Core,
+    /// The CPU time in seconds, `RLIMIT_CPU`.
+    Cpu,
+    /// The size of the data segment in bytes, `RLIMIT_DATA`.
+    Data,
+    /// The size of files the process may create in bytes, `RLIMIT_FSIZE`.
+    FileSize,
+    /// One more than the highest file descriptor the process may open,
+    /// `RLIMIT_NOFILE`.
+    NoFile,
+    /// The size of the main thread's stack in bytes, `RLIMIT_STACK`.
+    Stack,
+}
+
+#[cfg(not(any(target_os = "vxworks", target_os = "espidf", target_os = "horizon")))]
+impl Resource {
+    fn as_raw(self) -> libc::c_int {
+        (match self {
+            Resource::AddressSpace => libc::RLIMIT_AS,
+            Resource::Core => libc::RLIMIT_CORE,
+            Resource::Cpu => libc::RLIMIT_CPU,
+            Resource::Data => libc::RLIMIT_DATA,
+            Resource::FileSize => libc::RLIMIT_FSIZE,
+            Resource::NoFile => libc::RLIMIT_NOFILE,
+            Resource::Stack => libc::RLIMIT_STACK,
+        }) as libc::c_int
+    }
+}
+
+// These targets have no resource limits. Spawning a command with limits fails
+// with an `Unsupported` error there, so the value is never used.
+#[cfg(any(target_os = "vxworks", target_os = "espidf", target_os = "horizon"))]
+impl Resource {
+    fn as_raw(self) -> libc::c_int {
+        self as libc::c_int
+    }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -224,6 +364,21 @@ fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
         self.as_inner_mut().pgroup(pgroup);
         self
     }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+
+    fn controlling_terminal(&mut self, acquire: bool) -> &mut process::Command {
+        self.as_inner_mut().setctty(acquire);
+        self
+    }
+
+    fn rlimit(&mut self, resource: Resource, soft: u64, hard: u64) -> &mut process::Command {
+        self.as_inner_mut().rlimit(resource.as_raw(), soft, hard);
+        self
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`] and
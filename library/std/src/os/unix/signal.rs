@@ -0,0 +1,187 @@
+//! Unix-specific signal handling.
+//!
+//! Signals are delivered through a pipe by a handler installed for the process, so a [`Signals`]
+//! listener can receive them on any thread without the restrictions that apply inside signal
+//! handlers. For a portable way to react to Ctrl-C, see [`process::on_interrupt`].
+//!
+//! [`process::on_interrupt`]: crate::process::on_interrupt
+
+#![unstable(feature = "unix_signal", issue = "none")]
+
+use crate::fmt;
+use crate::io;
+use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+#[cfg(not(doc))]
+use crate::sys::signals::Listener;
+
+#[cfg(doc)]
+struct Listener;
+
+/// A signal that can be received through [`Signals`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Signal {
+    /// `SIGINT`, usually sent when the user presses Ctrl-C.
+    Interrupt,
+    /// `SIGTERM`, a request to terminate the process.
+    Terminate,
+    /// `SIGHUP`, sent when the controlling terminal is closed. Daemons often take it as a request
+    /// to reload their configuration.
+    Hangup,
+    /// `SIGCHLD`, sent when a child process exits or is stopped.
+    Child,
+}
+
+impl Signal {
+    /// Returns the number of this signal on the current platform.
+    pub fn as_raw(self) -> i32 {
+        match self {
+            Signal::Interrupt => libc::SIGINT,
+            Signal::Terminate => libc::SIGTERM,
+            Signal::Hangup => libc::SIGHUP,
+            Signal::Child => libc::SIGCHLD,
+        }
+    }
+
+    fn from_raw(signum: i32) -> Signal {
+        match signum {
+            libc::SIGINT => Signal::Interrupt,
+            libc::SIGTERM => Signal::Terminate,
+            libc::SIGHUP => Signal::Hangup,
+            libc::SIGCHLD => Signal::Child,
+            _ => unreachable!("received an unexpected signal: {signum}"),
+        }
+    }
+}
+
+/// A listener for signals sent to the process.
+///
+/// While a `Signals` exists, the signals it was created for no longer have their default effect,
+/// such as terminating the process. Instead, each time one of them arrives it is queued for every
+/// listener interested in it, and can be received with [`recv`], [`try_recv`] or [`iter`]. Once
+/// the last listener for a signal is dropped, its previous disposition is restored.
+///
+/// Signals of the same kind that arrive in quick succession may be merged into one, as the
+/// operating system does not queue them.
+///
+/// The listener is backed by a pipe, whose read end is available through [`AsFd`] so that it can
+/// be registered with `poll(2)` or an event loop.
+///
+/// [`recv`]: Signals::recv
+/// [`try_recv`]: Signals::try_recv
+/// [`iter`]: Signals::iter
+///
+/// # Examples
+///
+/// Shutting down gracefully on `SIGTERM`:
+///
+/// ```no_run
+/// #![feature(unix_signal)]
+/// use std::os::unix::signal::{Signal, Signals};
+///
+/// fn main() -> std::io::Result<()> {
+///     let signals = Signals::new(&[Signal::Terminate, Signal::Hangup])?;
+///     for signal in &signals {
+///         match signal? {
+///             Signal::Hangup => println!("reloading configuration"),
+///             _ => break,
+///         }
+///     }
+///     println!("shutting down");
+///     Ok(())
+/// }
+/// ```
+pub struct Signals {
+    inner: Listener,
+}
+
+impl Signals {
+    /// Starts listening for `signals`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signal handler can't be installed, or if too many listeners
+    /// already exist.
+    pub fn new(signals: &[Signal]) -> io::Result<Signals> {
+        let signals: Vec<_> = signals.iter().map(|signal| signal.as_raw()).collect();
+        Listener::new(&signals).map(|inner| Signals { inner })
+    }
+
+    /// Blocks until one of the signals arrives and returns it.
+    pub fn recv(&self) -> io::Result<Signal> {
+        self.inner.recv().map(Signal::from_raw)
+    }
+
+    /// Returns a signal that has already arrived, or `None` if there is none.
+    pub fn try_recv(&self) -> io::Result<Option<Signal>> {
+        self.inner.try_recv().map(|signum| signum.map(Signal::from_raw))
+    }
+
+    /// Returns an iterator over the signals as they arrive.
+    ///
+    /// The iterator blocks while waiting for the next signal. It only ends after yielding an
+    /// error other than [`Interrupted`].
+    ///
+    /// [`Interrupted`]: io::ErrorKind::Interrupted
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { signals: self, done: false }
+    }
+}
+
+impl<'a> IntoIterator for &'a Signals {
+    type Item = io::Result<Signal>;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl AsFd for Signals {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.inner.as_fd()
+    }
+}
+
+impl AsRawFd for Signals {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_fd().as_raw_fd()
+    }
+}
+
+impl fmt::Debug for Signals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signals").field("fd", &self.as_raw_fd()).finish_non_exhaustive()
+    }
+}
+
+/// An iterator over the signals received by a [`Signals`] listener.
+///
+/// It is created by [`Signals::iter`], and ends after yielding an error other than
+/// [`Interrupted`], as receiving would most likely keep failing.
+///
+/// [`Interrupted`]: io::ErrorKind::Interrupted
+#[derive(Debug)]
+pub struct Iter<'a> {
+    signals: &'a Signals,
+    done: bool,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = io::Result<Signal>;
+
+    fn next(&mut self) -> Option<io::Result<Signal>> {
+        if self.done {
+            return None;
+        }
+        let result = self.signals.recv();
+        if let Err(ref e) = result {
+            self.done = e.kind() != io::ErrorKind::Interrupted;
+        }
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done { (0, Some(0)) } else { (0, None) }
+    }
+}
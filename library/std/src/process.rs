@@ -2138,6 +2138,78 @@ pub fn id() -> u32 {
     crate::sys::os::getpid()
 }
 
+/// Registers `handler` to be called when the user asks the process to stop.
+///
+/// This is the portable subset of signal handling: on Unix, `handler` is called when the process
+/// receives `SIGINT` or `SIGTERM`; on Windows, when the console receives Ctrl-C or Ctrl-Break.
+/// Handlers run on a thread dedicated to them rather than inside a signal handler, so they may
+/// lock mutexes, allocate or print. Every registered handler is called, in the order in which
+/// they were registered, each time an interrupt arrives.
+///
+/// Once a handler has been registered, these interrupts no longer terminate the process. It is up
+/// to the program to shut down, for example by setting a flag that its main loop checks.
+///
+/// A handler that panics doesn't stop interrupts from being handled: the panic is reported by the
+/// panic hook as usual, the remaining handlers are still called, and so are all handlers on later
+/// interrupts. With `panic=abort`, a panicking handler aborts the process like any other panic.
+///
+/// For finer control over signals on Unix, see [`std::os::unix::signal`].
+///
+/// [`std::os::unix::signal`]: crate::os::unix::signal
+///
+/// # Errors
+///
+/// Returns an error of kind [`io::ErrorKind::Unsupported`] on platforms without a notion of
+/// interrupts, or if installing the underlying handler fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(on_interrupt)]
+/// use std::process;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+///
+/// fn main() -> std::io::Result<()> {
+///     let running = Arc::new(AtomicBool::new(true));
+///     let flag = running.clone();
+///     process::on_interrupt(move || flag.store(false, Ordering::SeqCst))?;
+///
+///     while running.load(Ordering::SeqCst) {
+///         // Do some work.
+///     }
+///     println!("shutting down");
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "on_interrupt", issue = "none")]
+pub fn on_interrupt<F>(handler: F) -> io::Result<()>
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    use crate::sync::{Arc, Mutex, PoisonError};
+
+    type Handler = Arc<dyn Fn() + Send + Sync>;
+    static HANDLERS: Mutex<Vec<Handler>> = Mutex::new(Vec::new());
+
+    fn run_handlers() {
+        // Don't hold the lock while calling the handlers, so that they may register more.
+        let handlers = HANDLERS.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        for handler in handlers {
+            // Unwinding out of here would abort on Windows, where this is called by the system,
+            // and stop listening for interrupts on Unix.
+            let _ = crate::panic::catch_unwind(crate::panic::AssertUnwindSafe(|| handler()));
+        }
+    }
+
+    let mut handlers = HANDLERS.lock().unwrap_or_else(PoisonError::into_inner);
+    if handlers.is_empty() {
+        imp::install_interrupt_handler(run_handlers)?;
+    }
+    handlers.push(Arc::new(handler));
+    Ok(())
+}
+
 /// A trait for implementing arbitrary return types in the `main` function.
 ///
 /// The C-main function only supports returning integers.
@@ -0,0 +1,378 @@
+//! Selection over a set of channel operations.
+
+use super::context::Context;
+use super::utils;
+
+use crate::time::Instant;
+
+/// Temporary data that gets initialized during a channel operation, and is consumed by `read` or
+/// `write`.
+///
+/// Each field contains data associated with a specific channel flavor.
+#[derive(Debug, Default)]
+pub struct Token {
+    pub(crate) array: super::array::ArrayToken,
+    pub(crate) list: super::list::ListToken,
+    pub(crate) zero: super::zero::ZeroToken,
+}
+
+/// Identifier associated with an operation by a specific thread on a specific channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operation(usize);
+
+impl Operation {
+    /// Creates an operation identifier from a mutable reference.
+    ///
+    /// This function essentially just turns the address of the reference into a number. The
+    /// reference should point to a variable that is specific to the thread and the operation,
+    /// and is alive for the entire duration of a blocking operation.
+    #[inline]
+    pub fn hook<T>(r: &mut T) -> Operation {
+        let val = r as *mut T as usize;
+        // Make sure that the pointer address doesn't equal the numerical representation of
+        // `Selected::{Waiting, Aborted, Disconnected}`.
+        assert!(val > 2);
+        Operation(val)
+    }
+}
+
+/// Current state of a blocking operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selected {
+    /// Still waiting for an operation.
+    Waiting,
+
+    /// The attempt to block the current thread has been aborted.
+    Aborted,
+
+    /// An operation became ready because a channel is disconnected.
+    Disconnected,
+
+    /// An operation became ready because a message can be sent or received.
+    Operation(Operation),
+}
+
+impl From<usize> for Selected {
+    #[inline]
+    fn from(val: usize) -> Selected {
+        match val {
+            0 => Selected::Waiting,
+            1 => Selected::Aborted,
+            2 => Selected::Disconnected,
+            oper => Selected::Operation(Operation(oper)),
+        }
+    }
+}
+
+impl Into<usize> for Selected {
+    #[inline]
+    fn into(self) -> usize {
+        match self {
+            Selected::Waiting => 0,
+            Selected::Aborted => 1,
+            Selected::Disconnected => 2,
+            Selected::Operation(Operation(val)) => val,
+        }
+    }
+}
+
+/// A receiver or a sender that can participate in select.
+///
+/// This is a handle that assists select in executing an operation, registration, deciding on the
+/// appropriate deadline for blocking, etc.
+pub trait SelectHandle {
+    /// Attempts to select an operation and returns `true` on success.
+    fn try_select(&self, token: &mut Token) -> bool;
+
+    /// Registers an operation for execution and returns `true` if it is now ready.
+    fn register(&self, oper: Operation, cx: &Context) -> bool;
+
+    /// Unregisters an operation for execution.
+    fn unregister(&self, oper: Operation);
+
+    /// Attempts to select an operation the thread got woken up for and returns `true` on success.
+    fn accept(&self, token: &mut Token, cx: &Context) -> bool;
+
+    /// Returns `true` if an operation can be executed without blocking.
+    fn is_ready(&self) -> bool;
+
+    /// Registers an operation for readiness notification and returns `true` if it is now ready.
+    fn watch(&self, oper: Operation, cx: &Context) -> bool;
+
+    /// Unregisters an operation for readiness notification.
+    fn unwatch(&self, oper: Operation);
+}
+
+/// Determines when a select operation should time out.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Timeout {
+    /// No blocking.
+    Now,
+
+    /// Block forever.
+    Never,
+
+    /// Time out after the time instant.
+    At(Instant),
+}
+
+/// A handle to a channel operation that takes part in select, along with the index the
+/// operation was added under and the address of the sender or receiver it belongs to.
+pub type Handle<'a> = (&'a dyn SelectHandle, usize, *const u8);
+
+/// Runs until one of the operations is selected, potentially blocking the current thread.
+///
+/// Successful receive operations will have to be followed up by `read()` and successful send
+/// operations by `write()`.
+pub fn run_select(
+    handles: &mut [Handle<'_>],
+    timeout: Timeout,
+) -> Option<(Token, usize, *const u8)> {
+    if handles.is_empty() {
+        // Wait until the timeout and return.
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => {
+                utils::sleep_until(None);
+                unreachable!();
+            }
+            Timeout::At(when) => {
+                utils::sleep_until(Some(when));
+                return None;
+            }
+        }
+    }
+
+    // Shuffle the operations for fairness.
+    utils::shuffle(handles);
+
+    // Create a token, which serves as a temporary variable that gets initialized in this function
+    // and is later used by a call to `read()` or `write()` that completes the selected operation.
+    let mut token = Token::default();
+
+    // Try selecting one of the operations without blocking.
+    for &(handle, i, ptr) in handles.iter() {
+        if handle.try_select(&mut token) {
+            return Some((token, i, ptr));
+        }
+    }
+
+    loop {
+        // Prepare for blocking.
+        let res = Context::with(|cx| {
+            let mut sel = Selected::Waiting;
+            let mut registered_count = 0;
+            let mut index_ready = None;
+
+            if let Timeout::Now = timeout {
+                cx.try_select(Selected::Aborted).unwrap();
+            }
+
+            // Register all operations.
+            for (handle, i, _) in handles.iter_mut() {
+                registered_count += 1;
+
+                // If registration returns `true`, that means the operation has just become ready.
+                if handle.register(Operation::hook::<&dyn SelectHandle>(handle), cx) {
+                    // Try aborting select.
+                    sel = match cx.try_select(Selected::Aborted) {
+                        Ok(()) => {
+                            index_ready = Some(*i);
+                            Selected::Aborted
+                        }
+                        Err(s) => s,
+                    };
+                    break;
+                }
+
+                // If another thread has already selected one of the operations, stop registration.
+                sel = cx.selected();
+                if sel != Selected::Waiting {
+                    break;
+                }
+            }
+
+            if sel == Selected::Waiting {
+                let deadline = match timeout {
+                    Timeout::Now => unreachable!(),
+                    Timeout::Never => None,
+                    Timeout::At(when) => Some(when),
+                };
+
+                // Block the current thread.
+                sel = cx.wait_until(deadline);
+            }
+
+            // Unregister all registered operations.
+            for (handle, _, _) in handles.iter_mut().take(registered_count) {
+                handle.unregister(Operation::hook::<&dyn SelectHandle>(handle));
+            }
+
+            match sel {
+                Selected::Waiting => unreachable!(),
+                Selected::Aborted => {
+                    // If an operation became ready during registration, try selecting it.
+                    if let Some(index_ready) = index_ready {
+                        for &(handle, i, ptr) in handles.iter() {
+                            if i == index_ready && handle.try_select(&mut token) {
+                                return Some((i, ptr));
+                            }
+                        }
+                    }
+                }
+                Selected::Disconnected => {}
+                Selected::Operation(_) => {
+                    // Find the selected operation.
+                    for (handle, i, ptr) in handles.iter_mut() {
+                        // Is this the selected operation?
+                        if sel == Selected::Operation(Operation::hook::<&dyn SelectHandle>(handle))
+                        {
+                            // Try selecting this operation.
+                            if handle.accept(&mut token, cx) {
+                                return Some((*i, *ptr));
+                            }
+                        }
+                    }
+                }
+            }
+
+            None
+        });
+
+        // Return if an operation was selected.
+        if let Some((i, ptr)) = res {
+            return Some((token, i, ptr));
+        }
+
+        // Try selecting one of the operations without blocking.
+        for &(handle, i, ptr) in handles.iter() {
+            if handle.try_select(&mut token) {
+                return Some((token, i, ptr));
+            }
+        }
+
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => {}
+            Timeout::At(when) => {
+                if Instant::now() >= when {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Runs until one of the operations becomes ready, potentially blocking the current thread.
+pub fn run_ready(handles: &mut [Handle<'_>], timeout: Timeout) -> Option<usize> {
+    if handles.is_empty() {
+        // Wait until the timeout and return.
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => {
+                utils::sleep_until(None);
+                unreachable!();
+            }
+            Timeout::At(when) => {
+                utils::sleep_until(Some(when));
+                return None;
+            }
+        }
+    }
+
+    // Shuffle the operations for fairness.
+    utils::shuffle(handles);
+
+    loop {
+        let backoff = utils::Backoff::new();
+        loop {
+            // Check operations for readiness.
+            for &(handle, i, _) in handles.iter() {
+                if handle.is_ready() {
+                    return Some(i);
+                }
+            }
+
+            if backoff.is_completed() {
+                break;
+            } else {
+                backoff.spin_heavy();
+            }
+        }
+
+        // Check for timeout.
+        match timeout {
+            Timeout::Now => return None,
+            Timeout::Never => {}
+            Timeout::At(when) => {
+                if Instant::now() >= when {
+                    return None;
+                }
+            }
+        }
+
+        // Prepare for blocking.
+        let res = Context::with(|cx| {
+            let mut sel = Selected::Waiting;
+            let mut registered_count = 0;
+
+            // Begin watching all operations.
+            for (handle, _, _) in handles.iter_mut() {
+                registered_count += 1;
+                let oper = Operation::hook::<&dyn SelectHandle>(handle);
+
+                // If registration returns `true`, that means the operation has just become ready.
+                if handle.watch(oper, cx) {
+                    sel = match cx.try_select(Selected::Operation(oper)) {
+                        Ok(()) => Selected::Operation(oper),
+                        Err(s) => s,
+                    };
+                    break;
+                }
+
+                // If another thread has already chosen one of the operations, stop registration.
+                sel = cx.selected();
+                if sel != Selected::Waiting {
+                    break;
+                }
+            }
+
+            if sel == Selected::Waiting {
+                let deadline = match timeout {
+                    Timeout::Now => unreachable!(),
+                    Timeout::Never => None,
+                    Timeout::At(when) => Some(when),
+                };
+
+                // Block the current thread.
+                sel = cx.wait_until(deadline);
+            }
+
+            // Unwatch all operations.
+            for (handle, _, _) in handles.iter_mut().take(registered_count) {
+                handle.unwatch(Operation::hook::<&dyn SelectHandle>(handle));
+            }
+
+            match sel {
+                Selected::Waiting => unreachable!(),
+                Selected::Aborted => {}
+                Selected::Disconnected => {}
+                Selected::Operation(_) => {
+                    for (handle, i, _) in handles.iter_mut() {
+                        let oper = Operation::hook::<&dyn SelectHandle>(handle);
+                        if sel == Selected::Operation(oper) {
+                            return Some(*i);
+                        }
+                    }
+                }
+            }
+
+            None
+        });
+
+        // Return if an operation became ready.
+        if res.is_some() {
+            return res;
+        }
+    }
+}
@@ -0,0 +1,531 @@
+use super::{Receiver, RecvError, SendError, SyncSender};
+use crate::error;
+use crate::fmt;
+use crate::marker::PhantomData;
+use crate::mem;
+use crate::sync::mpmc::select::{self as imp, Handle, Timeout, Token};
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+/// Waits on a set of channel operations until one of them can proceed.
+///
+/// The operations are added with [`recv`], which receives a message on a
+/// [`Receiver`], and [`send`], which sends a message on a [`SyncSender`]. Each
+/// added operation gets an index, counting up from 0 in the order the
+/// operations were added.
+///
+/// [`select`] blocks until at least one of the operations is ready, picks one
+/// of the ready operations at random, and returns it as a
+/// [`SelectedOperation`]. The selected operation must then be completed with
+/// [`SelectedOperation::recv`] or [`SelectedOperation::send`], passing the
+/// same receiver or sender that it was added for.
+///
+/// An operation on a disconnected channel is ready as well: completing it
+/// returns an error, just like calling [`Receiver::recv`] or
+/// [`SyncSender::send`] on that channel would.
+///
+/// [`ready`] only waits until one of the operations is ready, and returns its
+/// index without completing it. The operation may block again when it is
+/// actually performed, if another thread got to the channel first.
+///
+/// [`recv`]: Select::recv
+/// [`send`]: Select::send
+/// [`select`]: Select::select
+/// [`ready`]: Select::ready
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpsc_select)]
+/// use std::sync::mpsc::{channel, Select};
+/// use std::thread;
+///
+/// let (_tx1, rx1) = channel::<i32>();
+/// let (tx2, rx2) = channel();
+///
+/// thread::spawn(move || {
+///     tx2.send(2).unwrap();
+/// });
+///
+/// let mut sel = Select::new();
+/// let oper1 = sel.recv(&rx1);
+/// let oper2 = sel.recv(&rx2);
+///
+/// // Only the second channel ever receives a message.
+/// let oper = sel.select();
+/// match oper.index() {
+///     i if i == oper1 => unreachable!(),
+///     i if i == oper2 => assert_eq!(oper.recv(&rx2), Ok(2)),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub struct Select<'a> {
+    /// The added operations, with their index and the address of their receiver or sender.
+    handles: Vec<Handle<'a>>,
+
+    /// The index of the next added operation.
+    next_index: usize,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty set of channel operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::Select;
+    ///
+    /// let mut sel = Select::new();
+    ///
+    /// // There are no operations to select.
+    /// assert!(sel.try_select().is_err());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn new() -> Select<'a> {
+        Select { handles: Vec::with_capacity(4), next_index: 0 }
+    }
+
+    /// Adds an operation that receives a message on `r`, and returns its index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// let index = sel.recv(&rx);
+    /// assert_eq!(index, 0);
+    /// # drop(tx);
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn recv<T>(&mut self, r: &'a Receiver<T>) -> usize {
+        let i = self.next_index;
+        let ptr = r as *const Receiver<T> as *const u8;
+        self.handles.push((&r.inner, i, ptr));
+        self.next_index += 1;
+        i
+    }
+
+    /// Adds an operation that sends a message on `s`, and returns its index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{sync_channel, Select};
+    ///
+    /// let (tx, rx) = sync_channel::<i32>(1);
+    ///
+    /// let mut sel = Select::new();
+    /// let index = sel.send(&tx);
+    /// assert_eq!(index, 0);
+    /// # drop(rx);
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn send<T>(&mut self, s: &'a SyncSender<T>) -> usize {
+        let i = self.next_index;
+        let ptr = s as *const SyncSender<T> as *const u8;
+        self.handles.push((&s.inner, i, ptr));
+        self.next_index += 1;
+        i
+    }
+
+    /// Removes the operation with the given index.
+    ///
+    /// The indices of the other operations stay the same, and the index isn't
+    /// reused for operations that are added later. This is useful to stop
+    /// selecting an operation whose channel has been disconnected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operation with this index was added, or if it was already
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (tx1, rx1) = channel::<i32>();
+    /// let (tx2, rx2) = channel();
+    /// drop(tx1);
+    /// tx2.send(2).unwrap();
+    ///
+    /// let mut sel = Select::new();
+    /// let oper1 = sel.recv(&rx1);
+    /// let oper2 = sel.recv(&rx2);
+    ///
+    /// // The first channel is disconnected, so its operation would always be ready.
+    /// sel.remove(oper1);
+    ///
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), oper2);
+    /// assert_eq!(oper.recv(&rx2), Ok(2));
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn remove(&mut self, index: usize) {
+        let pos = self
+            .handles
+            .iter()
+            .position(|&(_, i, _)| i == index)
+            .expect("no operation with this index has been added to `Select`");
+        self.handles.swap_remove(pos);
+    }
+
+    /// Attempts to select one of the operations without blocking.
+    ///
+    /// Returns an error if none of the operations is ready.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (tx, rx) = channel();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&rx);
+    /// assert!(sel.try_select().is_err());
+    ///
+    /// tx.send(1).unwrap();
+    /// let oper = sel.try_select().unwrap();
+    /// assert_eq!(oper.recv(&rx), Ok(1));
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn try_select(&mut self) -> Result<SelectedOperation<'a>, TrySelectError> {
+        match imp::run_select(&mut self.handles, Timeout::Now) {
+            Some((token, index, ptr)) => Ok(SelectedOperation::new(token, index, ptr)),
+            None => Err(TrySelectError),
+        }
+    }
+
+    /// Blocks until one of the operations can be selected, and selects it.
+    ///
+    /// If several operations are ready, one of them is picked at random.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operations have been added, since this would block
+    /// forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{sync_channel, Select};
+    /// use std::thread;
+    ///
+    /// let (tx, rx) = sync_channel(0);
+    ///
+    /// thread::spawn(move || {
+    ///     assert_eq!(rx.recv(), Ok("hello"));
+    /// });
+    ///
+    /// let mut sel = Select::new();
+    /// sel.send(&tx);
+    ///
+    /// // Blocks until the other thread is ready to receive the message.
+    /// let oper = sel.select();
+    /// oper.send(&tx, "hello").unwrap();
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select(&mut self) -> SelectedOperation<'a> {
+        assert!(!self.handles.is_empty(), "no operations have been added to `Select`");
+        let (token, index, ptr) = imp::run_select(&mut self.handles, Timeout::Never).unwrap();
+        SelectedOperation::new(token, index, ptr)
+    }
+
+    /// Blocks until one of the operations can be selected and selects it, or
+    /// returns an error if `timeout` elapses first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::time::Duration;
+    ///
+    /// let (_tx, rx) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&rx);
+    /// assert!(sel.select_timeout(Duration::from_millis(100)).is_err());
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.select_deadline(deadline),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => Ok(self.select()),
+        }
+    }
+
+    /// Blocks until one of the operations can be selected and selects it, or
+    /// returns an error if `deadline` is reached first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::thread;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let (tx, rx) = channel();
+    ///
+    /// thread::spawn(move || {
+    ///     tx.send('a').unwrap();
+    /// });
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&rx);
+    /// let oper = sel.select_deadline(Instant::now() + Duration::from_millis(400)).unwrap();
+    /// assert_eq!(oper.recv(&rx), Ok('a'));
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select_deadline(
+        &mut self,
+        deadline: Instant,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        match imp::run_select(&mut self.handles, Timeout::At(deadline)) {
+            Some((token, index, ptr)) => Ok(SelectedOperation::new(token, index, ptr)),
+            None => Err(SelectTimeoutError),
+        }
+    }
+
+    /// Attempts to find a ready operation without blocking, and returns its
+    /// index.
+    ///
+    /// Returns an error if none of the operations is ready.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{sync_channel, Select};
+    ///
+    /// let (tx, rx) = sync_channel(1);
+    ///
+    /// let mut sel = Select::new();
+    /// let oper = sel.send(&tx);
+    /// assert_eq!(sel.try_ready(), Ok(oper));
+    ///
+    /// // The buffer is full now.
+    /// tx.send(1).unwrap();
+    /// assert!(sel.try_ready().is_err());
+    /// # drop(rx);
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn try_ready(&mut self) -> Result<usize, TrySelectError> {
+        match imp::run_ready(&mut self.handles, Timeout::Now) {
+            Some(index) => Ok(index),
+            None => Err(TrySelectError),
+        }
+    }
+
+    /// Blocks until one of the operations is ready, and returns its index.
+    ///
+    /// If several operations are ready, one of them is picked at random.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operations have been added, since this would block
+    /// forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::thread;
+    ///
+    /// let (tx, rx) = channel();
+    ///
+    /// thread::spawn(move || {
+    ///     tx.send(1).unwrap();
+    /// });
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&rx);
+    /// sel.ready();
+    /// assert_eq!(rx.try_recv(), Ok(1));
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn ready(&mut self) -> usize {
+        assert!(!self.handles.is_empty(), "no operations have been added to `Select`");
+        imp::run_ready(&mut self.handles, Timeout::Never).unwrap()
+    }
+
+    /// Blocks until one of the operations is ready and returns its index, or
+    /// returns an error if `timeout` elapses first.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn ready_timeout(&mut self, timeout: Duration) -> Result<usize, SelectTimeoutError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.ready_deadline(deadline),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => Ok(self.ready()),
+        }
+    }
+
+    /// Blocks until one of the operations is ready and returns its index, or
+    /// returns an error if `deadline` is reached first.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn ready_deadline(&mut self, deadline: Instant) -> Result<usize, SelectTimeoutError> {
+        match imp::run_ready(&mut self.handles, Timeout::At(deadline)) {
+            Some(index) => Ok(index),
+            None => Err(SelectTimeoutError),
+        }
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl<'a> Default for Select<'a> {
+    fn default() -> Select<'a> {
+        Select::new()
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select").finish_non_exhaustive()
+    }
+}
+
+/// An operation selected by a [`Select`], which has to be completed.
+///
+/// The operation is completed by passing the receiver or sender it was added
+/// for to [`recv`] or [`send`]. Until then, the other side of a
+/// [`sync_channel`] with a bound of 0 may be waiting for it.
+///
+/// # Panics
+///
+/// Dropping a `SelectedOperation` without completing it panics.
+///
+/// [`recv`]: SelectedOperation::recv
+/// [`send`]: SelectedOperation::send
+/// [`sync_channel`]: super::sync_channel
+#[must_use = "the selected operation has to be completed with `recv` or `send`"]
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub struct SelectedOperation<'a> {
+    /// The state the operation was selected with.
+    token: Token,
+
+    /// The index of the selected operation.
+    index: usize,
+
+    /// The address of the receiver or sender of the selected operation.
+    ptr: *const u8,
+
+    /// Keeps the receivers and senders of the `Select` borrowed.
+    _marker: PhantomData<&'a ()>,
+}
+
+impl SelectedOperation<'_> {
+    fn new(token: Token, index: usize, ptr: *const u8) -> Self {
+        SelectedOperation { token, index, ptr, _marker: PhantomData }
+    }
+
+    /// Returns the index of the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Completes the selected operation by receiving a message on `r`.
+    ///
+    /// Returns an error if the channel is disconnected and empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` isn't the receiver of the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn recv<T>(mut self, r: &Receiver<T>) -> Result<T, RecvError> {
+        assert!(
+            r as *const Receiver<T> as *const u8 == self.ptr,
+            "passed a receiver that wasn't selected",
+        );
+        let res = unsafe { r.inner.read(&mut self.token) };
+        mem::forget(self);
+        res.map_err(|_| RecvError)
+    }
+
+    /// Completes the selected operation by sending `msg` on `s`.
+    ///
+    /// Returns the message back if the channel is disconnected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` isn't the sender of the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn send<T>(mut self, s: &SyncSender<T>, msg: T) -> Result<(), SendError<T>> {
+        assert!(
+            s as *const SyncSender<T> as *const u8 == self.ptr,
+            "passed a sender that wasn't selected",
+        );
+        let res = unsafe { s.inner.write(&mut self.token, msg) };
+        mem::forget(self);
+        res.map_err(SendError)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Debug for SelectedOperation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectedOperation").field("index", &self.index).finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl Drop for SelectedOperation<'_> {
+    fn drop(&mut self) {
+        // Don't turn a panic while completing the operation into an abort.
+        if !thread::panicking() {
+            panic!("dropped `SelectedOperation` without completing the operation");
+        }
+    }
+}
+
+/// An error returned from [`Select::try_select`] or [`Select::try_ready`]
+/// when none of the operations is ready.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub struct TrySelectError;
+
+/// An error returned from the timeout and deadline variants of
+/// [`Select::select`] and [`Select::ready`] when none of the operations
+/// became ready in time.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub struct SelectTimeoutError;
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Display for TrySelectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "all operations in select would block".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl error::Error for TrySelectError {}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Display for SelectTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "timed out waiting on select".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl error::Error for SelectTimeoutError {}
@@ -0,0 +1,288 @@
+use super::*;
+use crate::env;
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+pub fn stress_factor() -> usize {
+    match env::var("RUST_TEST_STRESS") {
+        Ok(val) => val.parse().unwrap(),
+        Err(..) => 1,
+    }
+}
+
+#[test]
+fn smoke() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    tx1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let _oper2 = sel.recv(&rx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+}
+
+#[test]
+fn indices() {
+    let (_tx1, rx1) = channel::<i32>();
+    let (tx2, _rx2) = sync_channel::<i32>(1);
+    let (_tx3, rx3) = sync_channel::<i32>(0);
+
+    let mut sel = Select::new();
+    assert_eq!(sel.recv(&rx1), 0);
+    assert_eq!(sel.send(&tx2), 1);
+    assert_eq!(sel.recv(&rx3), 2);
+    sel.remove(1);
+    assert_eq!(sel.recv(&rx1), 3);
+}
+
+#[test]
+fn try_select_empty() {
+    let (_tx, rx) = channel::<i32>();
+
+    let mut sel = Select::new();
+    assert_eq!(sel.try_select().unwrap_err(), TrySelectError);
+    sel.recv(&rx);
+    assert_eq!(sel.try_select().unwrap_err(), TrySelectError);
+}
+
+#[test]
+fn disconnected() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    drop(tx1);
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    sel.recv(&rx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&rx1), Err(RecvError));
+}
+
+#[test]
+fn disconnected_send() {
+    let (tx, rx) = sync_channel::<i32>(0);
+    drop(rx);
+
+    let mut sel = Select::new();
+    sel.send(&tx);
+    let oper = sel.select();
+    assert_eq!(oper.send(&tx, 1), Err(SendError(1)));
+}
+
+#[test]
+fn remove() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    drop(tx1);
+    tx2.send(2).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    sel.remove(oper1);
+    for _ in 0..10 {
+        assert_eq!(sel.try_ready(), Ok(oper2));
+    }
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+}
+
+#[test]
+#[should_panic]
+fn remove_twice() {
+    let (_tx, rx) = channel::<i32>();
+
+    let mut sel = Select::new();
+    let oper = sel.recv(&rx);
+    sel.remove(oper);
+    sel.remove(oper);
+}
+
+#[test]
+fn select_timeout() {
+    let (_tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(1);
+    tx2.send(1).unwrap();
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    sel.send(&tx2);
+    let start = Instant::now();
+    assert_eq!(sel.select_timeout(Duration::from_millis(100)).unwrap_err(), SelectTimeoutError);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    drop(rx2);
+}
+
+#[test]
+fn select_deadline_wakes_up() {
+    let (tx, rx) = channel();
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx.send(1).unwrap();
+    });
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let oper = sel.select_deadline(Instant::now() + Duration::from_secs(10)).unwrap();
+    assert_eq!(oper.recv(&rx), Ok(1));
+    t.join().unwrap();
+}
+
+#[test]
+fn select_timeout_empty() {
+    let mut sel = Select::new();
+    assert!(sel.select_timeout(Duration::from_millis(10)).is_err());
+    assert!(sel.ready_timeout(Duration::from_millis(10)).is_err());
+}
+
+#[test]
+#[should_panic]
+fn select_empty() {
+    let mut sel = Select::new();
+    let _ = sel.select();
+}
+
+#[test]
+fn send_rendezvous() {
+    let (tx, rx) = sync_channel::<i32>(0);
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(rx.recv(), Ok(1));
+    });
+
+    let mut sel = Select::new();
+    sel.send(&tx);
+    let oper = sel.select();
+    oper.send(&tx, 1).unwrap();
+    t.join().unwrap();
+}
+
+#[test]
+fn recv_rendezvous() {
+    let (tx, rx) = sync_channel::<i32>(0);
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx.send(1).unwrap();
+    });
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let oper = sel.select();
+    assert_eq!(oper.recv(&rx), Ok(1));
+    t.join().unwrap();
+}
+
+#[test]
+fn send_and_recv() {
+    let (tx1, rx1) = sync_channel::<i32>(0);
+    let (tx2, rx2) = sync_channel::<i32>(0);
+    let t = thread::spawn(move || {
+        assert_eq!(rx1.recv(), Ok(1));
+        tx2.send(2).unwrap();
+    });
+
+    let mut sel = Select::new();
+    let send = sel.send(&tx1);
+    let recv = sel.recv(&rx2);
+
+    // Nothing can be received before the message is sent.
+    let oper = sel.select();
+    assert_eq!(oper.index(), send);
+    oper.send(&tx1, 1).unwrap();
+
+    sel.remove(send);
+    let oper = sel.select();
+    assert_eq!(oper.index(), recv);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+    t.join().unwrap();
+}
+
+#[test]
+fn ready() {
+    let (tx1, rx1) = sync_channel::<i32>(1);
+    let (tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    let send = sel.send(&tx1);
+    let recv = sel.recv(&rx2);
+    assert_eq!(sel.ready(), send);
+
+    tx1.send(1).unwrap();
+    assert_eq!(sel.try_ready(), Err(TrySelectError));
+    assert_eq!(sel.ready_timeout(Duration::from_millis(10)), Err(SelectTimeoutError));
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx2.send(2).unwrap();
+    });
+    assert_eq!(sel.ready(), recv);
+    assert_eq!(rx2.recv(), Ok(2));
+    assert_eq!(rx1.recv(), Ok(1));
+    t.join().unwrap();
+}
+
+#[test]
+#[should_panic]
+fn recv_wrong_receiver() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    tx1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    sel.recv(&rx2);
+    let oper = sel.select();
+    let _ = oper.recv(&rx2);
+}
+
+#[test]
+fn stress() {
+    const AMT: usize = 10000;
+    let amt = AMT * stress_factor();
+
+    let (tx1, rx1) = channel::<usize>();
+    let (tx2, rx2) = sync_channel::<usize>(0);
+    let (tx3, rx3) = sync_channel::<usize>(1);
+
+    let t = thread::spawn(move || {
+        for i in 0..amt {
+            match i % 3 {
+                0 => tx1.send(i).unwrap(),
+                1 => tx2.send(i).unwrap(),
+                _ => tx3.send(i).unwrap(),
+            }
+        }
+    });
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    let oper3 = sel.recv(&rx3);
+
+    let mut sum = 0;
+    let mut open = 3;
+    while open > 0 {
+        let oper = sel.select();
+        let index = oper.index();
+        let res = match index {
+            i if i == oper1 => oper.recv(&rx1),
+            i if i == oper2 => oper.recv(&rx2),
+            i if i == oper3 => oper.recv(&rx3),
+            _ => unreachable!(),
+        };
+        match res {
+            Ok(i) => sum += i,
+            Err(RecvError) => {
+                sel.remove(index);
+                open -= 1;
+            }
+        }
+    }
+    assert_eq!(sum, (0..amt).sum());
+    t.join().unwrap();
+}
@@ -704,3 +704,19 @@ fn issue_32114() {
     let _ = tx.send(123);
     assert_eq!(tx.send(123), Err(SendError(123)));
 }
+
+#[test]
+fn send_on_shared_sender() {
+    let (tx, rx) = channel();
+    thread::scope(|s| {
+        for i in 0..10 {
+            let tx = &tx;
+            s.spawn(move || tx.send(i).unwrap());
+        }
+    });
+    drop(tx);
+
+    let mut received: Vec<i32> = rx.iter().collect();
+    received.sort();
+    assert_eq!(received, (0..10).collect::<Vec<_>>());
+}
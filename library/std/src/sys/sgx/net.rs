@@ -317,6 +317,137 @@ fn from_inner(inner: Socket) -> TcpListener {
     }
 }
 
+pub struct TcpSocket(!);
+
+impl TcpSocket {
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        unsupported()
+    }
+
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        unsupported()
+    }
+
+    pub fn bind(&self, _: &SocketAddr) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn listen(self, _: u32) -> io::Result<TcpListener> {
+        self.0
+    }
+
+    pub fn connect(self, _: &SocketAddr) -> io::Result<TcpStream> {
+        self.0
+    }
+
+    pub fn connect_timeout(self, _: &SocketAddr, _: Duration) -> io::Result<TcpStream> {
+        self.0
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        self.0
+    }
+
+    pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_keepalive_params(
+        &self,
+        _: Option<Duration>,
+        _: Option<Duration>,
+        _: Option<u32>,
+    ) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0
+    }
+
+    pub fn set_nodelay(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_ttl(&self, _: u32) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.0
+    }
+
+    pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_unicast_hops_v6(&self, _: u32) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn unicast_hops_v6(&self) -> io::Result<u32> {
+        self.0
+    }
+
+    pub fn set_tclass_v6(&self, _: u32) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        self.0
+    }
+
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.0
+    }
+}
+
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+    }
+}
+
 pub struct UdpSocket(!);
 
 impl UdpSocket {
@@ -431,6 +431,42 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "SO_REUSEPORT is not supported"))
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "SO_REUSEPORT is not supported"))
+    }
+
+    pub fn set_keepalive_params(
+        &self,
+        _time: Option<Duration>,
+        _interval: Option<Duration>,
+        _retries: Option<u32>,
+    ) -> io::Result<()> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "configuring TCP keepalive probes is not supported",
+        ))
+    }
+
+    pub fn set_unicast_hops_v6(&self, _hops: u32) -> io::Result<()> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "IPV6_UNICAST_HOPS is not supported"))
+    }
+
+    pub fn unicast_hops_v6(&self) -> io::Result<u32> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "IPV6_UNICAST_HOPS is not supported"))
+    }
+
+    pub fn set_tclass_v6(&self, _tclass: u32) -> io::Result<()> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "IPV6_TCLASS is not supported"))
+    }
+
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "IPV6_TCLASS is not supported"))
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         let mut nonblocking = nonblocking as c_int;
         cvt(unsafe {
@@ -0,0 +1,595 @@
+//! A minimal io_uring driver for batching reads and writes on files.
+//!
+//! io_uring lets a thread queue I/O operations in a ring buffer shared with the kernel and start
+//! all of them with a single `io_uring_enter(2)` call instead of one syscall per operation. Only
+//! what `kernel_copy` and `std::os::linux::io` need is implemented here: reads, writes and
+//! vectored writes at explicit file offsets.
+//!
+//! Kernel support is probed at runtime. io_uring may be missing entirely, lack the `READ` and
+//! `WRITE` opcodes (kernels before 5.6), be disabled through the `kernel.io_uring_disabled` sysctl
+//! or be blocked by seccomp filters, so callers of [`submit`] must be prepared to fall back to
+//! plain syscalls. [`submit_or_fallback`] does that for them.
+//!
+//! Each thread lazily sets up its own ring and keeps it until the thread exits, so that
+//! repeated batches don't pay for creating and mapping a new ring every time. A ring holds a file
+//! descriptor and a few pages of memory that count against `RLIMIT_MEMLOCK` before Linux 5.12.
+//! Occasional users such as file copies use a [`RingScope`] to release the ring again once they
+//! are done.
+
+#[cfg(test)]
+mod tests;
+
+use crate::cell::RefCell;
+use crate::cmp;
+use crate::fs::File;
+use crate::io::{self, IoSlice};
+use crate::mem::{self, ManuallyDrop};
+use crate::os::unix::fs::FileExt;
+use crate::os::unix::io::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use crate::ptr;
+use crate::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use crate::sys::cvt;
+use libc::{EACCES, EAGAIN, EBUSY, ECANCELED, EINTR, ENOSYS, EPERM};
+
+const IORING_OFF_SQ_RING: libc::off_t = 0;
+const IORING_OFF_SQES: libc::off_t = 0x10000000;
+
+const IORING_FEAT_SINGLE_MMAP: u32 = 1 << 0;
+const IORING_FEAT_NODROP: u32 = 1 << 1;
+const IORING_FEAT_RW_CUR_POS: u32 = 1 << 3;
+
+const IORING_ENTER_GETEVENTS: libc::c_uint = 1 << 0;
+
+const IORING_OP_WRITEV: u8 = 2;
+const IORING_OP_ASYNC_CANCEL: u8 = 14;
+const IORING_OP_READ: u8 = 22;
+const IORING_OP_WRITE: u8 = 23;
+
+/// Number of submission queue entries of each ring, i.e. how many operations are started with
+/// a single `io_uring_enter` call.
+const RING_ENTRIES: u32 = 32;
+
+/// Set in the `user_data` of cancellation requests, which otherwise holds the index of the
+/// operation an entry belongs to.
+const CANCEL: u64 = 1 << 63;
+
+/// Linux rejects vectored I/O operations with more buffers than this (`UIO_MAXIOV`).
+const MAX_IOV: usize = 1024;
+
+// Not all fields are used, but they are part of the kernel ABI.
+#[allow(dead_code)]
+#[repr(C)]
+struct SqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    resv2: u64,
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct CqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    resv2: u64,
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct Params {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+}
+
+/// A submission queue entry.
+#[allow(dead_code)]
+#[repr(C)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    rw_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    pad: [u64; 2],
+}
+
+/// A completion queue entry.
+#[allow(dead_code)]
+#[repr(C)]
+struct Cqe {
+    user_data: u64,
+    res: i32,
+    flags: u32,
+}
+
+/// A single I/O operation on a file descriptor at an explicit offset.
+///
+/// Like `pread(2)` and `pwrite(2)` these don't use or change the file position.
+pub enum Op<'a> {
+    Read { fd: BorrowedFd<'a>, buf: &'a mut [u8], offset: u64 },
+    Write { fd: BorrowedFd<'a>, buf: &'a [u8], offset: u64 },
+    WriteVectored { fd: BorrowedFd<'a>, bufs: &'a [IoSlice<'a>], offset: u64 },
+}
+
+impl Op<'_> {
+    fn offset(&self) -> u64 {
+        match *self {
+            Op::Read { offset, .. }
+            | Op::Write { offset, .. }
+            | Op::WriteVectored { offset, .. } => offset,
+        }
+    }
+
+    /// io_uring takes an offset of -1 to mean the current file position, while `pread(2)` and
+    /// `pwrite(2)` reject any offset that doesn't fit in an `off_t`. Such operations are never
+    /// submitted and fail the same way as the syscalls would instead.
+    fn has_valid_offset(&self) -> bool {
+        self.offset() <= i64::MAX as u64
+    }
+
+    fn to_sqe(&mut self, user_data: u64) -> Sqe {
+        let (opcode, fd, addr, len, offset) = match self {
+            Op::Read { fd, buf, offset } => (
+                IORING_OP_READ,
+                fd.as_raw_fd(),
+                buf.as_mut_ptr() as usize,
+                cmp::min(buf.len(), u32::MAX as usize),
+                *offset,
+            ),
+            Op::Write { fd, buf, offset } => (
+                IORING_OP_WRITE,
+                fd.as_raw_fd(),
+                buf.as_ptr() as usize,
+                cmp::min(buf.len(), u32::MAX as usize),
+                *offset,
+            ),
+            Op::WriteVectored { fd, bufs, offset } => (
+                IORING_OP_WRITEV,
+                fd.as_raw_fd(),
+                bufs.as_ptr() as usize,
+                cmp::min(bufs.len(), MAX_IOV),
+                *offset,
+            ),
+        };
+        // SAFETY: all-zero is a valid `Sqe` and means "no flags" for every field we don't set.
+        let zeroed: Sqe = unsafe { mem::zeroed() };
+        Sqe { opcode, fd, off: offset, addr: addr as u64, len: len as u32, user_data, ..zeroed }
+    }
+
+    /// Performs the operation with a blocking syscall.
+    fn run_blocking(&mut self) -> io::Result<usize> {
+        match self {
+            Op::Read { fd, buf, offset } => borrow_file(*fd).read_at(buf, *offset),
+            Op::Write { fd, buf, offset } => borrow_file(*fd).write_at(buf, *offset),
+            Op::WriteVectored { fd, bufs, offset } => {
+                // Like `pwritev(2)`, report a short write rather than an error once some of the
+                // data has been written.
+                let file = borrow_file(*fd);
+                let mut written = 0;
+                for buf in bufs.iter() {
+                    match file.write_at(buf, *offset + written as u64) {
+                        Ok(n) => {
+                            written += n;
+                            if n < buf.len() {
+                                break;
+                            }
+                        }
+                        Err(e) if written == 0 => return Err(e),
+                        Err(_) => break,
+                    }
+                }
+                Ok(written)
+            }
+        }
+    }
+}
+
+fn borrow_file(fd: BorrowedFd<'_>) -> ManuallyDrop<File> {
+    // SAFETY: the `File` is never dropped, so it doesn't close the borrowed descriptor.
+    ManuallyDrop::new(unsafe { File::from_raw_fd(fd.as_raw_fd()) })
+}
+
+/// A memory mapping of part of a ring.
+struct Mmap {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl Mmap {
+    fn new(fd: &OwnedFd, len: usize, offset: libc::off_t) -> io::Result<Mmap> {
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd.as_raw_fd(),
+                offset,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Mmap { ptr: ptr.cast(), len })
+        }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr.cast(), self.len);
+        }
+    }
+}
+
+struct Ring {
+    // The mappings must be released before the ring's file descriptor is closed.
+    sqes: Mmap,
+    _rings: Mmap,
+    fd: OwnedFd,
+    sq_tail: *const AtomicU32,
+    sq_mask: u32,
+    sq_array: *mut u32,
+    cq_head: *const AtomicU32,
+    cq_tail: *const AtomicU32,
+    cq_mask: u32,
+    cqes: *const Cqe,
+}
+
+impl Ring {
+    fn new(entries: u32) -> io::Result<Ring> {
+        // SAFETY: all-zero parameters request a ring with default settings.
+        let mut params: Params = unsafe { mem::zeroed() };
+        let fd = cvt(unsafe {
+            libc::syscall(libc::SYS_io_uring_setup, entries as libc::c_uint, &mut params)
+        })?;
+        // SAFETY: `io_uring_setup` returned a new file descriptor that nothing else owns.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd as RawFd) };
+
+        // `IORING_FEAT_RW_CUR_POS` was added together with the `READ` and `WRITE` opcodes.
+        let required = IORING_FEAT_SINGLE_MMAP | IORING_FEAT_NODROP | IORING_FEAT_RW_CUR_POS;
+        if params.features & required != required {
+            return Err(io::const_io_error!(
+                io::ErrorKind::Unsupported,
+                "io_uring lacks required features",
+            ));
+        }
+
+        // With `IORING_FEAT_SINGLE_MMAP` both queues live in one mapping.
+        let sq_len = params.sq_off.array as usize + params.sq_entries as usize * 4;
+        let cq_len =
+            params.cq_off.cqes as usize + params.cq_entries as usize * mem::size_of::<Cqe>();
+        let rings = Mmap::new(&fd, cmp::max(sq_len, cq_len), IORING_OFF_SQ_RING)?;
+        let sqes =
+            Mmap::new(&fd, params.sq_entries as usize * mem::size_of::<Sqe>(), IORING_OFF_SQES)?;
+
+        // SAFETY: the kernel reported these offsets into the mapping we just created.
+        unsafe {
+            let at = |offset: u32| rings.ptr.add(offset as usize);
+            Ok(Ring {
+                sq_tail: at(params.sq_off.tail).cast(),
+                sq_mask: *at(params.sq_off.ring_mask).cast::<u32>(),
+                sq_array: at(params.sq_off.array).cast(),
+                cq_head: at(params.cq_off.head).cast(),
+                cq_tail: at(params.cq_off.tail).cast(),
+                cq_mask: *at(params.cq_off.ring_mask).cast::<u32>(),
+                cqes: at(params.cq_off.cqes).cast(),
+                sqes,
+                _rings: rings,
+                fd,
+            })
+        }
+    }
+
+    /// Queues an entry for submission.
+    ///
+    /// # Safety
+    ///
+    /// The submission queue must have room for the entry, and the memory it refers to must stay
+    /// valid until its completion has been reaped or the entry has been discarded.
+    unsafe fn push(&mut self, sqe: Sqe) {
+        // Only this thread writes the tail, the kernel only reads it.
+        let tail = (*self.sq_tail).load(Ordering::Relaxed);
+        let index = tail & self.sq_mask;
+        self.sqes.ptr.cast::<Sqe>().add(index as usize).write(sqe);
+        self.sq_array.add(index as usize).write(index);
+        (*self.sq_tail).store(tail.wrapping_add(1), Ordering::Release);
+    }
+
+    /// Removes the last `count` queued entries that haven't been submitted yet.
+    fn discard(&mut self, count: u32) {
+        unsafe {
+            let tail = (*self.sq_tail).load(Ordering::Relaxed);
+            (*self.sq_tail).store(tail.wrapping_sub(count), Ordering::Release);
+        }
+    }
+
+    /// Submits up to `to_submit` queued entries and waits for at least `min_complete`
+    /// completions. Returns how many entries were submitted.
+    fn enter(&self, to_submit: u32, min_complete: u32) -> io::Result<u32> {
+        let flags = if min_complete > 0 { IORING_ENTER_GETEVENTS } else { 0 };
+        let submitted = cvt(unsafe {
+            libc::syscall(
+                libc::SYS_io_uring_enter,
+                self.fd.as_raw_fd(),
+                to_submit as libc::c_uint,
+                min_complete as libc::c_uint,
+                flags,
+                ptr::null::<libc::sigset_t>(),
+                0 as libc::size_t,
+            )
+        })?;
+        Ok(submitted as u32)
+    }
+
+    /// Blocks until the completion queue has entries, without calling `io_uring_enter`.
+    fn poll(&self) {
+        let mut pollfd = libc::pollfd { fd: self.fd.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+        // Errors are ignored and the caller polls again, as giving up would release buffers the
+        // kernel may still access.
+        unsafe { libc::poll(&mut pollfd, 1, -1) };
+    }
+
+    fn pop(&mut self) -> Option<Cqe> {
+        unsafe {
+            // Only this thread writes the head, the kernel only reads it.
+            let head = (*self.cq_head).load(Ordering::Relaxed);
+            if head == (*self.cq_tail).load(Ordering::Acquire) {
+                return None;
+            }
+            let cqe = self.cqes.add((head & self.cq_mask) as usize).read();
+            (*self.cq_head).store(head.wrapping_add(1), Ordering::Release);
+            Some(cqe)
+        }
+    }
+
+    /// Runs at most `RING_ENTRIES` operations and appends their results to `results`.
+    ///
+    /// If `io_uring_enter` fails with an error that retrying won't fix once some operations have
+    /// been submitted, the submitted operations are cancelled and waited for, and the ones that
+    /// didn't run are performed with blocking syscalls instead.
+    ///
+    /// If this returns an error, none of the operations have been started.
+    fn run(&mut self, ops: &mut [Op<'_>], results: &mut Vec<io::Result<usize>>) -> io::Result<()> {
+        debug_assert!(ops.len() <= RING_ENTRIES as usize);
+        let base = results.len();
+        results.extend(ops.iter().map(|_| Ok(0)));
+        let mut queued = 0;
+        for (i, op) in ops.iter_mut().enumerate() {
+            if !op.has_valid_offset() {
+                results[base + i] = Err(io::Error::from_raw_os_error(libc::EINVAL));
+                continue;
+            }
+            // SAFETY: at most `RING_ENTRIES` entries are queued at once, and the buffers of `ops`
+            // stay borrowed until every submitted entry has completed below.
+            unsafe { self.push(op.to_sqe(i as u64)) };
+            queued += 1;
+        }
+
+        let mut unsubmitted = queued;
+        let mut in_flight = 0;
+        // Bit masks of the operations that have completed, and of those that still need to be
+        // performed with blocking syscalls.
+        let mut completed = 0u64;
+        let mut blocking = 0u64;
+        let mut cancelling = false;
+        while unsubmitted > 0 || in_flight > 0 {
+            match self.enter(unsubmitted, 1) {
+                Ok(submitted) => {
+                    unsubmitted -= submitted;
+                    in_flight += submitted;
+                }
+                Err(e) if e.raw_os_error() == Some(EINTR) => {}
+                // The kernel is short on resources until some of our operations complete.
+                Err(e) if matches!(e.raw_os_error(), Some(EAGAIN | EBUSY)) && in_flight > 0 => {}
+                Err(_) if cancelling => {
+                    // Cancellation requests that can't be submitted aren't needed to wait for
+                    // the operations, they only make it faster.
+                    self.discard(unsubmitted);
+                    unsubmitted = 0;
+                    if in_flight > 0 {
+                        self.poll();
+                    }
+                }
+                Err(e) => {
+                    self.discard(unsubmitted);
+                    if unsubmitted == queued {
+                        results.truncate(base);
+                        return Err(e);
+                    }
+                    // Entries are consumed in order, so the unsubmitted operations are the last
+                    // queued ones.
+                    let mut remaining = unsubmitted;
+                    for (i, op) in ops.iter().enumerate().rev() {
+                        if remaining == 0 {
+                            break;
+                        }
+                        if op.has_valid_offset() {
+                            blocking |= 1 << i;
+                            remaining -= 1;
+                        }
+                    }
+                    unsubmitted = 0;
+                    // The kernel may still access the buffers of submitted operations, so they
+                    // must not be released before those have completed. Ask the kernel to cancel
+                    // them rather than wait for all of them to run.
+                    for (i, op) in ops.iter().enumerate() {
+                        if op.has_valid_offset() && (completed | blocking) & (1 << i) == 0 {
+                            let zeroed: Sqe = unsafe { mem::zeroed() };
+                            let sqe = Sqe {
+                                opcode: IORING_OP_ASYNC_CANCEL,
+                                fd: -1,
+                                addr: i as u64,
+                                user_data: CANCEL | i as u64,
+                                ..zeroed
+                            };
+                            // SAFETY: every queued entry has been submitted or discarded, so
+                            // there is room for one entry per operation, and a cancellation
+                            // request refers to no memory.
+                            unsafe { self.push(sqe) };
+                            unsubmitted += 1;
+                        }
+                    }
+                    cancelling = true;
+                }
+            }
+            while let Some(cqe) = self.pop() {
+                in_flight -= 1;
+                if cqe.user_data & CANCEL != 0 {
+                    continue;
+                }
+                let i = cqe.user_data as usize;
+                completed |= 1 << i;
+                if cancelling && matches!(-cqe.res, ECANCELED | EINTR) {
+                    // The operation was cancelled before it could do anything.
+                    blocking |= 1 << i;
+                    continue;
+                }
+                results[base + i] = if cqe.res < 0 {
+                    Err(io::Error::from_raw_os_error(-cqe.res))
+                } else {
+                    Ok(cqe.res as usize)
+                };
+            }
+        }
+        for (i, op) in ops.iter_mut().enumerate() {
+            if blocking & (1 << i) != 0 {
+                results[base + i] = op.run_blocking();
+            }
+        }
+        Ok(())
+    }
+}
+
+thread_local! { static RING: RefCell<Option<Ring>> = const { RefCell::new(None) } }
+
+/// Calls `f` with this thread's ring, setting it up first if needed.
+///
+/// Returns `None` if io_uring is not available.
+fn with_ring<R>(f: impl FnOnce(&mut Ring) -> R) -> Option<R> {
+    const NOT_PROBED: u8 = 0;
+    const UNAVAILABLE: u8 = 1;
+    const AVAILABLE: u8 = 2;
+
+    // Whether io_uring is usable at all. A single failed setup is not enough to mark it
+    // unavailable as some errors, like hitting `RLIMIT_MEMLOCK` or the file descriptor limit,
+    // may be temporary.
+    static STATE: AtomicU8 = AtomicU8::new(NOT_PROBED);
+
+    if STATE.load(Ordering::Relaxed) == UNAVAILABLE {
+        return None;
+    }
+    RING.try_with(|ring| {
+        let mut ring = ring.try_borrow_mut().ok()?;
+        if ring.is_none() {
+            match Ring::new(RING_ENTRIES) {
+                Ok(new) => {
+                    STATE.store(AVAILABLE, Ordering::Relaxed);
+                    *ring = Some(new);
+                }
+                Err(e) => {
+                    // - io_uring doesn't exist (ENOSYS)
+                    // - io_uring is disabled by sysctl or blocked by seccomp (EPERM, EACCES)
+                    // - the kernel is too old for the operations we need (Unsupported)
+                    if matches!(e.raw_os_error(), Some(ENOSYS | EPERM | EACCES))
+                        || e.kind() == io::ErrorKind::Unsupported
+                    {
+                        STATE.store(UNAVAILABLE, Ordering::Relaxed);
+                    }
+                    return None;
+                }
+            }
+        }
+        ring.as_mut().map(f)
+    })
+    .ok()
+    .flatten()
+}
+
+/// Releases the current thread's ring when dropped, unless the thread already had one when the
+/// scope was created.
+pub struct RingScope {
+    had_ring: bool,
+}
+
+impl RingScope {
+    pub fn new() -> RingScope {
+        let had_ring = RING
+            .try_with(|ring| ring.try_borrow().map_or(true, |ring| ring.is_some()))
+            .unwrap_or(true);
+        RingScope { had_ring }
+    }
+}
+
+impl Drop for RingScope {
+    fn drop(&mut self) {
+        if !self.had_ring {
+            let _ = RING.try_with(|ring| {
+                if let Ok(mut ring) = ring.try_borrow_mut() {
+                    *ring = None;
+                }
+            });
+        }
+    }
+}
+
+/// Returns whether operations can currently be submitted through io_uring.
+pub fn is_available() -> bool {
+    with_ring(|_| ()).is_some()
+}
+
+/// Runs `ops` through io_uring and returns the result of each operation, in order.
+///
+/// Operations may run concurrently and complete in any order, so the same bytes of a file should
+/// not be written by more than one of them, or read by one and written by another.
+///
+/// Returns `None` if io_uring is not available, in which case no operation has been started.
+pub fn submit(ops: &mut [Op<'_>]) -> Option<Vec<io::Result<usize>>> {
+    with_ring(|ring| {
+        let mut results = Vec::with_capacity(ops.len());
+        for chunk in ops.chunks_mut(RING_ENTRIES as usize) {
+            if ring.run(chunk, &mut results).is_err() {
+                // Nothing of this chunk was started, so perform it the slow way instead of
+                // failing operations the kernel never looked at.
+                results.extend(chunk.iter_mut().map(Op::run_blocking));
+            }
+        }
+        results
+    })
+}
+
+/// Like [`submit`], but performs the operations one after another with blocking syscalls if
+/// io_uring is not available.
+pub fn submit_or_fallback(ops: &mut [Op<'_>]) -> Vec<io::Result<usize>> {
+    match submit(ops) {
+        Some(results) => results,
+        None => ops.iter_mut().map(Op::run_blocking).collect(),
+    }
+}
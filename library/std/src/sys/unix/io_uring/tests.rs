@@ -0,0 +1,129 @@
+use super::{is_available, submit, submit_or_fallback, Op, RING_ENTRIES};
+use crate::fs::{self, File, OpenOptions};
+use crate::io::{ErrorKind, IoSlice, Read, Seek};
+use crate::os::unix::io::AsFd;
+use crate::sys_common::io::test::tmpdir;
+
+fn open_rw(path: &crate::path::Path) -> File {
+    OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path).unwrap()
+}
+
+/// Writes with one batch, then reads everything back with another.
+fn write_then_read(run: impl Fn(&mut [Op<'_>]) -> Vec<crate::io::Result<usize>>) {
+    let tmp = tmpdir();
+    let mut file = open_rw(&tmp.join("batch"));
+    let fd = file.as_fd();
+
+    let (a, b) = (IoSlice::new(b"cd"), IoSlice::new(b"ef"));
+    let bufs = [a, b];
+    let results = run(&mut [
+        Op::Write { fd, buf: b"ab", offset: 0 },
+        Op::WriteVectored { fd, bufs: &bufs, offset: 2 },
+        Op::Write { fd, buf: b"gh", offset: 6 },
+    ]);
+    assert_eq!(results.into_iter().map(Result::unwrap).collect::<Vec<_>>(), [2, 4, 2]);
+
+    let (mut head, mut tail, mut past_end) = ([0; 3], [0; 8], [0; 4]);
+    let results = run(&mut [
+        Op::Read { fd, buf: &mut head, offset: 0 },
+        Op::Read { fd, buf: &mut tail, offset: 3 },
+        Op::Read { fd, buf: &mut past_end, offset: 8 },
+    ]);
+    assert_eq!(results.into_iter().map(Result::unwrap).collect::<Vec<_>>(), [3, 5, 0]);
+    assert_eq!(&head, b"abc");
+    assert_eq!(&tail[..5], b"defgh");
+
+    // Positional operations don't move the cursor.
+    assert_eq!(file.stream_position().unwrap(), 0);
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).unwrap();
+    assert_eq!(contents, "abcdefgh");
+}
+
+#[test]
+fn ring_operations() {
+    if !is_available() {
+        return;
+    }
+    write_then_read(|ops| submit(ops).unwrap());
+}
+
+#[test]
+fn blocking_fallback() {
+    write_then_read(|ops| ops.iter_mut().map(Op::run_blocking).collect());
+}
+
+#[test]
+fn submit_or_fallback_works_either_way() {
+    write_then_read(submit_or_fallback);
+}
+
+#[test]
+fn more_ops_than_ring_entries() {
+    let tmp = tmpdir();
+    let file = open_rw(&tmp.join("many"));
+    let fd = file.as_fd();
+
+    let count = RING_ENTRIES as usize * 3 + 1;
+    let data: Vec<u8> = (0..count).map(|i| i as u8).collect();
+    let mut ops: Vec<_> = data
+        .iter()
+        .enumerate()
+        .map(|(i, byte)| Op::Write { fd, buf: crate::slice::from_ref(byte), offset: i as u64 })
+        .collect();
+    let results = submit_or_fallback(&mut ops);
+    assert_eq!(results.len(), count);
+    assert!(results.into_iter().all(|r| r.unwrap() == 1));
+    assert_eq!(fs::read(tmp.join("many")).unwrap(), data);
+}
+
+#[test]
+fn errors_are_reported_per_operation() {
+    let tmp = tmpdir();
+    let path = tmp.join("read-only");
+    fs::write(&path, b"xyz").unwrap();
+    let file = File::open(&path).unwrap();
+    let fd = file.as_fd();
+
+    let mut buf = [0; 3];
+    let results = submit_or_fallback(&mut [
+        Op::Write { fd, buf: b"a", offset: 0 },
+        Op::Read { fd, buf: &mut buf, offset: 0 },
+    ]);
+    assert_eq!(results[0].as_ref().unwrap_err().raw_os_error(), Some(libc::EBADF));
+    assert_eq!(*results[1].as_ref().unwrap(), 3);
+    assert_eq!(&buf, b"xyz");
+}
+
+#[test]
+fn offsets_beyond_off_t_are_rejected() {
+    let tmp = tmpdir();
+    let mut file = open_rw(&tmp.join("offsets"));
+    let fd = file.as_fd();
+
+    let check = |run: &dyn Fn(&mut [Op<'_>]) -> Vec<crate::io::Result<usize>>| {
+        let mut buf = [0; 2];
+        let bufs = [IoSlice::new(b"c")];
+        let results = run(&mut [
+            Op::Write { fd, buf: b"ab", offset: u64::MAX },
+            Op::Write { fd, buf: b"ab", offset: 0 },
+            Op::Read { fd, buf: &mut buf, offset: i64::MAX as u64 + 1 },
+            Op::WriteVectored { fd, bufs: &bufs, offset: u64::MAX },
+        ]);
+        for i in [0, 2, 3] {
+            assert_eq!(results[i].as_ref().unwrap_err().kind(), ErrorKind::InvalidInput);
+        }
+        assert_eq!(*results[1].as_ref().unwrap(), 2);
+    };
+    check(&|ops| ops.iter_mut().map(Op::run_blocking).collect());
+    check(&submit_or_fallback);
+    if is_available() {
+        check(&|ops| submit(ops).unwrap());
+    }
+
+    // Nothing was written at, or moved, the current file position.
+    assert_eq!(file.stream_position().unwrap(), 0);
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).unwrap();
+    assert_eq!(contents, "ab");
+}
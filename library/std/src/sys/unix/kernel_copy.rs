@@ -125,6 +125,14 @@ fn copy_file_range_candidate(&self) -> bool {
             _ => false,
         }
     }
+
+    /// The number of bytes that can be read if this is a regular file.
+    fn regular_file_len(&self) -> Option<u64> {
+        match self {
+            FdMeta::Metadata(meta) if meta.is_file() => Some(meta.len()),
+            _ => None,
+        }
+    }
 }
 
 struct CopyParams(FdMeta, Option<RawFd>);
@@ -206,6 +214,22 @@ fn copy(self) -> Result<u64> {
                     }
                 }
             }
+
+            // None of the zero-copy syscalls worked for these files, e.g. because they are blocked
+            // by a seccomp filter, in which case batching reads and writes through io_uring still
+            // saves most of the syscalls of the generic copy loop.
+            if let Some(len) = input_meta.regular_file_len() {
+                if output_meta.copy_file_range_candidate() {
+                    let result = copy_regular_files_uring(readfd, writefd, reader.min_limit(), len);
+                    result.update_take(reader);
+
+                    match result {
+                        CopyResult::Ended(bytes_copied) => return Ok(bytes_copied + written),
+                        CopyResult::Error(e, _) => return Err(e),
+                        CopyResult::Fallback(bytes) => written += bytes,
+                    }
+                }
+            }
         }
 
         // fallback if none of the more specialized syscalls wants to work with these file descriptors
@@ -601,6 +625,161 @@ fn copy_file_range(
     CopyResult::Ended(written)
 }
 
+/// Copies between regular files by batching positional reads and writes through io_uring.
+///
+/// This is a last resort before the generic read/write loop, for when the zero-copy syscalls
+/// can't be used: `copy_file_range` refuses to copy between filesystems of different types since
+/// Linux 5.19, and `sendfile` and `copy_file_range` may both be blocked, e.g. by a seccomp filter.
+/// Each batch writes the data read by the previous one while reading the next chunks into a
+/// second buffer.
+///
+/// Copying stops at EOF, after `max_len` bytes or after `len_hint` bytes (usually the size of the
+/// source file). In the last case `Fallback` is returned so that callers pick up data that may
+/// have been appended in the meantime. Both file offsets are advanced by the number of bytes
+/// copied.
+#[cfg(target_os = "linux")]
+pub(super) fn copy_regular_files_uring(
+    reader: RawFd,
+    writer: RawFd,
+    max_len: u64,
+    len_hint: u64,
+) -> CopyResult {
+    use crate::os::unix::fs::FileExt;
+    use crate::os::unix::io::BorrowedFd;
+    use crate::sys::io_uring::{self, Op};
+
+    const CHUNK_SIZE: usize = 128 * 1024;
+    const CHUNKS: usize = 8;
+
+    let len = min(max_len, len_hint);
+    // Don't leave a ring behind in every thread that ever copied a file.
+    let _ring_scope = io_uring::RingScope::new();
+    if len == 0 || !io_uring::is_available() {
+        return CopyResult::Fallback(0);
+    }
+
+    // Writes to files opened with O_APPEND ignore the offset, so they might get reordered.
+    match cvt(unsafe { libc::fcntl(writer, libc::F_GETFL) }) {
+        Ok(flags) if flags & libc::O_APPEND == 0 => {}
+        _ => return CopyResult::Fallback(0),
+    }
+    // Reads and writes of the same batch run concurrently, which would go wrong if they overlap.
+    let same_file = unsafe {
+        let mut r: libc::stat64 = crate::mem::zeroed();
+        let mut w: libc::stat64 = crate::mem::zeroed();
+        cvt(libc::fstat64(reader, &mut r))
+            .and_then(|_| cvt(libc::fstat64(writer, &mut w)))
+            .map(|_| r.st_dev == w.st_dev && r.st_ino == w.st_ino)
+    };
+    if !matches!(same_file, Ok(false)) {
+        return CopyResult::Fallback(0);
+    }
+    // Fails for pipes and sockets, which can't be used with positional I/O anyway.
+    let position =
+        |fd: RawFd| cvt(unsafe { libc::lseek64(fd, 0, libc::SEEK_CUR) }).map(|pos| pos as u64);
+    let (Ok(read_start), Ok(write_start)) = (position(reader), position(writer)) else {
+        return CopyResult::Fallback(0);
+    };
+
+    // SAFETY: the caller keeps both file descriptors open for the duration of this call.
+    let (reader_fd, writer_fd) =
+        unsafe { (BorrowedFd::borrow_raw(reader), BorrowedFd::borrow_raw(writer)) };
+    let writer_file = ManuallyDrop::new(unsafe { File::from_raw_fd(writer) });
+
+    let buf_len = min(len, (CHUNK_SIZE * CHUNKS) as u64) as usize;
+    let mut bufs = [vec![0u8; buf_len], vec![0u8; buf_len]];
+    // Index of the buffer to read into next, the other one holds `pending` bytes to be written.
+    let mut current = 0;
+    let mut pending = 0;
+    let mut read = 0u64;
+    let mut written = 0u64;
+    let mut eof = false;
+    let mut error = None;
+
+    loop {
+        let to_read = if eof { 0 } else { min(len - read, buf_len as u64) as usize };
+        if pending == 0 && to_read == 0 {
+            break;
+        }
+
+        let results = {
+            let [first, second] = &mut bufs;
+            let (read_buf, write_buf) =
+                if current == 0 { (first, second) } else { (second, first) };
+            let mut ops = Vec::with_capacity(1 + CHUNKS);
+            if pending > 0 {
+                ops.push(Op::Write {
+                    fd: writer_fd,
+                    buf: &write_buf[..pending],
+                    offset: write_start + written,
+                });
+            }
+            for (i, chunk) in read_buf[..to_read].chunks_mut(CHUNK_SIZE).enumerate() {
+                let offset = read_start + read + (i * CHUNK_SIZE) as u64;
+                ops.push(Op::Read { fd: reader_fd, buf: chunk, offset });
+            }
+            io_uring::submit_or_fallback(&mut ops)
+        };
+        let mut results = results.into_iter();
+
+        if pending > 0 {
+            let write_buf = &bufs[1 - current][..pending];
+            let offset = write_start + written;
+            let done = match results.next().unwrap() {
+                Ok(n) => n,
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            };
+            // Complete short writes with blocking syscalls.
+            if let Err(e) = writer_file.write_all_at(&write_buf[done..], offset + done as u64) {
+                written += done as u64;
+                error = Some(e);
+                break;
+            }
+            written += pending as u64;
+        }
+
+        // Stop at the first short read. For regular files this means EOF was reached.
+        let mut filled = 0;
+        for (i, result) in results.enumerate() {
+            let requested = min(CHUNK_SIZE, to_read - i * CHUNK_SIZE);
+            match result {
+                Ok(n) => filled += n,
+                // Write what has been read so far before reporting the error.
+                Err(e) => error = Some(e),
+            }
+            if filled < (i * CHUNK_SIZE) + requested {
+                eof = true;
+                break;
+            }
+        }
+        read += filled as u64;
+        pending = filled;
+        current = 1 - current;
+    }
+
+    let result = match error {
+        None if eof || written == max_len => CopyResult::Ended(written),
+        None => CopyResult::Fallback(written),
+        // Nothing has been copied, leave reporting the error to the fallback.
+        Some(_) if written == 0 => return CopyResult::Fallback(0),
+        Some(e) => CopyResult::Error(e, written),
+    };
+    let seek = |fd: RawFd, pos: u64| cvt(unsafe { libc::lseek64(fd, pos as i64, libc::SEEK_SET) });
+    match seek(reader, read_start + written).and_then(|_| seek(writer, write_start + written)) {
+        Ok(_) => result,
+        Err(e) => CopyResult::Error(e, written),
+    }
+}
+
+/// io_uring is not used on Android.
+#[cfg(not(target_os = "linux"))]
+pub(super) fn copy_regular_files_uring(_: RawFd, _: RawFd, _: u64, _: u64) -> CopyResult {
+    CopyResult::Fallback(0)
+}
+
 #[derive(PartialEq)]
 enum SpliceMode {
     Sendfile,
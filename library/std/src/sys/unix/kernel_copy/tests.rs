@@ -83,6 +83,52 @@ fn copies_append_mode_sink() -> Result<()> {
     Ok(())
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn uring_copy_offsets() -> Result<()> {
+    use super::{copy_regular_files_uring, CopyResult};
+
+    if !crate::sys::io_uring::is_available() {
+        return Ok(());
+    }
+
+    let tmp_path = tmpdir();
+    let source_path = tmp_path.join("copy-uring.source");
+    let sink_path = tmp_path.join("copy-uring.sink");
+    let mut source =
+        OpenOptions::new().create(true).truncate(true).write(true).read(true).open(&source_path)?;
+    let data: Vec<u8> = (0..300_000u32).map(|i| i as u8).collect();
+    source.write_all(&data)?;
+    source.seek(SeekFrom::Start(2))?;
+    let mut sink =
+        OpenOptions::new().create(true).truncate(true).write(true).read(true).open(&sink_path)?;
+    sink.write_all(b"ab")?;
+
+    // Stops at the length hint and leaves the rest to the caller.
+    let result = copy_regular_files_uring(source.as_raw_fd(), sink.as_raw_fd(), u64::MAX, 3);
+    assert!(matches!(result, CopyResult::Fallback(3)));
+    assert_eq!(source.stream_position()?, 5);
+    assert_eq!(sink.stream_position()?, 5);
+
+    // Stops at the length limit.
+    let result = copy_regular_files_uring(source.as_raw_fd(), sink.as_raw_fd(), 200_000, u64::MAX);
+    assert!(matches!(result, CopyResult::Ended(200_000)));
+
+    // Stops at EOF.
+    let result = copy_regular_files_uring(source.as_raw_fd(), sink.as_raw_fd(), u64::MAX, u64::MAX);
+    assert!(matches!(result, CopyResult::Ended(99_995)));
+    assert_eq!(source.stream_position()?, 300_000);
+    assert_eq!(sink.stream_position()?, 300_000);
+
+    let mut copied = Vec::new();
+    sink.seek(SeekFrom::Start(0))?;
+    sink.read_to_end(&mut copied)?;
+    assert_eq!(&copied[..2], b"ab");
+    assert!(copied[2..] == data[2..]);
+
+    Ok(())
+}
+
 #[bench]
 fn bench_file_to_file_copy(b: &mut test::Bencher) {
     const BYTES: usize = 128 * 1024;
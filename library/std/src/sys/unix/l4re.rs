@@ -362,6 +362,139 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         }
     }
 
+    pub struct TcpSocket {
+        inner: Socket,
+    }
+
+    impl TcpSocket {
+        pub fn new_v4() -> io::Result<TcpSocket> {
+            unimpl!();
+        }
+
+        pub fn new_v6() -> io::Result<TcpSocket> {
+            unimpl!();
+        }
+
+        pub fn bind(&self, _: &SocketAddr) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn listen(self, _: u32) -> io::Result<TcpListener> {
+            unimpl!();
+        }
+
+        pub fn connect(self, _: &SocketAddr) -> io::Result<TcpStream> {
+            unimpl!();
+        }
+
+        pub fn connect_timeout(self, _: &SocketAddr, _: Duration) -> io::Result<TcpStream> {
+            unimpl!();
+        }
+
+        pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+            unimpl!();
+        }
+
+        pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn reuse_address(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn reuse_port(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn keepalive(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_keepalive_params(
+            &self,
+            _: Option<Duration>,
+            _: Option<Duration>,
+            _: Option<u32>,
+        ) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn send_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_nodelay(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn nodelay(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_ttl(&self, _: u32) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn ttl(&self) -> io::Result<u32> {
+            unimpl!();
+        }
+
+        pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn only_v6(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_unicast_hops_v6(&self, _: u32) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn unicast_hops_v6(&self) -> io::Result<u32> {
+            unimpl!();
+        }
+
+        pub fn set_tclass_v6(&self, _: u32) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn tclass_v6(&self) -> io::Result<u32> {
+            unimpl!();
+        }
+
+        pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+            unimpl!();
+        }
+    }
+
+    impl fmt::Debug for TcpSocket {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "No networking support available on L4Re.")
+        }
+    }
+
     pub struct UdpSocket {
         inner: Socket,
     }
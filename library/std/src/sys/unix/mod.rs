@@ -19,6 +19,8 @@
 pub mod fs;
 pub mod futex;
 pub mod io;
+#[cfg(target_os = "linux")]
+pub mod io_uring;
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub mod kernel_copy;
 #[cfg(target_os = "l4re")]
@@ -35,6 +37,8 @@
 pub mod pipe;
 pub mod process;
 pub mod rand;
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "emscripten")))]
+pub mod signals;
 pub mod stack_overflow;
 pub mod stdio;
 pub mod thread;
@@ -392,6 +392,190 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "watchos",
+    ))]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_REUSEPORT, reuse as c_int)
+    }
+
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "watchos",
+    ))]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(self, libc::SOL_SOCKET, libc::SO_REUSEPORT)?;
+        Ok(raw != 0)
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "watchos",
+    )))]
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "SO_REUSEPORT is not supported"))
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "watchos",
+    )))]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "SO_REUSEPORT is not supported"))
+    }
+
+    pub fn set_unicast_hops_v6(&self, hops: u32) -> io::Result<()> {
+        setsockopt(self, libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS, hops as c_int)
+    }
+
+    pub fn unicast_hops_v6(&self) -> io::Result<u32> {
+        let raw: c_int = getsockopt(self, libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS)?;
+        Ok(raw as u32)
+    }
+
+    #[cfg(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "watchos",
+    ))]
+    pub fn set_tclass_v6(&self, tclass: u32) -> io::Result<()> {
+        setsockopt(self, libc::IPPROTO_IPV6, libc::IPV6_TCLASS, tclass as c_int)
+    }
+
+    #[cfg(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "watchos",
+    ))]
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        let raw: c_int = getsockopt(self, libc::IPPROTO_IPV6, libc::IPV6_TCLASS)?;
+        Ok(raw as u32)
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "watchos",
+    )))]
+    pub fn set_tclass_v6(&self, _tclass: u32) -> io::Result<()> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "IPV6_TCLASS is not supported"))
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "watchos",
+    )))]
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "IPV6_TCLASS is not supported"))
+    }
+
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "watchos",
+    ))]
+    pub fn set_keepalive_params(
+        &self,
+        time: Option<Duration>,
+        interval: Option<Duration>,
+        retries: Option<u32>,
+    ) -> io::Result<()> {
+        #[cfg(target_vendor = "apple")]
+        use libc::TCP_KEEPALIVE as TCP_KEEPIDLE;
+        #[cfg(not(target_vendor = "apple"))]
+        use libc::TCP_KEEPIDLE;
+
+        // All of these options are in whole seconds.
+        let secs = |dur: Duration| cmp::min(dur.as_secs(), c_int::MAX as u64) as c_int;
+
+        if let Some(time) = time {
+            setsockopt(self, libc::IPPROTO_TCP, TCP_KEEPIDLE, secs(time))?;
+        }
+        if let Some(interval) = interval {
+            setsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL, secs(interval))?;
+        }
+        if let Some(retries) = retries {
+            let retries = cmp::min(retries, c_int::MAX as u32) as c_int;
+            setsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPCNT, retries)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "watchos",
+    )))]
+    pub fn set_keepalive_params(
+        &self,
+        _time: Option<Duration>,
+        _interval: Option<Duration>,
+        _retries: Option<u32>,
+    ) -> io::Result<()> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "configuring TCP keepalive probes is not supported",
+        ))
+    }
+
     #[cfg(any(target_os = "android", target_os = "linux",))]
     pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
         setsockopt(self, libc::SOL_SOCKET, libc::SO_PASSCRED, passcred as libc::c_int)
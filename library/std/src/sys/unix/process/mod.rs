@@ -1,8 +1,18 @@
 pub use self::process_common::{Command, CommandArgs, ExitCode, Stdio, StdioPipes};
 pub use self::process_inner::{ExitStatus, ExitStatusError, Process};
 pub use crate::ffi::OsString as EnvKey;
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "emscripten")))]
+pub use crate::sys::signals::install_interrupt_handler;
 pub use crate::sys_common::process::CommandEnvs;
 
+#[cfg(any(target_os = "espidf", target_os = "horizon", target_os = "emscripten"))]
+pub fn install_interrupt_handler(_handler: fn()) -> crate::io::Result<()> {
+    Err(crate::io::const_io_error!(
+        crate::io::ErrorKind::Unsupported,
+        "signals are not supported on this platform",
+    ))
+}
+
 #[cfg_attr(any(target_os = "espidf", target_os = "horizon"), allow(unused))]
 mod process_common;
 
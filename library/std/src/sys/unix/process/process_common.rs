@@ -84,6 +84,11 @@ pub struct Command {
     #[cfg(target_os = "linux")]
     create_pidfd: bool,
     pgroup: Option<pid_t>,
+    setsid: bool,
+    setctty: bool,
+    rlimits: Vec<(c_int, u64, u64)>,
+    #[cfg(target_os = "linux")]
+    pdeathsig: Option<c_int>,
 }
 
 // Create a new type for argv, so that we can make it `Send` and `Sync`
@@ -148,6 +153,9 @@ pub fn new(program: &OsStr) -> Command {
             stdout: None,
             stderr: None,
             pgroup: None,
+            setsid: false,
+            setctty: false,
+            rlimits: Vec::new(),
         }
     }
 
@@ -171,6 +179,10 @@ pub fn new(program: &OsStr) -> Command {
             stderr: None,
             create_pidfd: false,
             pgroup: None,
+            setsid: false,
+            setctty: false,
+            rlimits: Vec::new(),
+            pdeathsig: None,
         }
     }
 
@@ -209,6 +221,22 @@ pub fn groups(&mut self, groups: &[gid_t]) {
     pub fn pgroup(&mut self, pgroup: pid_t) {
         self.pgroup = Some(pgroup);
     }
+    pub fn setsid(&mut self, setsid: bool) {
+        self.setsid = setsid;
+    }
+    pub fn setctty(&mut self, setctty: bool) {
+        self.setctty = setctty;
+    }
+    pub fn rlimit(&mut self, resource: c_int, soft: u64, hard: u64) {
+        // Later limits for the same resource replace earlier ones.
+        self.rlimits.retain(|&(r, ..)| r != resource);
+        self.rlimits.push((resource, soft, hard));
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn pdeathsig(&mut self, signal: c_int) {
+        self.pdeathsig = Some(signal);
+    }
 
     #[cfg(target_os = "linux")]
     pub fn create_pidfd(&mut self, val: bool) {
@@ -277,6 +305,54 @@ pub fn get_pgroup(&self) -> Option<pid_t> {
         self.pgroup
     }
 
+    pub fn get_setsid(&self) -> bool {
+        self.setsid
+    }
+
+    pub fn get_setctty(&self) -> bool {
+        self.setctty
+    }
+
+    pub fn get_rlimits(&self) -> &[(c_int, u64, u64)] {
+        &self.rlimits
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    #[allow(dead_code)]
+    pub fn get_pdeathsig(&self) -> Option<c_int> {
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn get_pdeathsig(&self) -> Option<c_int> {
+        self.pdeathsig
+    }
+
+    /// Checks that the session related settings and resource limits can be
+    /// applied, together and on this platform.
+    pub fn check_session(&self) -> io::Result<()> {
+        #[cfg(any(target_os = "vxworks", target_os = "espidf", target_os = "horizon"))]
+        if self.setsid || self.setctty || !self.rlimits.is_empty() {
+            return Err(io::const_io_error!(
+                io::ErrorKind::Unsupported,
+                "sessions and resource limits are not supported on this platform",
+            ));
+        }
+        if self.setsid && self.pgroup.is_some() {
+            return Err(io::const_io_error!(
+                io::ErrorKind::InvalidInput,
+                "a new session can't be combined with a process group",
+            ));
+        }
+        if self.setctty && !self.setsid {
+            return Err(io::const_io_error!(
+                io::ErrorKind::InvalidInput,
+                "acquiring a controlling terminal requires a new session",
+            ));
+        }
+        Ok(())
+    }
+
     pub fn get_closures(&mut self) -> &mut Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
     }
@@ -122,3 +122,99 @@ fn test_process_group_no_posix_spawn() {
         t!(cat.wait());
     }
 }
+
+#[test]
+#[cfg_attr(
+    any(
+        // See test_process_mask
+        target_os = "macos",
+        target_arch = "arm",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+    ),
+    ignore
+)]
+fn test_setsid() {
+    // Check both the posix_spawn and the fork path, the latter is forced by
+    // `pre_exec`.
+    for force_fork in [false, true] {
+        unsafe {
+            let mut cmd = Command::new(OsStr::new("cat"));
+            cmd.setsid(true);
+            if force_fork {
+                cmd.pre_exec(Box::new(|| Ok(())));
+            }
+            cmd.stdin(Stdio::MakePipe);
+            cmd.stdout(Stdio::MakePipe);
+            let (mut cat, pipes) = t!(cmd.spawn(Stdio::Null, true));
+
+            // The child leads a new session and a new process group in it.
+            let pid = cat.id() as libc::pid_t;
+            assert_eq!(libc::getsid(pid), pid);
+            assert_eq!(libc::getpgid(pid), pid);
+            assert_ne!(libc::getsid(0), pid);
+
+            drop(pipes);
+            t!(cat.wait());
+        }
+    }
+}
+
+#[test]
+fn test_session_conflicts() {
+    let mut cmd = Command::new(OsStr::new("true"));
+    cmd.setsid(true);
+    cmd.pgroup(0);
+    let err = cmd.spawn(Stdio::Null, false).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let mut cmd = Command::new(OsStr::new("true"));
+    cmd.setctty(true);
+    let err = cmd.spawn(Stdio::Null, false).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_rlimit() {
+    let mut current = mem::MaybeUninit::<libc::rlimit>::uninit();
+    t!(cvt(unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, current.as_mut_ptr()) }));
+    let hard = unsafe { current.assume_init() }.rlim_max;
+    let soft = if hard == libc::RLIM_INFINITY { 64 } else { crate::cmp::min(hard, 64) };
+
+    let mut cmd = Command::new(OsStr::new("sh"));
+    cmd.arg(OsStr::new("-c"));
+    cmd.arg(OsStr::new("ulimit -Sn"));
+    // The second call for the same resource replaces the first one.
+    cmd.rlimit(libc::RLIMIT_NOFILE as c_int, 1, 1);
+    cmd.rlimit(libc::RLIMIT_NOFILE as c_int, soft as u64, hard as u64);
+    cmd.stdout(Stdio::MakePipe);
+    let (mut child, mut pipes) = t!(cmd.spawn(Stdio::Null, false));
+
+    let stdout = pipes.stdout.take().unwrap();
+    let (mut output, mut buf) = (Vec::new(), [0; 16]);
+    loop {
+        match t!(stdout.read(&mut buf)) {
+            0 => break,
+            n => output.extend_from_slice(&buf[..n]),
+        }
+    }
+    assert!(t!(child.wait()).success());
+    assert_eq!(output, format!("{soft}\n").as_bytes());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_pdeathsig() {
+    // The signal is sent when the thread that spawned the child exits.
+    let mut child = crate::thread::spawn(|| {
+        let mut cmd = Command::new(OsStr::new("sleep"));
+        cmd.arg(OsStr::new("1000"));
+        cmd.pdeathsig(libc::SIGKILL);
+        t!(cmd.spawn(Stdio::Null, false)).0
+    })
+    .join()
+    .unwrap();
+
+    let status = t!(child.wait());
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+}
@@ -51,6 +51,7 @@ pub fn spawn(
                 "nul byte found in provided data",
             ));
         }
+        self.check_session()?;
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
@@ -70,13 +71,14 @@ pub fn spawn(
         // in its own process. Thus the parent drops the lock guard while the child
         // forgets it to avoid unlocking it on a new thread, which would be invalid.
         let env_lock = sys::os::env_read_lock();
+        let parent = unsafe { libc::getpid() };
         let (pid, pidfd) = unsafe { self.do_fork()? };
 
         if pid == 0 {
             crate::panic::always_abort();
             mem::forget(env_lock);
             drop(input);
-            let Err(err) = unsafe { self.do_exec(theirs, envp.as_ref()) };
+            let Err(err) = unsafe { self.do_exec(theirs, envp.as_ref(), parent) };
             let errno = err.raw_os_error().unwrap_or(libc::EINVAL) as u32;
             let errno = errno.to_be_bytes();
             let bytes = [
@@ -226,6 +228,9 @@ pub fn exec(&mut self, default: Stdio) -> io::Error {
         if self.saw_nul() {
             return io::const_io_error!(ErrorKind::InvalidInput, "nul byte found in provided data",);
         }
+        if let Err(e) = self.check_session() {
+            return e;
+        }
 
         match self.setup_io(default, true) {
             Ok((_, theirs)) => {
@@ -235,7 +240,7 @@ pub fn exec(&mut self, default: Stdio) -> io::Error {
                     // environment lock before we try to exec.
                     let _lock = sys::os::env_read_lock();
 
-                    let Err(e) = self.do_exec(theirs, envp.as_ref());
+                    let Err(e) = self.do_exec(theirs, envp.as_ref(), libc::getppid());
                     e
                 }
             }
@@ -273,10 +278,16 @@ pub fn exec(&mut self, default: Stdio) -> io::Error {
     // allocation). Instead we just close it manually. This will never
     // have the drop glue anyway because this code never returns (the
     // child will either exec() or invoke libc::exit)
+    //
+    // `parent` is the process ID the new program should see as its parent,
+    // used to detect whether it already exited before a parent-death signal
+    // could be requested.
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
     unsafe fn do_exec(
         &mut self,
         stdio: ChildPipes,
         maybe_envp: Option<&CStringArray>,
+        parent: pid_t,
     ) -> Result<!, io::Error> {
         use crate::sys::{self, cvt_r};
 
@@ -292,6 +303,12 @@ unsafe fn do_exec(
 
         #[cfg(not(target_os = "l4re"))]
         {
+            // Raising hard limits requires privileges, so this has to happen
+            // before they are dropped below.
+            for &(resource, soft, hard) in self.get_rlimits() {
+                let limit = libc::rlimit { rlim_cur: rlim(soft), rlim_max: rlim(hard) };
+                cvt(libc::setrlimit(resource as _, &limit))?;
+            }
             if let Some(_g) = self.get_groups() {
                 //FIXME: Redox kernel does not support setgroups yet
                 #[cfg(not(target_os = "redox"))]
@@ -323,6 +340,25 @@ unsafe fn do_exec(
             cvt(libc::setpgid(0, pgroup))?;
         }
 
+        if self.get_setsid() {
+            cvt(libc::setsid())?;
+            if self.get_setctty() {
+                cvt(libc::ioctl(libc::STDIN_FILENO, libc::TIOCSCTTY as _, 0))?;
+            }
+        }
+
+        // Changing credentials above clears the parent-death signal, so it
+        // has to be requested afterwards.
+        #[cfg(target_os = "linux")]
+        if let Some(signal) = self.get_pdeathsig() {
+            cvt(libc::prctl(libc::PR_SET_PDEATHSIG, signal as libc::c_ulong))?;
+            // The parent may have exited before the signal was requested, in
+            // which case it would never be delivered.
+            if libc::getppid() != parent {
+                cvt(libc::kill(libc::getpid(), signal))?;
+            }
+        }
+
         // emscripten has no signal support.
         #[cfg(not(target_os = "emscripten"))]
         {
@@ -419,10 +455,30 @@ fn posix_spawn(
             || !self.get_closures().is_empty()
             || self.get_groups().is_some()
             || self.get_create_pidfd()
+            // posix_spawn() has no attributes for these, see the CommandExt docs.
+            || !self.get_rlimits().is_empty()
+            || self.get_setctty()
+            || self.get_pdeathsig().is_some()
         {
             return Ok(None);
         }
 
+        // glibc 2.26+ and macOS can start the child in a new session. The
+        // flag isn't exposed by the libc crate for all of them yet.
+        #[cfg(target_os = "macos")]
+        const POSIX_SPAWN_SETSID: c_int = 0x0400;
+        #[cfg(not(target_os = "macos"))]
+        const POSIX_SPAWN_SETSID: c_int = 0x80;
+        let setsid = self.get_setsid();
+        if setsid {
+            #[cfg(all(target_os = "linux", target_env = "gnu"))]
+            if !matches!(sys::os::glibc_version(), Some(version) if version >= (2, 26)) {
+                return Ok(None);
+            }
+            #[cfg(any(target_os = "freebsd", all(target_os = "linux", target_env = "musl")))]
+            return Ok(None);
+        }
+
         // Only glibc 2.24+ posix_spawn() supports returning ENOENT directly.
         #[cfg(all(target_os = "linux", target_env = "gnu"))]
         {
@@ -528,6 +584,9 @@ fn drop(&mut self) {
                 flags |= libc::POSIX_SPAWN_SETPGROUP;
                 cvt_nz(libc::posix_spawnattr_setpgroup(attrs.0.as_mut_ptr(), pgroup))?;
             }
+            if setsid {
+                flags |= POSIX_SPAWN_SETSID;
+            }
 
             let mut set = MaybeUninit::<libc::sigset_t>::uninit();
             cvt(sigemptyset(set.as_mut_ptr()))?;
@@ -554,6 +613,16 @@ fn drop(&mut self) {
     }
 }
 
+/// Converts a resource limit to `rlim_t`. `u64::MAX`, like any value too large
+/// to be represented, means no limit.
+#[cfg(not(target_os = "l4re"))]
+fn rlim(value: u64) -> libc::rlim_t {
+    match libc::rlim_t::try_from(value) {
+        Ok(value) if value < libc::RLIM_INFINITY => value,
+        _ => libc::RLIM_INFINITY,
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Processes
 ////////////////////////////////////////////////////////////////////////////////
@@ -28,6 +28,7 @@ pub fn spawn(
                 "nul byte found in provided data",
             ));
         }
+        self.check_session()?;
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
         let mut p = Process { pid: 0, status: None };
 
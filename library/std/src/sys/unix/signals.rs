@@ -0,0 +1,218 @@
+//! Delivery of signals to listeners through self-pipes.
+//!
+//! A signal handler may only call async-signal-safe functions, which rules out locks and
+//! allocation. The handler installed here therefore only looks up which listeners are interested
+//! in the signal in a fixed table of atomics, and writes the signal number into each of their
+//! pipes. Listeners read from the other end of their pipe, outside of the handler.
+//!
+//! Handlers are installed when the first listener for a signal is created and the previous
+//! disposition of the signal is restored once the last one is dropped.
+
+#[cfg(test)]
+mod tests;
+
+use crate::io;
+use crate::mem;
+use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd};
+use crate::ptr;
+use crate::sync::atomic::{AtomicI32, AtomicU32, AtomicUsize, Ordering};
+use crate::sync::{Mutex, PoisonError};
+use crate::sys::fd::FileDesc;
+use crate::sys::os::{errno, set_errno};
+use crate::sys::{cvt, cvt_r, pipe};
+use crate::sys_common::IntoInner;
+use crate::thread;
+use libc::c_int;
+
+/// The signals that can be listened for.
+pub const SIGNALS: [c_int; 4] = [libc::SIGINT, libc::SIGTERM, libc::SIGHUP, libc::SIGCHLD];
+
+/// How many listeners can exist at the same time.
+const MAX_LISTENERS: usize = 64;
+
+struct Slot {
+    /// The write end of the listener's pipe, or -1 if the slot is free.
+    fd: AtomicI32,
+    /// The signals the listener is interested in, with bit `n` standing for signal number `n`.
+    mask: AtomicU32,
+}
+
+const FREE: Slot = Slot { fd: AtomicI32::new(-1), mask: AtomicU32::new(0) };
+static SLOTS: [Slot; MAX_LISTENERS] = [FREE; MAX_LISTENERS];
+
+/// The number of signal handlers currently running, on any thread.
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+struct Registry {
+    /// The number of listeners for each of `SIGNALS`.
+    listeners: [usize; SIGNALS.len()],
+    /// The disposition of each of `SIGNALS` before our handler was installed.
+    previous: [Option<libc::sigaction>; SIGNALS.len()],
+}
+
+// Serializes creating and dropping listeners. Never touched by the signal handler.
+static REGISTRY: Mutex<Registry> =
+    Mutex::new(Registry { listeners: [0; SIGNALS.len()], previous: [None; SIGNALS.len()] });
+
+extern "C" fn handler(signum: c_int) {
+    RUNNING.fetch_add(1, Ordering::SeqCst);
+    // `write` may change errno, which the interrupted code might be about to read.
+    let saved_errno = errno();
+    let bit = 1 << signum;
+    for slot in &SLOTS {
+        if slot.mask.load(Ordering::SeqCst) & bit != 0 {
+            let fd = slot.fd.load(Ordering::SeqCst);
+            if fd >= 0 {
+                // The write end is non-blocking. If the pipe is full, the listener still has
+                // plenty of signals to process and dropping this one is fine.
+                let byte = signum as u8;
+                unsafe { libc::write(fd, (&byte as *const u8).cast(), 1) };
+            }
+        }
+    }
+    set_errno(saved_errno);
+    RUNNING.fetch_sub(1, Ordering::SeqCst);
+}
+
+unsafe fn install(signum: c_int) -> io::Result<libc::sigaction> {
+    let mut action: libc::sigaction = mem::zeroed();
+    action.sa_sigaction = handler as extern "C" fn(c_int) as libc::sighandler_t;
+    action.sa_flags = libc::SA_RESTART as _;
+    cvt(libc::sigemptyset(&mut action.sa_mask))?;
+    let mut previous = mem::zeroed();
+    cvt(libc::sigaction(signum, &action, &mut previous))?;
+    Ok(previous)
+}
+
+unsafe fn restore(signum: c_int, previous: &libc::sigaction) {
+    libc::sigaction(signum, previous, ptr::null_mut());
+}
+
+/// Receives signals through a pipe written to by the signal handler.
+pub struct Listener {
+    read: FileDesc,
+    // Kept open until the listener has been removed from `SLOTS`.
+    _write: FileDesc,
+    slot: usize,
+    mask: u32,
+}
+
+impl Listener {
+    /// Starts listening for `signals`, which must all be part of `SIGNALS`.
+    pub fn new(signals: &[c_int]) -> io::Result<Listener> {
+        let mut mask = 0u32;
+        for &signum in signals {
+            debug_assert!(SIGNALS.contains(&signum));
+            mask |= 1 << signum;
+        }
+
+        let (read, write) = pipe::anon_pipe()?;
+        let (read, write) = (read.into_inner(), write.into_inner());
+        write.set_nonblocking(true)?;
+
+        let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+        let slot = SLOTS
+            .iter()
+            .position(|slot| slot.fd.load(Ordering::SeqCst) < 0)
+            .ok_or(io::const_io_error!(io::ErrorKind::Other, "too many signal listeners"))?;
+        SLOTS[slot].fd.store(write.as_raw_fd(), Ordering::SeqCst);
+        SLOTS[slot].mask.store(mask, Ordering::SeqCst);
+
+        for (i, &signum) in SIGNALS.iter().enumerate() {
+            if mask & (1 << signum) == 0 || registry.listeners[i] > 0 {
+                continue;
+            }
+            match unsafe { install(signum) } {
+                Ok(previous) => registry.previous[i] = Some(previous),
+                Err(e) => {
+                    // Undo everything done for this listener so far.
+                    SLOTS[slot].mask.store(0, Ordering::SeqCst);
+                    SLOTS[slot].fd.store(-1, Ordering::SeqCst);
+                    for (j, &signum) in SIGNALS.iter().enumerate().take(i) {
+                        if mask & (1 << signum) != 0 && registry.listeners[j] == 0 {
+                            if let Some(previous) = registry.previous[j].take() {
+                                unsafe { restore(signum, &previous) };
+                            }
+                        }
+                    }
+                    wait_for_handlers();
+                    return Err(e);
+                }
+            }
+        }
+        for (i, &signum) in SIGNALS.iter().enumerate() {
+            if mask & (1 << signum) != 0 {
+                registry.listeners[i] += 1;
+            }
+        }
+
+        Ok(Listener { read, _write: write, slot, mask })
+    }
+
+    /// Blocks until a signal arrives and returns its number.
+    pub fn recv(&self) -> io::Result<c_int> {
+        let mut byte = 0u8;
+        loop {
+            match self.read.read(crate::slice::from_mut(&mut byte)) {
+                Ok(_) => return Ok(byte as c_int),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Returns the number of a signal that already arrived, if any.
+    pub fn try_recv(&self) -> io::Result<Option<c_int>> {
+        let mut pollfd =
+            libc::pollfd { fd: self.read.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+        if cvt_r(|| unsafe { libc::poll(&mut pollfd, 1, 0) })? == 0 {
+            return Ok(None);
+        }
+        self.recv().map(Some)
+    }
+}
+
+impl AsFd for Listener {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.read.as_fd()
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+        SLOTS[self.slot].mask.store(0, Ordering::SeqCst);
+        SLOTS[self.slot].fd.store(-1, Ordering::SeqCst);
+        for (i, &signum) in SIGNALS.iter().enumerate() {
+            if self.mask & (1 << signum) != 0 {
+                registry.listeners[i] -= 1;
+                if registry.listeners[i] == 0 {
+                    if let Some(previous) = registry.previous[i].take() {
+                        unsafe { restore(signum, &previous) };
+                    }
+                }
+            }
+        }
+        // A handler that started before the slot was cleared may still write to the pipe, so it
+        // can't be closed yet.
+        wait_for_handlers();
+    }
+}
+
+fn wait_for_handlers() {
+    while RUNNING.load(Ordering::SeqCst) != 0 {
+        thread::yield_now();
+    }
+}
+
+/// Calls `handler` on a dedicated thread each time the process receives `SIGINT` or `SIGTERM`.
+/// `handler` must not unwind, see `process::on_interrupt`.
+pub fn install_interrupt_handler(handler: fn()) -> io::Result<()> {
+    let listener = Listener::new(&[libc::SIGINT, libc::SIGTERM])?;
+    thread::Builder::new().name("interrupt handler".to_owned()).spawn(move || {
+        while listener.recv().is_ok() {
+            handler();
+        }
+    })?;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use super::Listener;
+use crate::sync::{Mutex, MutexGuard, PoisonError};
+
+// Only signals that no other test sends are raised here, and only while a listener for them is
+// alive: otherwise the default action would terminate the test process.
+
+// Signals are delivered to every listener in the process, so tests running in parallel would
+// receive each other's signals.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serialize() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[test]
+fn receives_raised_signal() {
+    let _serial = serialize();
+    let listener = Listener::new(&[libc::SIGHUP]).unwrap();
+    assert_eq!(listener.try_recv().unwrap(), None);
+    unsafe { libc::raise(libc::SIGHUP) };
+    unsafe { libc::raise(libc::SIGHUP) };
+    assert_eq!(listener.recv().unwrap(), libc::SIGHUP);
+    assert_eq!(listener.try_recv().unwrap(), Some(libc::SIGHUP));
+    assert_eq!(listener.try_recv().unwrap(), None);
+}
+
+#[test]
+fn delivers_to_interested_listeners() {
+    let _serial = serialize();
+    let first = Listener::new(&[libc::SIGHUP, libc::SIGTERM]).unwrap();
+    let second = Listener::new(&[libc::SIGHUP]).unwrap();
+    let third = Listener::new(&[libc::SIGTERM]).unwrap();
+    unsafe { libc::raise(libc::SIGHUP) };
+    assert_eq!(first.recv().unwrap(), libc::SIGHUP);
+    assert_eq!(second.recv().unwrap(), libc::SIGHUP);
+    assert_eq!(third.try_recv().unwrap(), None);
+
+    // Dropping a listener leaves the others working.
+    drop(first);
+    unsafe { libc::raise(libc::SIGTERM) };
+    assert_eq!(third.recv().unwrap(), libc::SIGTERM);
+    assert_eq!(second.try_recv().unwrap(), None);
+}
+
+#[test]
+fn signals_from_other_threads() {
+    let _serial = serialize();
+    let listener = Listener::new(&[libc::SIGHUP]).unwrap();
+    crate::thread::spawn(|| unsafe { libc::kill(libc::getpid(), libc::SIGHUP) }).join().unwrap();
+    assert_eq!(listener.recv().unwrap(), libc::SIGHUP);
+}
@@ -1,15 +1,19 @@
 use crate::cmp;
 use crate::ffi::CStr;
+#[cfg(target_os = "linux")]
+use crate::fmt;
 use crate::io;
 use crate::mem;
 use crate::num::NonZeroUsize;
 use crate::ptr;
+#[cfg(target_os = "linux")]
+use crate::sync::{Mutex, PoisonError};
 use crate::sys::{os, stack_overflow};
 use crate::time::Duration;
 
 #[cfg(all(target_os = "linux", target_env = "gnu"))]
 use crate::sys::weak::dlsym;
-#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+#[cfg(any(target_os = "linux", target_os = "solaris", target_os = "illumos"))]
 use crate::sys::weak::weak;
 #[cfg(not(any(target_os = "l4re", target_os = "vxworks", target_os = "espidf")))]
 pub const DEFAULT_MIN_STACK_SIZE: usize = 2 * 1024 * 1024;
@@ -49,6 +53,24 @@ unsafe impl Sync for Thread {}
 impl Thread {
     // unsafe: see thread::Builder::spawn_unchecked for safety requirements
     pub unsafe fn new(stack: usize, p: Box<dyn FnOnce()>) -> io::Result<Thread> {
+        Thread::new_with_attr(stack, p, |_| Ok(()))
+    }
+
+    // unsafe: see thread::Builder::spawn_unchecked for safety requirements
+    #[cfg(target_os = "linux")]
+    pub unsafe fn new_with_options(
+        stack: usize,
+        options: &SpawnOptions,
+        p: Box<dyn FnOnce()>,
+    ) -> io::Result<Thread> {
+        Thread::new_with_attr(stack, p, |attr| options.apply(attr))
+    }
+
+    unsafe fn new_with_attr(
+        stack: usize,
+        p: Box<dyn FnOnce()>,
+        configure: impl FnOnce(&mut libc::pthread_attr_t) -> io::Result<()>,
+    ) -> io::Result<Thread> {
         let p = Box::into_raw(box p);
         let mut native: libc::pthread_t = mem::zeroed();
         let mut attr: libc::pthread_attr_t = mem::zeroed();
@@ -84,6 +106,13 @@ pub unsafe fn new(stack: usize, p: Box<dyn FnOnce()>) -> io::Result<Thread> {
             };
         }
 
+        if let Err(e) = configure(&mut attr) {
+            assert_eq!(libc::pthread_attr_destroy(&mut attr), 0);
+            // The thread was never created, so p was not consumed.
+            drop(Box::from_raw(p));
+            return Err(e);
+        }
+
         let ret = libc::pthread_create(&mut native, &attr, thread_start, p as *mut _);
         // Note: if the thread creation fails and this assert fails, then p will
         // be leaked. However, an alternative design could cause double-free
@@ -271,6 +300,120 @@ fn drop(&mut self) {
     }
 }
 
+/// Linux-specific settings applied to a thread when it is created, as configured through
+/// `std::os::linux::thread::BuilderExt`.
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+pub struct SpawnOptions {
+    affinity: Option<Vec<usize>>,
+    scheduler: Option<(libc::c_int, libc::c_int)>,
+    // Behind a mutex so that `thread::Builder` stays `Sync` and unwind safe, which it has always
+    // been, without requiring the same of every hook.
+    spawn_hooks: Mutex<Vec<Box<dyn FnOnce() + Send>>>,
+}
+
+#[cfg(target_os = "linux")]
+impl SpawnOptions {
+    pub fn affinity(&mut self, cpus: impl IntoIterator<Item = usize>) {
+        // The mask is only built when spawning, once it is known how many CPUs the kernel
+        // supports, so that a huge index can't make it allocate a huge mask.
+        self.affinity = Some(cpus.into_iter().collect());
+    }
+
+    pub fn scheduler(&mut self, policy: libc::c_int, priority: libc::c_int) {
+        self.scheduler = Some((policy, priority));
+    }
+
+    pub fn spawn_hook(&mut self, hook: Box<dyn FnOnce() + Send>) {
+        self.spawn_hooks.get_mut().unwrap_or_else(PoisonError::into_inner).push(hook);
+    }
+
+    pub fn take_spawn_hooks(&mut self) -> Vec<Box<dyn FnOnce() + Send>> {
+        mem::take(self.spawn_hooks.get_mut().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    unsafe fn apply(&self, attr: &mut libc::pthread_attr_t) -> io::Result<()> {
+        // These are missing from some versions of the libc crate, but are part of POSIX and
+        // provided by both glibc and musl.
+        extern "C" {
+            fn pthread_attr_setinheritsched(
+                attr: *mut libc::pthread_attr_t,
+                inherit: libc::c_int,
+            ) -> libc::c_int;
+            fn pthread_attr_setschedpolicy(
+                attr: *mut libc::pthread_attr_t,
+                policy: libc::c_int,
+            ) -> libc::c_int;
+            fn pthread_attr_setschedparam(
+                attr: *mut libc::pthread_attr_t,
+                param: *const libc::sched_param,
+            ) -> libc::c_int;
+        }
+        const PTHREAD_EXPLICIT_SCHED: libc::c_int = 1;
+
+        fn cvt_pthread(ret: libc::c_int) -> io::Result<()> {
+            if ret == 0 { Ok(()) } else { Err(io::Error::from_raw_os_error(ret)) }
+        }
+
+        if let Some(cpus) = &self.affinity {
+            // glibc-only, so look it up at runtime rather than failing to link on musl.
+            weak! {
+                fn pthread_attr_setaffinity_np(
+                    *mut libc::pthread_attr_t, libc::size_t, *const libc::cpu_set_t
+                ) -> libc::c_int
+            }
+            let Some(setaffinity) = pthread_attr_setaffinity_np.get() else {
+                return Err(io::const_io_error!(
+                    io::ErrorKind::Unsupported,
+                    "setting the CPU affinity of a new thread is not supported on this platform",
+                ));
+            };
+
+            // A CPU index beyond the kernel's own mask can't name a CPU. Never pass a mask smaller
+            // than a `cpu_set_t` either, so that glibc does not reject it.
+            let len = affinity_mask().map_or(0, |mask| mask.len()).max(CPU_SET_WORDS);
+            let mut mask: Vec<libc::c_ulong> = vec![0; len];
+            for &cpu in cpus {
+                let Some(word) = mask.get_mut(cpu / BITS) else {
+                    return Err(io::const_io_error!(
+                        io::ErrorKind::InvalidInput,
+                        "CPU index is larger than the number of CPUs supported by the kernel",
+                    ));
+                };
+                *word |= 1 << (cpu % BITS);
+            }
+            cvt_pthread(setaffinity(
+                attr,
+                mask.len() * mem::size_of::<libc::c_ulong>(),
+                mask.as_ptr().cast(),
+            ))?;
+        }
+
+        if let Some((policy, priority)) = self.scheduler {
+            let param = libc::sched_param { sched_priority: priority };
+            cvt_pthread(pthread_attr_setinheritsched(attr, PTHREAD_EXPLICIT_SCHED))?;
+            cvt_pthread(pthread_attr_setschedpolicy(attr, policy))?;
+            cvt_pthread(pthread_attr_setschedparam(attr, &param))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl fmt::Debug for SpawnOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpawnOptions")
+            .field("affinity", &self.affinity)
+            .field("scheduler", &self.scheduler)
+            .field(
+                "spawn_hooks",
+                &self.spawn_hooks.lock().unwrap_or_else(PoisonError::into_inner).len(),
+            )
+            .finish()
+    }
+}
+
 pub fn available_parallelism() -> io::Result<NonZeroUsize> {
     cfg_if::cfg_if! {
         if #[cfg(any(
@@ -286,14 +429,10 @@ pub fn available_parallelism() -> io::Result<NonZeroUsize> {
             #[cfg(any(target_os = "android", target_os = "linux"))]
             {
                 let quota = cgroups::quota().max(1);
-                let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
-                unsafe {
-                    if libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) == 0 {
-                        let count = libc::CPU_COUNT(&set) as usize;
-                        let count = count.min(quota);
-                        // SAFETY: affinity mask can't be empty and the quota gets clamped to a minimum of 1
-                        return Ok(NonZeroUsize::new_unchecked(count));
-                    }
+                if let Some(count) = affinity_count() {
+                    let count = count.min(quota);
+                    // SAFETY: affinity mask can't be empty and the quota gets clamped to a minimum of 1
+                    return Ok(unsafe { NonZeroUsize::new_unchecked(count) });
                 }
             }
             match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
@@ -379,6 +518,45 @@ pub fn available_parallelism() -> io::Result<NonZeroUsize> {
     }
 }
 
+/// The number of bits in a word of a CPU mask.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const BITS: usize = libc::c_ulong::BITS as usize;
+
+/// The number of words in a `cpu_set_t`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const CPU_SET_WORDS: usize = mem::size_of::<libc::cpu_set_t>() / mem::size_of::<libc::c_ulong>();
+
+/// Returns the number of CPUs in the current thread's affinity mask.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn affinity_count() -> Option<usize> {
+    let mask = affinity_mask()?;
+    Some(mask.iter().map(|word| word.count_ones() as usize).sum())
+}
+
+/// Returns the current thread's affinity mask.
+///
+/// The kernel rejects masks smaller than its own, so unlike `CPU_COUNT` on a fixed-size
+/// `cpu_set_t` this keeps growing the buffer until it fits, which matters on machines with more
+/// than 1024 CPUs or for threads pinned to high CPU numbers. The length of the returned mask
+/// therefore covers every CPU the kernel supports.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn affinity_mask() -> Option<Vec<libc::c_ulong>> {
+    // Far more CPUs than any machine has today.
+    const MAX_MASK_LEN: usize = 1 << 16;
+
+    let mut mask: Vec<libc::c_ulong> = vec![0; CPU_SET_WORDS];
+    loop {
+        let size = mask.len() * mem::size_of::<libc::c_ulong>();
+        if unsafe { libc::sched_getaffinity(0, size, mask.as_mut_ptr().cast()) } == 0 {
+            return Some(mask);
+        }
+        if os::errno() != libc::EINVAL || mask.len() >= MAX_MASK_LEN {
+            return None;
+        }
+        mask.resize(mask.len() * 2, 0);
+    }
+}
+
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod cgroups {
     //! Currently not covered
@@ -209,3 +209,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().finish()
     }
 }
+
+pub fn install_interrupt_handler(_handler: fn()) -> io::Result<()> {
+    unsupported()
+}
@@ -123,6 +123,9 @@
 pub const FILE_FLAG_BACKUP_SEMANTICS: DWORD = 0x02000000;
 pub const SECURITY_SQOS_PRESENT: DWORD = 0x00100000;
 
+pub const LOCKFILE_FAIL_IMMEDIATELY: DWORD = 0x00000001;
+pub const LOCKFILE_EXCLUSIVE_LOCK: DWORD = 0x00000002;
+
 pub const FIONBIO: c_ulong = 0x8004667e;
 
 #[repr(C)]
@@ -208,14 +211,22 @@ fn clone(&self) -> Self {
 pub const SOCK_STREAM: c_int = 1;
 pub const SOCKET_ERROR: c_int = -1;
 pub const SOL_SOCKET: c_int = 0xffff;
+pub const SO_REUSEADDR: c_int = 0x0004;
+pub const SO_KEEPALIVE: c_int = 0x0008;
 pub const SO_LINGER: c_int = 0x0080;
+pub const SO_SNDBUF: c_int = 0x1001;
+pub const SO_RCVBUF: c_int = 0x1002;
 pub const SO_RCVTIMEO: c_int = 0x1006;
 pub const SO_SNDTIMEO: c_int = 0x1005;
 pub const IPPROTO_IP: c_int = 0;
 pub const IPPROTO_TCP: c_int = 6;
 pub const IPPROTO_IPV6: c_int = 41;
 pub const TCP_NODELAY: c_int = 0x0001;
+pub const TCP_KEEPALIVE: c_int = 3;
+pub const TCP_KEEPCNT: c_int = 16;
+pub const TCP_KEEPINTVL: c_int = 17;
 pub const IP_TTL: c_int = 4;
+pub const IPV6_UNICAST_HOPS: c_int = 4;
 pub const IPV6_V6ONLY: c_int = 27;
 pub const SO_ERROR: c_int = 0x1007;
 pub const SO_BROADCAST: c_int = 0x0020;
@@ -768,6 +779,11 @@ pub struct EXCEPTION_POINTERS {
     pub type PVECTORED_EXCEPTION_HANDLER =
         extern "system" fn(ExceptionInfo: *mut EXCEPTION_POINTERS) -> LONG;
 
+    pub const CTRL_C_EVENT: DWORD = 0;
+    pub const CTRL_BREAK_EVENT: DWORD = 1;
+
+    pub type PHANDLER_ROUTINE = unsafe extern "system" fn(CtrlType: DWORD) -> BOOL;
+
     #[repr(C)]
     #[derive(Copy, Clone)]
     pub struct CONSOLE_READCONSOLE_CONTROL {
@@ -844,6 +860,7 @@ pub fn WriteConsoleW(
         ) -> BOOL;
 
         pub fn GetConsoleMode(hConsoleHandle: HANDLE, lpMode: LPDWORD) -> BOOL;
+        pub fn SetConsoleCtrlHandler(HandlerRoutine: PHANDLER_ROUTINE, Add: BOOL) -> BOOL;
         // Allowed but unused by UWP
         pub fn GetFileInformationByHandle(
             hFile: HANDLE,
@@ -1013,6 +1030,21 @@ pub fn SetFilePointerEx(
         dwMoveMethod: DWORD,
     ) -> BOOL;
     pub fn FlushFileBuffers(hFile: HANDLE) -> BOOL;
+    pub fn LockFileEx(
+        hFile: HANDLE,
+        dwFlags: DWORD,
+        dwReserved: DWORD,
+        nNumberOfBytesToLockLow: DWORD,
+        nNumberOfBytesToLockHigh: DWORD,
+        lpOverlapped: LPOVERLAPPED,
+    ) -> BOOL;
+    pub fn UnlockFile(
+        hFile: HANDLE,
+        dwFileOffsetLow: DWORD,
+        dwFileOffsetHigh: DWORD,
+        nNumberOfBytesToUnlockLow: DWORD,
+        nNumberOfBytesToUnlockHigh: DWORD,
+    ) -> BOOL;
     pub fn CreateFileW(
         lpFileName: LPCWSTR,
         dwDesiredAccess: DWORD,
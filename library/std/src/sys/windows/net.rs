@@ -415,6 +415,53 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "SO_REUSEPORT is not supported"))
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "SO_REUSEPORT is not supported"))
+    }
+
+    pub fn set_unicast_hops_v6(&self, hops: u32) -> io::Result<()> {
+        net::setsockopt(self, c::IPPROTO_IPV6, c::IPV6_UNICAST_HOPS, hops as c_int)
+    }
+
+    pub fn unicast_hops_v6(&self) -> io::Result<u32> {
+        let raw: c_int = net::getsockopt(self, c::IPPROTO_IPV6, c::IPV6_UNICAST_HOPS)?;
+        Ok(raw as u32)
+    }
+
+    pub fn set_tclass_v6(&self, _tclass: u32) -> io::Result<()> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "IPV6_TCLASS is not supported"))
+    }
+
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        Err(io::const_io_error!(io::ErrorKind::Unsupported, "IPV6_TCLASS is not supported"))
+    }
+
+    pub fn set_keepalive_params(
+        &self,
+        time: Option<Duration>,
+        interval: Option<Duration>,
+        retries: Option<u32>,
+    ) -> io::Result<()> {
+        // These options are available since Windows 10 version 1709, and are all in whole
+        // seconds.
+        let secs = |dur: Duration| cmp::min(dur.as_secs(), c::DWORD::MAX as u64) as c::DWORD;
+
+        if let Some(time) = time {
+            net::setsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPALIVE, secs(time))?;
+        }
+        if let Some(interval) = interval {
+            net::setsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPINTVL, secs(interval))?;
+        }
+        if let Some(retries) = retries {
+            net::setsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPCNT, retries as c::DWORD)?;
+        }
+        Ok(())
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         let raw: c_int = net::getsockopt(self, c::SOL_SOCKET, c::SO_ERROR)?;
         if raw == 0 { Ok(None) } else { Ok(Some(io::Error::from_raw_os_error(raw as i32))) }
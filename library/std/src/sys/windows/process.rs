@@ -713,6 +713,38 @@ fn from(code: u32) -> Self {
     }
 }
 
+/// Calls `handler` each time Ctrl-C or Ctrl-Break is pressed in the console of the process.
+/// `handler` must not unwind, see `process::on_interrupt`.
+#[cfg(not(target_vendor = "uwp"))]
+pub fn install_interrupt_handler(handler: fn()) -> io::Result<()> {
+    use crate::sync::atomic::{AtomicPtr, Ordering};
+
+    static HANDLER: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+    // The system calls this on a new thread, so `handler` isn't restricted in what it can do.
+    unsafe extern "system" fn ctrl_handler(ctrl_type: c::DWORD) -> c::BOOL {
+        match ctrl_type {
+            c::CTRL_C_EVENT | c::CTRL_BREAK_EVENT => {
+                let handler: fn() = mem::transmute(HANDLER.load(Ordering::Acquire));
+                handler();
+                c::TRUE
+            }
+            _ => c::FALSE,
+        }
+    }
+
+    HANDLER.store(handler as *mut (), Ordering::Release);
+    cvt(unsafe { c::SetConsoleCtrlHandler(ctrl_handler, c::TRUE) }).map(drop)
+}
+
+#[cfg(target_vendor = "uwp")]
+pub fn install_interrupt_handler(_handler: fn()) -> io::Result<()> {
+    Err(io::const_io_error!(
+        io::ErrorKind::Unsupported,
+        "console control handlers are not supported on UWP",
+    ))
+}
+
 fn zeroed_startupinfo() -> c::STARTUPINFO {
     c::STARTUPINFO {
         cb: 0,
@@ -485,6 +485,186 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Unconnected TCP sockets
+////////////////////////////////////////////////////////////////////////////////
+
+pub struct TcpSocket {
+    inner: Socket,
+}
+
+impl TcpSocket {
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        TcpSocket::new(&SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0))
+    }
+
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        TcpSocket::new(&SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0))
+    }
+
+    fn new(family: &SocketAddr) -> io::Result<TcpSocket> {
+        init();
+
+        let sock = Socket::new(family, c::SOCK_STREAM)?;
+        Ok(TcpSocket { inner: sock })
+    }
+
+    pub fn socket(&self) -> &Socket {
+        &self.inner
+    }
+
+    pub fn into_socket(self) -> Socket {
+        self.inner
+    }
+
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (addr, len) = addr.into_inner();
+        cvt(unsafe { c::bind(self.inner.as_raw(), addr.as_ptr(), len as _) })?;
+        Ok(())
+    }
+
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = cmp::min(backlog, c_int::MAX as u32) as c_int;
+        cvt(unsafe { c::listen(self.inner.as_raw(), backlog) })?;
+        Ok(TcpListener { inner: self.inner })
+    }
+
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        let (addr, len) = addr.into_inner();
+        cvt_r(|| unsafe { c::connect(self.inner.as_raw(), addr.as_ptr(), len) })?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn connect_timeout(self, addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.inner.connect_timeout(addr, timeout)?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        sockname(|buf, len| unsafe { c::getsockname(self.inner.as_raw(), buf, len) })
+    }
+
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuse as c_int)
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.inner.set_reuse_port(reuse)
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.inner.reuse_port()
+    }
+
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE, keepalive as c_int)
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_keepalive_params(
+        &self,
+        time: Option<Duration>,
+        interval: Option<Duration>,
+        retries: Option<u32>,
+    ) -> io::Result<()> {
+        self.inner.set_keepalive_params(time, interval, retries)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_SNDBUF)?;
+        Ok(raw as usize)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_RCVBUF)?;
+        Ok(raw as usize)
+    }
+
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.set_nodelay(nodelay)
+    }
+
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.inner.nodelay()
+    }
+
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IP, c::IP_TTL, ttl as c_int)
+    }
+
+    pub fn ttl(&self) -> io::Result<u32> {
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_IP, c::IP_TTL)?;
+        Ok(raw as u32)
+    }
+
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_unicast_hops_v6(&self, hops: u32) -> io::Result<()> {
+        self.inner.set_unicast_hops_v6(hops)
+    }
+
+    pub fn unicast_hops_v6(&self) -> io::Result<u32> {
+        self.inner.unicast_hops_v6()
+    }
+
+    pub fn set_tclass_v6(&self, tclass: u32) -> io::Result<()> {
+        self.inner.set_tclass_v6(tclass)
+    }
+
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        self.inner.tclass_v6()
+    }
+
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.inner.take_error()
+    }
+}
+
+impl FromInner<Socket> for TcpSocket {
+    fn from_inner(socket: Socket) -> TcpSocket {
+        TcpSocket { inner: socket }
+    }
+}
+
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = f.debug_struct("TcpSocket");
+
+        if let Ok(addr) = self.socket_addr() {
+            res.field("addr", &addr);
+        }
+
+        let name = if cfg!(windows) { "socket" } else { "fd" };
+        res.field(name, &self.inner.as_raw()).finish()
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // UDP
 ////////////////////////////////////////////////////////////////////////////////
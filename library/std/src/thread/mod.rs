@@ -174,6 +174,8 @@
 use crate::sys_common::thread;
 use crate::sys_common::thread_info;
 use crate::sys_common::thread_parker::Parker;
+#[cfg(target_os = "linux")]
+use crate::sys_common::AsInnerMut;
 use crate::sys_common::{AsInner, IntoInner};
 use crate::time::Duration;
 
@@ -268,6 +270,9 @@ pub struct Builder {
     name: Option<String>,
     // The size of the stack for the spawned thread in bytes
     stack_size: Option<usize>,
+    // Platform-specific settings, see `std::os::linux::thread::BuilderExt`
+    #[cfg(target_os = "linux")]
+    native: imp::SpawnOptions,
 }
 
 impl Builder {
@@ -291,7 +296,12 @@ impl Builder {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> Builder {
-        Builder { name: None, stack_size: None }
+        Builder {
+            name: None,
+            stack_size: None,
+            #[cfg(target_os = "linux")]
+            native: Default::default(),
+        }
     }
 
     /// Names the thread-to-be. Currently the name is used for identification
@@ -471,8 +481,14 @@ unsafe fn spawn_unchecked_<'a, 'scope, F, T>(
         T: Send + 'a,
         'scope: 'a,
     {
+        #[cfg(target_os = "linux")]
+        let Builder { name, stack_size, mut native } = self;
+        #[cfg(not(target_os = "linux"))]
         let Builder { name, stack_size } = self;
 
+        #[cfg(target_os = "linux")]
+        let spawn_hooks = native.take_spawn_hooks();
+
         let stack_size = stack_size.unwrap_or_else(thread::min_stack);
 
         let my_thread = Thread::new(name.map(|name| {
@@ -502,6 +518,10 @@ unsafe fn spawn_unchecked_<'a, 'scope, F, T>(
             // are properly set and protected from each other.
             thread_info::set(unsafe { imp::guard::current() }, their_thread);
             let try_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                #[cfg(target_os = "linux")]
+                for hook in spawn_hooks {
+                    hook();
+                }
                 crate::sys_common::backtrace::__rust_begin_short_backtrace(f)
             }));
             // SAFETY: `their_packet` as been built just above and moved by the
@@ -530,12 +550,14 @@ unsafe fn spawn_unchecked_<'a, 'scope, F, T>(
             // exist after the thread has terminated, which is signaled by `Thread::join`
             // returning.
             native: unsafe {
-                imp::Thread::new(
-                    stack_size,
-                    mem::transmute::<Box<dyn FnOnce() + 'a>, Box<dyn FnOnce() + 'static>>(
-                        Box::new(main),
-                    ),
-                )?
+                let main = mem::transmute::<Box<dyn FnOnce() + 'a>, Box<dyn FnOnce() + 'static>>(
+                    Box::new(main),
+                );
+                #[cfg(target_os = "linux")]
+                let thread = imp::Thread::new_with_options(stack_size, &native, main)?;
+                #[cfg(not(target_os = "linux"))]
+                let thread = imp::Thread::new(stack_size, main)?;
+                thread
             },
             thread: my_thread,
             packet: my_packet,
@@ -543,6 +565,17 @@ unsafe fn spawn_unchecked_<'a, 'scope, F, T>(
     }
 }
 
+/// Allows extension traits within `std`.
+#[unstable(feature = "sealed", issue = "none")]
+impl crate::sealed::Sealed for Builder {}
+
+#[cfg(target_os = "linux")]
+impl AsInnerMut<imp::SpawnOptions> for Builder {
+    fn as_inner_mut(&mut self) -> &mut imp::SpawnOptions {
+        &mut self.native
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Free functions
 ////////////////////////////////////////////////////////////////////////////////
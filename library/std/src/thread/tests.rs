@@ -43,6 +43,94 @@ fn test_invalid_named_thread() {
     let _ = Builder::new().name("ada l\0velace".to_string()).spawn(|| {});
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn test_spawn_hook() {
+    use crate::os::linux::thread::BuilderExt;
+
+    let (tx, rx) = channel();
+    let tx2 = tx.clone();
+    let tx3 = tx.clone();
+    Builder::new()
+        .name("hooked".to_string())
+        .spawn_hook(move || tx.send(thread::current().name().map(str::to_owned)).unwrap())
+        .spawn_hook(move || tx2.send(None).unwrap())
+        .spawn(move || tx3.send(Some("main".to_string())).unwrap())
+        .unwrap()
+        .join()
+        .unwrap();
+    let received: Vec<_> = rx.try_iter().collect();
+    assert_eq!(received, [Some("hooked".to_string()), None, Some("main".to_string())]);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_spawn_hook_panic() {
+    use crate::os::linux::thread::BuilderExt;
+
+    let ran = Arc::new(AtomicBool::new(false));
+    let ran2 = ran.clone();
+    let result = Builder::new()
+        .spawn_hook(|| panic!("hook failed"))
+        .spawn(move || ran2.store(true, Ordering::Relaxed))
+        .unwrap()
+        .join();
+    assert!(result.is_err());
+    assert!(!ran.load(Ordering::Relaxed));
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_affinity() {
+    use crate::os::linux::thread::BuilderExt;
+
+    // Pick a CPU this process is allowed to run on.
+    let cpu = unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        assert_eq!(libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set), 0);
+        (0..libc::CPU_SETSIZE as usize).find(|&cpu| libc::CPU_ISSET(cpu, &set)).unwrap()
+    };
+    Builder::new()
+        .affinity([cpu])
+        .spawn(move || {
+            assert_eq!(thread::available_parallelism().unwrap().get(), 1);
+            assert_eq!(unsafe { libc::sched_getcpu() }, cpu as libc::c_int);
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn test_affinity_out_of_range() {
+    use crate::io::ErrorKind;
+    use crate::os::linux::thread::BuilderExt;
+
+    // Must fail without trying to allocate a mask large enough to hold the index.
+    let err = Builder::new().affinity([0, usize::MAX]).spawn(|| {}).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_builder_auto_traits() {
+    use crate::panic::{RefUnwindSafe, UnwindSafe};
+
+    fn assert_traits<T: Send + Sync + UnwindSafe + RefUnwindSafe>() {}
+    assert_traits::<Builder>();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_scheduler_invalid_priority() {
+    use crate::os::linux::thread::{BuilderExt, SchedPolicy};
+
+    // Non-real-time policies only accept a priority of 0.
+    let result = Builder::new().scheduler(SchedPolicy::Other, 10).spawn(|| {});
+    assert!(result.is_err());
+    Builder::new().scheduler(SchedPolicy::Other, 0).spawn(|| {}).unwrap().join().unwrap();
+}
+
 #[test]
 fn test_run_basic() {
     let (tx, rx) = channel();
@@ -54,6 +54,7 @@ pub struct ConsoleTestState {
     pub failures: Vec<(TestDesc, Vec<u8>)>,
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub teardown_failures: Vec<String>,
     pub options: Options,
 }
 
@@ -77,6 +78,7 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             failures: Vec::new(),
             not_failures: Vec::new(),
             time_failures: Vec::new(),
+            teardown_failures: Vec::new(),
             options: opts.options,
         })
     }
@@ -132,6 +134,11 @@ pub fn write_log_result(
     fn current_test_count(&self) -> usize {
         self.passed + self.failed + self.ignored + self.measured
     }
+
+    /// Whether all tests passed and all fixtures were torn down.
+    pub(crate) fn is_success(&self) -> bool {
+        self.failed == 0 && self.teardown_failures.is_empty()
+    }
 }
 
 // List the tests to console, and optionally to logfile. Filters are honored.
@@ -237,6 +244,12 @@ fn on_test_event(
         }
         TestEvent::TeWait(ref test) => out.write_test_start(test)?,
         TestEvent::TeTimeout(ref test) => out.write_timeout(test)?,
+        TestEvent::TeTeardownFailed(failures) => {
+            for failure in &failures {
+                st.write_log(|| format!("{failure}\n"))?;
+            }
+            st.teardown_failures.extend(failures);
+        }
         TestEvent::TeResult(completed_test) => {
             let test = &completed_test.desc;
             let result = &completed_test.result;
@@ -33,4 +33,8 @@ pub enum TestEvent {
     TeResult(CompletedTest),
     TeTimeout(TestDesc),
     TeFilteredOut(usize),
+    /// Fixtures set up by the tests panicked while being torn down after all tests have run,
+    /// with a message for every such panic. This isn't the result of a test, but it makes the
+    /// run as a whole fail.
+    TeTeardownFailed(Vec<String>),
 }
@@ -0,0 +1,349 @@
+//! Shared setup and teardown for tests, and parameterized tests.
+//!
+//! A [`Fixture`] is set up once per test binary, the first time a test asks for it, and torn down
+//! after all tests have run. A teardown that panics fails the run as a whole rather than a test:
+//! the console lists it under "fixture teardown failures", the JSON output in the
+//! `teardown_failures` of the final `suite` event, and the JUnit output as an error of the suite.
+//! [`with_fixture`] wraps a single test in its own setup and teardown.
+//! [`parameterized`] turns one test into a test per case, each with a name of its own, so cases
+//! are filtered, shuffled and reported like any other test.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::{self, ThreadId};
+
+use super::types::{DynTestFn, DynTestName, TestDesc, TestDescAndFn, TestFn};
+
+type Teardown = Box<dyn FnOnce() + Send>;
+
+/// Teardowns of the fixtures set up outside of [`run_tests`], such as in a test running in a
+/// subprocess, in the order in which they were set up.
+///
+/// [`run_tests`]: crate::run_tests
+static TEARDOWNS: Mutex<Vec<Teardown>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static CURRENT: RefCell<Option<Teardowns>> = RefCell::new(None);
+}
+
+/// A value shared by all tests of a binary, meant to be stored in a `static`.
+///
+/// The value is set up by the first test that calls [`get`], and every later call returns the
+/// same value. Once all tests have run, the teardown function, if any, is called with it, and the
+/// next call to [`get`] sets up a new value. If setup panics, the test that called [`get`] fails
+/// and the next one tries again.
+///
+/// Tests that call [`get`] while another test sets the fixture up wait for that setup to finish.
+/// A setup function that calls [`get`] on its own fixture panics instead of waiting for itself.
+///
+/// Values are never dropped, since [`get`] hands out `'static` references to them: a fixture
+/// that is set up again after its teardown leaks the previous value. Resources that need to be
+/// released, such as temporary directories or child processes, should therefore be released by
+/// the teardown function rather than by a `Drop` implementation.
+///
+/// When tests run in subprocesses, as they do with `-C panic=abort`, every test sets up its own
+/// copy of the fixture, which is only torn down if the test succeeds: a failed test's process
+/// exits without running teardown functions.
+///
+/// [`get`]: Fixture::get
+///
+/// # Examples
+///
+/// ```
+/// #![feature(test)]
+/// extern crate test;
+/// use test::Fixture;
+///
+/// static NUMBERS: Fixture<Vec<u32>> = Fixture::new(|| (1..=10).collect());
+///
+/// fn sum_is_55() {
+///     assert_eq!(NUMBERS.get().iter().sum::<u32>(), 55);
+/// }
+/// # sum_is_55();
+/// ```
+pub struct Fixture<T: 'static> {
+    state: Mutex<State<T>>,
+    set_up: Condvar,
+    setup: fn() -> T,
+    teardown: Option<fn(&T)>,
+}
+
+enum State<T: 'static> {
+    Empty,
+    /// The given thread runs the setup function, without holding the lock.
+    SettingUp(ThreadId),
+    Ready(&'static T),
+}
+
+impl<T: Send + Sync + 'static> Fixture<T> {
+    /// Creates a fixture that is set up by calling `setup`.
+    pub const fn new(setup: fn() -> T) -> Fixture<T> {
+        Fixture { state: Mutex::new(State::Empty), set_up: Condvar::new(), setup, teardown: None }
+    }
+
+    /// Creates a fixture that is set up by calling `setup` and torn down by calling `teardown`
+    /// after all tests have run.
+    ///
+    /// When tests run in subprocesses, as they do with `-C panic=abort`, `teardown` is only
+    /// called if the test that set the fixture up succeeds.
+    pub const fn with_teardown(setup: fn() -> T, teardown: fn(&T)) -> Fixture<T> {
+        Fixture {
+            state: Mutex::new(State::Empty),
+            set_up: Condvar::new(),
+            setup,
+            teardown: Some(teardown),
+        }
+    }
+
+    /// Returns the value of the fixture, setting it up first if no test has done so yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the setup function panics, or if it is called from the setup function of this
+    /// fixture.
+    pub fn get(&'static self) -> &'static T {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            match *state {
+                State::Ready(value) => return value,
+                State::SettingUp(thread) if thread == thread::current().id() => {
+                    panic!("the setup of a fixture called `Fixture::get` on the same fixture")
+                }
+                State::SettingUp(_) => {
+                    state = self.set_up.wait(state).unwrap_or_else(PoisonError::into_inner)
+                }
+                State::Empty => break,
+            }
+        }
+        *state = State::SettingUp(thread::current().id());
+        drop(state);
+
+        // Lets the next test try again if setup panics.
+        let reset = ResetOnDrop(self);
+        let value: &'static T = Box::leak(Box::new((self.setup)()));
+        mem::forget(reset);
+
+        if let Some(teardown) = self.teardown {
+            register(Box::new(move || {
+                // Forget the value first, so that tests running later set up a new one.
+                *self.state.lock().unwrap_or_else(PoisonError::into_inner) = State::Empty;
+                teardown(value);
+            }));
+        }
+        *self.state.lock().unwrap_or_else(PoisonError::into_inner) = State::Ready(value);
+        self.set_up.notify_all();
+        value
+    }
+}
+
+/// Marks a fixture as not set up when dropped, waking up the tests waiting for its setup.
+struct ResetOnDrop<T: 'static>(&'static Fixture<T>);
+
+impl<T: 'static> Drop for ResetOnDrop<T> {
+    fn drop(&mut self) {
+        *self.0.state.lock().unwrap_or_else(PoisonError::into_inner) = State::Empty;
+        self.0.set_up.notify_all();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Fixture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match *self.state.lock().unwrap_or_else(PoisonError::into_inner) {
+            State::Ready(value) => Some(value),
+            State::Empty | State::SettingUp(_) => None,
+        };
+        f.debug_struct("Fixture").field("value", &value).finish_non_exhaustive()
+    }
+}
+
+/// Registers a teardown with the [`run_tests`] call the current thread runs tests for, or with
+/// the process if there is none.
+///
+/// [`run_tests`]: crate::run_tests
+fn register(teardown: Teardown) {
+    match Teardowns::current() {
+        Some(teardowns) => {
+            teardowns.0.lock().unwrap_or_else(PoisonError::into_inner).push(teardown)
+        }
+        None => TEARDOWNS.lock().unwrap_or_else(PoisonError::into_inner).push(teardown),
+    }
+}
+
+/// Tears down the fixtures set up outside of [`run_tests`], in the reverse order of their setup,
+/// and returns the messages of the teardowns that panicked.
+///
+/// [`run_tests`]: crate::run_tests
+pub(crate) fn run_teardowns() -> Vec<String> {
+    run_all(mem::take(&mut *TEARDOWNS.lock().unwrap_or_else(PoisonError::into_inner)))
+}
+
+fn run_all(teardowns: Vec<Teardown>) -> Vec<String> {
+    teardowns
+        .into_iter()
+        .rev()
+        .filter_map(|teardown| panic::catch_unwind(AssertUnwindSafe(teardown)).err())
+        .map(|payload| {
+            let message = payload
+                .downcast_ref::<String>()
+                .map(|s| &**s)
+                .or_else(|| payload.downcast_ref::<&'static str>().copied())
+                .unwrap_or("Box<dyn Any>");
+            format!("fixture teardown panicked: {message}")
+        })
+        .collect()
+}
+
+/// Teardowns of the fixtures set up by the tests of one [`run_tests`] call, in the order in which
+/// they were set up.
+///
+/// Tests run by several `run_tests` calls at the same time therefore don't tear down each other's
+/// fixtures.
+///
+/// [`run_tests`]: crate::run_tests
+#[derive(Clone, Default)]
+pub(crate) struct Teardowns(Arc<Mutex<Vec<Teardown>>>);
+
+impl Teardowns {
+    /// Returns the teardowns fixtures set up on the current thread register with.
+    pub(crate) fn current() -> Option<Teardowns> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Makes fixtures set up on the current thread register their teardowns here, until the
+    /// returned guard is dropped.
+    pub(crate) fn enter(&self) -> Entered {
+        Entered(CURRENT.with(|current| current.replace(Some(self.clone()))))
+    }
+
+    /// Tears down the fixtures, in the reverse order of their setup, and returns the messages of
+    /// the teardowns that panicked.
+    pub(crate) fn run(&self) -> Vec<String> {
+        run_all(mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner)))
+    }
+}
+
+/// Tears down the fixtures that are left when dropped, such as when [`run_tests`] returns early
+/// because reporting an event failed.
+///
+/// [`run_tests`]: crate::run_tests
+pub(crate) struct TeardownOnDrop(pub(crate) Teardowns);
+
+impl Drop for TeardownOnDrop {
+    fn drop(&mut self) {
+        // The error that made us return early is reported instead.
+        let _ = self.0.run();
+    }
+}
+
+/// Restores the teardowns that were current before [`Teardowns::enter`] when dropped.
+pub(crate) struct Entered(Option<Teardowns>);
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Creates a test that runs `setup`, passes its result to `test` and hands it to `teardown`
+/// afterwards.
+///
+/// `teardown` is called even if `test` panics, in which case the test still fails with the
+/// original panic, even if `teardown` panics too. If `setup` or `teardown` panic, the test fails.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(test)]
+/// extern crate test;
+/// use test::{with_fixture, DynTestName, ShouldPanic, TestDesc, TestDescAndFn, TestType};
+///
+/// let test = TestDescAndFn {
+///     desc: TestDesc {
+///         name: DynTestName("pushes_to_vec".to_string()),
+///         ignore: false,
+///         ignore_message: None,
+///         should_panic: ShouldPanic::No,
+///         compile_fail: false,
+///         no_run: false,
+///         test_type: TestType::UnitTest,
+///     },
+///     testfn: with_fixture(
+///         || vec![1, 2],
+///         |v: &mut Vec<i32>| {
+///             v.push(3);
+///             assert_eq!(v.len(), 3);
+///         },
+///         drop,
+///     ),
+/// };
+/// # drop(test);
+/// ```
+pub fn with_fixture<T, S, F, D>(setup: S, test: F, teardown: D) -> TestFn
+where
+    S: FnOnce() -> T + Send + 'static,
+    F: FnOnce(&mut T) + Send + 'static,
+    D: FnOnce(T) + Send + 'static,
+{
+    DynTestFn(Box::new(move || {
+        let mut value = setup();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| test(&mut value)));
+        let teardown_result = panic::catch_unwind(AssertUnwindSafe(|| teardown(value)));
+        if let Err(payload) = result.and(teardown_result) {
+            panic::resume_unwind(payload);
+        }
+    }))
+}
+
+/// Expands `test` into one test per case.
+///
+/// Every case consists of a label and the argument passed to `test`. The resulting tests are
+/// described by `desc`, except that each is named `<name>::<label>`, where `<name>` is the name
+/// in `desc`. Labels should therefore be unique and, by convention, valid identifiers.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(test)]
+/// extern crate test;
+/// use test::{parameterized, StaticTestName, ShouldPanic, TestDesc, TestType};
+///
+/// let desc = TestDesc {
+///     name: StaticTestName("parses"),
+///     ignore: false,
+///     ignore_message: None,
+///     should_panic: ShouldPanic::No,
+///     compile_fail: false,
+///     no_run: false,
+///     test_type: TestType::UnitTest,
+/// };
+/// let tests = parameterized(desc, [("zero", "0"), ("negative", "-12")], |input: &str| {
+///     assert!(input.parse::<i32>().is_ok());
+/// });
+/// assert_eq!(tests[1].desc.name.as_slice(), "parses::negative");
+/// ```
+pub fn parameterized<L, P, I, F>(desc: TestDesc, cases: I, test: F) -> Vec<TestDescAndFn>
+where
+    L: fmt::Display,
+    P: Send + 'static,
+    I: IntoIterator<Item = (L, P)>,
+    F: Fn(P) + Send + Sync + 'static,
+{
+    let test = Arc::new(test);
+    cases
+        .into_iter()
+        .map(|(label, param)| {
+            let test = test.clone();
+            TestDescAndFn {
+                desc: TestDesc {
+                    name: DynTestName(format!("{}::{}", desc.name, label)),
+                    ..desc.clone()
+                },
+                testfn: DynTestFn(Box::new(move || test(param))),
+            }
+        })
+        .collect()
+}
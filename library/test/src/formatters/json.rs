@@ -173,7 +173,7 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
              \"ignored\": {}, \
              \"measured\": {}, \
              \"filtered_out\": {}",
-            if state.failed == 0 { "ok" } else { "failed" },
+            if state.is_success() { "ok" } else { "failed" },
             state.passed,
             state.failed,
             state.ignored,
@@ -186,9 +186,19 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             self.write_message(&time_str)?;
         }
 
+        if !state.teardown_failures.is_empty() {
+            let failures: Vec<_> = state
+                .teardown_failures
+                .iter()
+                .map(|failure| format!("\"{}\"", EscapedString(failure)))
+                .collect();
+            let failures_str = format!(", \"teardown_failures\": [{}]", failures.join(", "));
+            self.write_message(&failures_str)?;
+        }
+
         self.writeln_message(" }")?;
 
-        Ok(state.failed == 0)
+        Ok(state.is_success())
     }
 }
 
@@ -66,12 +66,15 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
 
         self.write_message(&*format!(
             "<testsuite name=\"test\" package=\"test\" id=\"0\" \
-             errors=\"0\" \
+             errors=\"{}\" \
              failures=\"{}\" \
              tests=\"{}\" \
              skipped=\"{}\" \
              >",
-            state.failed, state.total, state.ignored
+            state.teardown_failures.len(),
+            state.failed,
+            state.total,
+            state.ignored
         ))?;
         for (desc, result, duration) in std::mem::replace(&mut self.results, Vec::new()) {
             let (class_name, test_name) = parse_class_name(&desc);
@@ -133,13 +136,22 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             }
         }
         self.write_message("<system-out/>")?;
-        self.write_message("<system-err/>")?;
+        if state.teardown_failures.is_empty() {
+            self.write_message("<system-err/>")?;
+        } else {
+            // Fixtures are torn down after all tests have run, so their failures are errors of
+            // the suite rather than of one of its tests.
+            self.write_message(&*format!(
+                "<system-err>{}</system-err>",
+                state.teardown_failures.join("\n")
+            ))?;
+        }
         self.write_message("</testsuite>")?;
         self.write_message("</testsuites>")?;
 
         self.out.write_all(b"\n")?;
 
-        Ok(state.failed == 0)
+        Ok(state.is_success())
     }
 }
 
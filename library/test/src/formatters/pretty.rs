@@ -168,6 +168,14 @@ pub fn write_time_failures(&mut self, state: &ConsoleTestState) -> io::Result<()
         self.write_results(&state.time_failures, "failures (time limit exceeded)")
     }
 
+    pub fn write_teardown_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nfixture teardown failures:\n")?;
+        for failure in &state.teardown_failures {
+            self.write_plain(&format!("    {failure}\n"))?;
+        }
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -242,7 +250,7 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_successes(state)?;
         }
-        let success = state.failed == 0;
+        let success = state.is_success();
         if !success {
             if !state.failures.is_empty() {
                 self.write_failures(state)?;
@@ -251,6 +259,10 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             if !state.time_failures.is_empty() {
                 self.write_time_failures(state)?;
             }
+
+            if !state.teardown_failures.is_empty() {
+                self.write_teardown_failures(state)?;
+            }
         }
 
         self.write_plain("\ntest result: ")?;
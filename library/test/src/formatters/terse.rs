@@ -154,6 +154,14 @@ pub fn write_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
         Ok(())
     }
 
+    pub fn write_teardown_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nfixture teardown failures:\n")?;
+        for failure in &state.teardown_failures {
+            self.write_plain(&format!("    {failure}\n"))?;
+        }
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -226,10 +234,13 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_outputs(state)?;
         }
-        let success = state.failed == 0;
-        if !success {
+        let success = state.is_success();
+        if state.failed != 0 {
             self.write_failures(state)?;
         }
+        if !state.teardown_failures.is_empty() {
+            self.write_teardown_failures(state)?;
+        }
 
         self.write_plain("\ntest result: ")?;
 
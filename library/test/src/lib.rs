@@ -24,6 +24,7 @@
 // Public reexports
 pub use self::bench::{black_box, Bencher};
 pub use self::console::run_tests_console;
+pub use self::fixture::{parameterized, with_fixture, Fixture};
 pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
 pub use self::types::TestName::*;
 pub use self::types::*;
@@ -65,6 +66,7 @@ pub mod test {
 mod cli;
 mod console;
 mod event;
+mod fixture;
 mod formatters;
 mod helpers;
 mod options;
@@ -293,6 +295,11 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
         })
     }
 
+    // Fixtures set up by these tests are torn down once they have all run. Tests running on
+    // threads of their own register their teardowns here too, see `run_test`.
+    let teardowns = fixture::TeardownOnDrop(fixture::Teardowns::default());
+    let _entered = teardowns.0.enter();
+
     if concurrency == 1 {
         while !remaining.is_empty() {
             let (id, test) = remaining.pop().unwrap();
@@ -382,6 +389,12 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
             notify_about_test_event(event)?;
         }
     }
+
+    let failures = teardowns.0.run();
+    if !failures.is_empty() {
+        let event = TestEvent::TeTeardownFailed(failures);
+        notify_about_test_event(event)?;
+    }
     Ok(())
 }
 
@@ -513,7 +526,11 @@ fn run_test_inner(
             let cfg = thread::Builder::new().name(name.as_slice().to_owned());
             let mut runtest = Arc::new(Mutex::new(Some(runtest)));
             let runtest2 = runtest.clone();
-            match cfg.spawn(move || runtest2.lock().unwrap().take().unwrap()()) {
+            let teardowns = fixture::Teardowns::current();
+            match cfg.spawn(move || {
+                let _entered = teardowns.as_ref().map(fixture::Teardowns::enter);
+                runtest2.lock().unwrap().take().unwrap()()
+            }) {
                 Ok(handle) => Some(handle),
                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
                     // `ErrorKind::WouldBlock` means hitting the thread limit on some
@@ -683,7 +700,16 @@ fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Sen
         }
 
         if let TrOk = test_result {
-            process::exit(test_result::TR_OK);
+            // Only tear down fixtures after a success, as running arbitrary code from within the
+            // panic hook could deadlock.
+            let failures = fixture::run_teardowns();
+            if failures.is_empty() {
+                process::exit(test_result::TR_OK);
+            }
+            for failure in failures {
+                eprintln!("{failure}");
+            }
+            process::exit(test_result::TR_FAILED);
         } else {
             process::exit(test_result::TR_FAILED);
         }
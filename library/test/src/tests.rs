@@ -821,3 +821,227 @@ fn should_sort_failures_before_printing_them() {
     let bpos = s.find("b").unwrap();
     assert!(apos < bpos);
 }
+
+fn desc_named(name: &'static str) -> TestDesc {
+    TestDesc {
+        name: StaticTestName(name),
+        ignore: false,
+        ignore_message: None,
+        should_panic: ShouldPanic::No,
+        compile_fail: false,
+        no_run: false,
+        test_type: TestType::Unknown,
+    }
+}
+
+// FIXME: Re-enable emscripten once it can catch panics again (introduced by #65251)
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn fixture_torn_down_after_failure() {
+    let (teardown_tx, teardown_rx) = channel();
+    let testfn = with_fixture(
+        || vec![1],
+        |v: &mut Vec<i32>| {
+            v.push(2);
+            panic!("failed");
+        },
+        move |v| teardown_tx.send(v).unwrap(),
+    );
+    let desc = TestDescAndFn { desc: desc_named("whatever"), testfn };
+    let (tx, rx) = channel();
+    run_test(&TestOpts::new(), false, TestId(0), desc, RunStrategy::InProcess, tx, Concurrent::No);
+    assert_ne!(rx.recv().unwrap().result, TrOk);
+    assert_eq!(teardown_rx.recv().unwrap(), [1, 2]);
+}
+
+#[test]
+fn shared_fixture_set_up_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static SETUPS: AtomicUsize = AtomicUsize::new(0);
+    static TEARDOWNS: AtomicUsize = AtomicUsize::new(0);
+    static FIXTURE: Fixture<usize> = Fixture::with_teardown(
+        || SETUPS.fetch_add(1, Ordering::SeqCst) + 42,
+        |_| {
+            TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+        },
+    );
+
+    let tests = ["a", "b", "c"]
+        .into_iter()
+        .map(|name| TestDescAndFn {
+            desc: desc_named(name),
+            testfn: DynTestFn(Box::new(|| {
+                assert_eq!(*FIXTURE.get(), 42);
+                assert_eq!(TEARDOWNS.load(Ordering::SeqCst), 0);
+            })),
+        })
+        .collect();
+    let opts = TestOpts { run_tests: true, test_threads: Some(2), ..TestOpts::new() };
+    let mut passed = 0;
+    run_tests(&opts, tests, |event| {
+        if let TestEvent::TeResult(test) = event {
+            assert_eq!(test.result, TrOk);
+            passed += 1;
+        }
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(passed, 3);
+    assert_eq!(SETUPS.load(Ordering::SeqCst), 1);
+    assert_eq!(TEARDOWNS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn shared_fixture_set_up_again_after_teardown() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static SETUPS: AtomicUsize = AtomicUsize::new(0);
+    static TEARDOWNS: AtomicUsize = AtomicUsize::new(0);
+    static FIXTURE: Fixture<usize> = Fixture::with_teardown(
+        || SETUPS.fetch_add(1, Ordering::SeqCst),
+        |_| {
+            TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+        },
+    );
+
+    for run in 0..2 {
+        let tests = vec![TestDescAndFn {
+            desc: desc_named("whatever"),
+            testfn: DynTestFn(Box::new(move || {
+                assert_eq!(*FIXTURE.get(), run);
+                // Another run in the same process must not tear down this run's fixture.
+                let other =
+                    TestDescAndFn { desc: desc_named("other"), testfn: DynTestFn(Box::new(|| {})) };
+                let opts = TestOpts { run_tests: true, ..TestOpts::new() };
+                thread::spawn(move || run_tests(&opts, vec![other], |_| Ok(())).unwrap())
+                    .join()
+                    .unwrap();
+                assert_eq!(TEARDOWNS.load(Ordering::SeqCst), run);
+            })),
+        }];
+        let opts = TestOpts { run_tests: true, ..TestOpts::new() };
+        run_tests(&opts, tests, |event| {
+            if let TestEvent::TeResult(test) = event {
+                assert_eq!(test.result, TrOk);
+            }
+            Ok(())
+        })
+        .unwrap();
+    }
+    assert_eq!(SETUPS.load(Ordering::SeqCst), 2);
+    assert_eq!(TEARDOWNS.load(Ordering::SeqCst), 2);
+}
+
+// FIXME: Re-enable emscripten once it can catch panics again (introduced by #65251)
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn shared_fixture_teardown_panic_is_reported() {
+    static FIXTURE: Fixture<()> = Fixture::with_teardown(|| (), |_| panic!("teardown failed"));
+
+    let tests = vec![TestDescAndFn {
+        desc: desc_named("whatever"),
+        testfn: DynTestFn(Box::new(|| {
+            FIXTURE.get();
+        })),
+    }];
+    let opts = TestOpts { run_tests: true, ..TestOpts::new() };
+    let mut results = Vec::new();
+    let mut teardown_failures = Vec::new();
+    run_tests(&opts, tests, |event| {
+        match event {
+            TestEvent::TeResult(test) => results.push((test.desc.name.to_string(), test.result)),
+            TestEvent::TeTeardownFailed(failures) => teardown_failures.extend(failures),
+            _ => {}
+        }
+        Ok(())
+    })
+    .unwrap();
+    // The failed teardown isn't reported as a test of its own.
+    assert_eq!(results, [("whatever".to_string(), TrOk)]);
+    assert_eq!(teardown_failures.len(), 1);
+    assert!(teardown_failures[0].contains("teardown failed"));
+}
+
+// FIXME: Re-enable emscripten once it can catch panics again (introduced by #65251)
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn shared_fixture_recursive_setup_panics() {
+    static FIXTURE: Fixture<u32> = Fixture::new(|| *FIXTURE.get() + 1);
+
+    let result = panic::catch_unwind(|| FIXTURE.get());
+    assert!(result.is_err());
+    // The failed setup doesn't leave the fixture in the middle of being set up.
+    assert!(panic::catch_unwind(|| FIXTURE.get()).is_err());
+}
+
+// FIXME: Re-enable emscripten once it can catch panics again (introduced by #65251)
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn fixture_teardown_panic_keeps_test_panic() {
+    let testfn = with_fixture(|| (), |_: &mut ()| panic!("test failed"), |_| panic!("teardown"));
+    let mut desc = desc_named("whatever");
+    desc.should_panic = ShouldPanic::YesWithMessage("test failed");
+    let desc = TestDescAndFn { desc, testfn };
+    let (tx, rx) = channel();
+    run_test(&TestOpts::new(), false, TestId(0), desc, RunStrategy::InProcess, tx, Concurrent::No);
+    assert_eq!(rx.recv().unwrap().result, TrOk);
+}
+
+#[test]
+fn shared_fixture_torn_down_when_reporting_fails() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static TEARDOWNS: AtomicUsize = AtomicUsize::new(0);
+    static FIXTURE: Fixture<()> = Fixture::with_teardown(
+        || (),
+        |_| {
+            TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+        },
+    );
+
+    let tests = vec![TestDescAndFn {
+        desc: desc_named("whatever"),
+        testfn: DynTestFn(Box::new(|| {
+            FIXTURE.get();
+        })),
+    }];
+    let opts = TestOpts { run_tests: true, ..TestOpts::new() };
+    let result = run_tests(&opts, tests, |event| match event {
+        TestEvent::TeResult(_) => Err(io::Error::new(io::ErrorKind::Other, "broken pipe")),
+        _ => Ok(()),
+    });
+    assert!(result.is_err());
+    assert_eq!(TEARDOWNS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn parameterized_cases_are_separate_tests() {
+    let tests = || {
+        parameterized(desc_named("square"), [("two", 2), ("three", 3), ("twelve", 12)], |n: u32| {
+            assert_eq!(n * n / n, n);
+        })
+    };
+
+    let names: Vec<_> = tests().iter().map(|t| t.desc.name.to_string()).collect();
+    assert_eq!(names, ["square::two", "square::three", "square::twelve"]);
+
+    let filtered =
+        filter_tests(&TestOpts { filters: vec!["::tw".into()], ..TestOpts::new() }, tests());
+    let names: Vec<_> = filtered.iter().map(|t| t.desc.name.to_string()).collect();
+    assert_eq!(names, ["square::twelve", "square::two"]);
+
+    for test in tests() {
+        let (tx, rx) = channel();
+        run_test(
+            &TestOpts::new(),
+            false,
+            TestId(0),
+            test,
+            RunStrategy::InProcess,
+            tx,
+            Concurrent::No,
+        );
+        assert_eq!(rx.recv().unwrap().result, TrOk);
+    }
+}
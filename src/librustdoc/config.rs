@@ -344,6 +344,13 @@ pub(crate) fn from_matches(
         let config::JsonConfig { json_rendered, json_unused_externs, .. } =
             config::parse_json(matches);
         let error_format = config::parse_error_format(matches, color, json_rendered);
+        if error_format == ErrorOutputType::Sarif {
+            // rustdoc creates several diagnostic handlers, each of which would write its own log.
+            rustc_session::early_error(
+                ErrorOutputType::default(),
+                "`--error-format=sarif` is not supported by rustdoc",
+            );
+        }
         let diagnostic_width = matches.opt_get("diagnostic-width").unwrap_or_default();
 
         let codegen_options = CodegenOptions::build(matches, error_format);
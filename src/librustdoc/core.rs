@@ -196,6 +196,7 @@ pub(crate) fn new_handler(
                 .ui_testing(unstable_opts.ui_testing),
             )
         }
+        ErrorOutputType::Sarif => unreachable!("rejected when parsing the options"),
     };
 
     rustc_errors::Handler::with_emitter_and_flags(
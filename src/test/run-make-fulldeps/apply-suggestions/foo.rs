@@ -0,0 +1,5 @@
+pub fn foo() -> i32 {
+    let mut a = 1;
+    let b = (a + 1);
+    b
+}
@@ -0,0 +1,23 @@
+pub const TABLE: [u32; 16] = {
+    let mut table = [0; 16];
+    let mut i = 0;
+    while i < 16 {
+        table[i] = crc32(i as u8);
+        i += 1;
+    }
+    table
+};
+
+const fn crc32(byte: u8) -> u32 {
+    let mut crc = byte as u32;
+    let mut bit = 0;
+    while bit < 8 {
+        crc = crc32_step(crc);
+        bit += 1;
+    }
+    crc
+}
+
+const fn crc32_step(crc: u32) -> u32 {
+    if crc & 1 == 1 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 }
+}
@@ -0,0 +1,18 @@
+#![feature(const_eval_limit)]
+#![const_eval_limit = "100"]
+
+pub const X: u32 = crc32(0xff);
+
+const fn crc32(byte: u8) -> u32 {
+    let mut crc = byte as u32;
+    let mut bit = 0;
+    while bit < 1000 {
+        crc = crc32_step(crc);
+        bit += 1;
+    }
+    crc
+}
+
+const fn crc32_step(crc: u32) -> u32 {
+    if crc & 1 == 1 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 }
+}
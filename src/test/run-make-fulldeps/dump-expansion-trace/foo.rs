@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate mac;
+
+macro_rules! make_struct {
+    ($name:ident) => {
+        #[derive(Clone, Named)]
+        struct $name {
+            x: i32,
+        }
+    };
+}
+
+make_struct!(Point);
+
+#[identity]
+fn main() {
+    let p = Point { x: 1 };
+    let _ = p.clone();
+    assert_eq!(name(), "Point");
+}
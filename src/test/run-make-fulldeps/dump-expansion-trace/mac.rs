@@ -0,0 +1,15 @@
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+#[proc_macro_derive(Named)]
+pub fn derive_named(_input: TokenStream) -> TokenStream {
+    "fn name() -> &'static str { \"Point\" }".parse().unwrap()
+}
+
+#[proc_macro_attribute]
+pub fn identity(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
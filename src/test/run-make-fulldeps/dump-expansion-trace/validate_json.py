@@ -0,0 +1,46 @@
+#!/usr/bin/env python
+
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    trace = json.load(f)
+
+assert trace["crate"] == "foo"
+expansions = trace["expansions"]
+by_macro = {}
+for expansion in expansions:
+    by_macro.setdefault(expansion["macro"], []).append(expansion)
+ids = {expansion["id"]: expansion for expansion in expansions}
+
+[make_struct] = by_macro["make_struct"]
+assert make_struct["kind"] == "bang"
+assert make_struct["expander"] == "declarative"
+assert make_struct["parent"] is None
+assert make_struct["input"] == "Point"
+assert "struct Point" in make_struct["output"]
+assert make_struct["time_ns"] >= make_struct["self_time_ns"]
+
+# Derives on the item produced by `make_struct!` are nested in its expansion.
+[clone] = by_macro["Clone"]
+assert clone["kind"] == "derive"
+assert clone["expander"] == "builtin"
+[named] = by_macro["Named"]
+assert named["expander"] == "proc_macro"
+assert "fn name() -> &'static str" in named["output"]
+for derive in [clone, named]:
+    parent = ids[derive["parent"]]
+    assert parent["macro"] == "derive"
+    assert parent["parent"] == make_struct["id"]
+
+[identity] = by_macro["identity"]
+assert identity["kind"] == "attr"
+assert identity["input"].startswith("#[identity]\nfn main()")
+assert identity["output"].startswith("fn main()")
+
+# `assert_eq!` is expanded from the output of `#[identity]`.
+[assert_eq] = by_macro["assert_eq"]
+assert assert_eq["parent"] == identity["id"]
+assert assert_eq["depth"] == identity["depth"] + 1
+
+assert {m["macro"] for m in trace["macros"]} >= {"make_struct", "Clone", "Named", "identity"}
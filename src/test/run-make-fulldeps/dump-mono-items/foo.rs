@@ -0,0 +1,12 @@
+fn generic<T: Default>() -> T {
+    leaf()
+}
+
+#[inline]
+fn leaf<T: Default>() -> T {
+    T::default()
+}
+
+fn main() {
+    let _: u32 = generic();
+}
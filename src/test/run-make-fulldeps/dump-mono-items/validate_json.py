@@ -0,0 +1,27 @@
+#!/usr/bin/env python
+
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    dump = json.load(f)
+
+assert dump["crate"] == "foo"
+assert len(dump["codegen_units"]) > 0
+
+items = {item["name"]: item for item in dump["mono_items"]}
+
+main = items["fn foo::main"]
+assert main["is_root"]
+assert main["instantiation_chain"] == ["fn foo::main"]
+assert len(main["placements"]) == 1
+
+generic = items["fn foo::generic::<u32>"]
+assert not generic["is_root"]
+assert generic["instantiation_chain"] == ["fn foo::main", "fn foo::generic::<u32>"]
+assert generic["referenced_by"] == [{"item": "fn foo::main", "inlined": False}]
+
+leaf = items["fn foo::leaf::<u32>"]
+assert leaf["instantiation_mode"] == "local_copy"
+assert leaf["instantiation_chain"][-2] == "fn foo::generic::<u32>"
+assert {"item": "fn foo::generic::<u32>", "inlined": True} in leaf["referenced_by"]
@@ -0,0 +1,14 @@
+trait Speak {}
+
+struct Dog;
+struct Wrapper<T>(T);
+
+impl Speak for Dog {}
+impl<T: Speak> Speak for Wrapper<T> {}
+impl Speak for Box<Dog> {}
+
+fn needs_speak<T: Speak>(_: T) {}
+
+fn main() {
+    needs_speak(Wrapper(Box::new(0u8)));
+}
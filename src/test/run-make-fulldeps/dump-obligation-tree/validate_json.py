@@ -0,0 +1,26 @@
+#!/usr/bin/env python
+
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    dump = json.load(f)
+
+assert dump["item"] == "main"
+assert "Box<u8>: " in dump["failed_obligation"]
+
+# The tree starts at the bound of `needs_speak`, the failing bound is nested in it.
+root = dump["tree"]
+assert "Wrapper<std::boxed::Box<u8>>: " in root["predicate"]
+assert root["result"] == "fails"
+
+[wrapper_impl] = [c for c in root["candidates"] if c["kind"] == "impl"]
+assert "Speak for Wrapper<T>\n  where T: " in wrapper_impl["description"]
+[inner] = [o for o in wrapper_impl["nested"] if o["result"] == "fails"]
+assert "std::boxed::Box<u8>: " in inner["predicate"]
+
+# The impl for `Box<Dog>` was considered, but its header doesn't match.
+[box_impl] = inner["candidates"]
+assert "Speak for std::boxed::Box<Dog>" in box_impl["description"]
+assert box_impl["result"] == "fails"
+assert "does not match" in box_impl["rejection"]
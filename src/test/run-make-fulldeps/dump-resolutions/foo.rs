@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+
+mod inner {
+    pub mod deep {
+        pub fn helper() {}
+        pub struct Hidden;
+    }
+
+    pub use self::deep::helper;
+    pub(crate) fn internal() {}
+}
+
+pub mod api {
+    pub use crate::inner::helper as run;
+    use crate::inner::*;
+    use std::collections::HashMap;
+
+    macro_rules! import_internal {
+        () => {
+            use crate::inner::internal;
+        };
+    }
+    import_internal!();
+
+    pub fn call() {
+        internal();
+        deep::helper();
+    }
+}
@@ -0,0 +1,44 @@
+#!/usr/bin/env python
+
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    dump = json.load(f)
+
+assert dump["crate"] == "foo"
+modules = {m["path"]: m for m in dump["modules"]}
+imports = {i["path"]: i for i in modules["crate::api"]["imports"]}
+
+# A re-export of a re-export resolves through the `use` in `inner`.
+run = imports["crate::inner::helper as run"]
+assert run["kind"] == "single"
+assert run["visibility"] == "pub"
+resolution = run["resolutions"]["value"]
+assert resolution["path"] == "crate::inner::deep::helper"
+assert [t["module"] for t in resolution["through"]] == ["crate::inner"]
+
+# The glob import is used for `deep` only, but brings everything public into scope.
+glob = imports["crate::inner::*"]
+assert glob["kind"] == "glob"
+assert glob["module"] == "crate::inner"
+assert glob["used_names"] == ["deep"]
+assert {n["name"] for n in glob["names"]} >= {"deep", "helper", "internal"}
+
+unused = imports["std::collections::HashMap"]
+assert unused["status"] == "unused"
+assert unused["visibility"] == "private"
+assert unused["resolutions"]["type"]["path"] == "std::collections::hash::map::HashMap"
+
+# The import written by `import_internal!` is listed in the module it was expanded into.
+internal = imports["crate::inner::internal"]
+assert internal["from_macro"]
+assert internal["status"] == "used"
+
+items = {i["path"]: i for i in dump["items"]}
+assert items["crate::inner::internal"]["visibility"] == "pub(crate)"
+assert items["crate::inner::internal"]["effective_visibility"] is None
+assert items["crate::inner::deep::helper"]["effective_visibility"] == "exported"
+assert items["crate::inner::deep::Hidden"]["visibility"] == "pub"
+assert items["crate::inner::deep::Hidden"]["effective_visibility"] is None
+assert items["crate::api::call"]["effective_visibility"] == "public"
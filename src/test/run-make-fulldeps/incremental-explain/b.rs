@@ -0,0 +1,11 @@
+fn unchanged(x: u32) -> u32 {
+    x * 2
+}
+
+fn edited() -> u32 {
+    2
+}
+
+fn main() {
+    println!("{}", unchanged(edited()));
+}
@@ -0,0 +1,28 @@
+#!/usr/bin/env python
+
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    report = json.load(f)
+
+assert report["crate"] == "foo"
+assert report["option_hashes"]["-C debug-assertions"] is not None
+
+if sys.argv[2] == "edit":
+    assert report["previous_session"]["loaded"]
+    assert report["changed_options"] == []
+    assert report["summary"]["green"] > 0
+    assert report["summary"]["reexecuted"] > 0
+
+    # Only the edited function changed, so everything that was re-executed
+    # is attributed to it.
+    causes = [cause["node"] for cause in report["root_causes"]]
+    assert any("edited" in cause for cause in causes), causes
+    assert not any("unchanged" in cause for cause in causes), causes
+    assert [f["file"].endswith("foo.rs") for f in report["source_files"]] == [True]
+else:
+    # Changing a tracked option discards the previous session entirely.
+    assert not report["previous_session"]["loaded"]
+    assert report["changed_options"] == ["-C debug-assertions"]
+    assert report["summary"]["reexecuted"] == 0
@@ -0,0 +1,9 @@
+/// Some documentation that moves the existing warnings down.
+pub fn foo() {
+    let a = 1;
+    let b = 2;
+}
+
+pub fn bar() {
+    let c = 3;
+}
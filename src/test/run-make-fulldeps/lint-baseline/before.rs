@@ -0,0 +1,4 @@
+pub fn foo() {
+    let a = 1;
+    let b = 2;
+}
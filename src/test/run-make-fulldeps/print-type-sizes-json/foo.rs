@@ -0,0 +1,15 @@
+pub struct Padded {
+    pub a: u8,
+    pub b: u32,
+}
+
+pub enum Niche {
+    A,
+    B(&'static u8),
+}
+
+pub fn sizes() -> (Padded, Niche) {
+    let padded = Padded { a: 0, b: 1 };
+    let niche = Niche::B(&0);
+    (padded, niche)
+}
@@ -0,0 +1,23 @@
+#!/usr/bin/env python
+
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    types = {t["type"]: t for t in json.load(f)["types"]}
+
+padded = types["Padded"]
+assert padded["kind"] == "struct"
+assert padded["size"] == 8
+assert padded["span"]["file"].endswith("foo.rs")
+assert padded["span"]["line_start"] == 1
+fields = padded["variants"][0]["fields"]
+assert [field["name"] for field in fields] == ["b", "a"]
+assert fields[0]["padding_before"] == 0
+assert padded["end_padding"] == 3
+
+niche = types["Niche"]
+assert niche["kind"] == "enum"
+assert niche["discriminant_size"] is None
+assert sorted(variant["name"] for variant in niche["variants"]) == ["A", "B"]
+assert niche["niche"] is not None
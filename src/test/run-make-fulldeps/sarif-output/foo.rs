@@ -0,0 +1,5 @@
+pub fn foo() -> u32 {
+    let x = 1;
+    let y: u32 = "2";
+    y
+}
@@ -0,0 +1,39 @@
+#!/usr/bin/env python
+
+import json
+import sys
+
+with open(sys.argv[1]) as f:
+    log = json.load(f)
+
+assert log["version"] == "2.1.0"
+assert len(log["runs"]) == 1
+run = log["runs"][0]
+assert run["automationDetails"]["id"] == "foo/"
+assert run["tool"]["driver"]["name"] == "rustc"
+assert not run["invocations"][0]["executionSuccessful"]
+
+rules = {rule["id"]: rule for rule in run["tool"]["driver"]["rules"]}
+assert "unused_variables" in rules
+assert "E0308" in rules["E0308"]["fullDescription"]["text"]
+
+results = {result["ruleId"]: result for result in run["results"]}
+
+unused = results["unused_variables"]
+assert unused["level"] == "warning"
+location = unused["locations"][0]["physicalLocation"]
+assert location["artifactLocation"]["uri"] == "foo.rs"
+assert location["region"]["startLine"] == 2
+assert location["region"]["startColumn"] == 9
+fix = unused["fixes"][0]
+assert fix["properties"]["applicability"] == "MachineApplicable"
+assert fix["artifactChanges"][0]["replacements"][0]["insertedContent"]["text"] == "_x"
+
+mismatch = results["E0308"]
+assert mismatch["level"] == "error"
+assert mismatch["locations"][0]["physicalLocation"]["region"]["startLine"] == 3
+assert len(mismatch["relatedLocations"]) > 0
+
+# The summary at the end of the compilation has no span.
+notifications = run["invocations"][0]["toolExecutionNotifications"]
+assert any("aborting" in n["message"]["text"] for n in notifications)
@@ -0,0 +1,24 @@
+#[no_mangle]
+pub fn big_entry() -> u8 {
+    big_frame(1)
+}
+
+#[inline(never)]
+fn big_frame(value: u8) -> u8 {
+    let buffer = [value; 4096];
+    unsafe { core::ptr::read_volatile(&buffer as *const [u8; 4096] as *const u8) }
+}
+
+#[no_mangle]
+pub fn recursive_entry(n: u32) -> u32 {
+    recurse(n)
+}
+
+fn recurse(n: u32) -> u32 {
+    if n == 0 { 0 } else { recurse(n - 1) }
+}
+
+#[no_mangle]
+pub fn indirect_entry(f: fn() -> u32) -> u32 {
+    f()
+}
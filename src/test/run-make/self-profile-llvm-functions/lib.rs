@@ -0,0 +1,11 @@
+#![crate_type = "lib"]
+
+#[inline(never)]
+fn generic<T: Copy + Into<u64>>(values: &[T]) -> u64 {
+    values.iter().map(|&v| v.into()).sum()
+}
+
+#[no_mangle]
+pub fn profiled_entry_point(a: &[u32], b: &[u8]) -> u64 {
+    generic(a) + generic(b)
+}
@@ -0,0 +1,203 @@
+// run-pass
+// Test that users are able to use stable mir APIs to retrieve information of the current crate
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::{rustc_internal, stable_mir};
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    // Get the local crate using stable_mir API.
+    let local = stable_mir::local_crate();
+    assert_eq!(&local.name, CRATE_NAME);
+    assert!(local.is_local);
+
+    assert_eq!(stable_mir::entry_fn(), None);
+
+    // Find items in the local crate.
+    let items = stable_mir::all_local_items();
+    assert!(get_item(tcx, &items, (DefKind::Fn, "foo_bar")).is_some());
+
+    // Find the `std` crate.
+    assert!(stable_mir::find_crate("std").is_some());
+    assert!(stable_mir::external_crates().iter().any(|krate| krate.name == "core"));
+
+    let bar = get_item(tcx, &items, (DefKind::Fn, "bar")).unwrap();
+    assert_eq!(bar.name(), "bar");
+    assert_eq!(bar.span().lo_line, 1);
+    let body = bar.body();
+    assert_eq!(body.arg_count, 1);
+    let block = &body.blocks[0];
+    match &block.statements[0] {
+        stable_mir::mir::Statement::Assign(place, stable_mir::mir::Rvalue::Use(_)) => {
+            assert_eq!(place.local, 0);
+        }
+        other => panic!("{:?}", other),
+    }
+    match &block.terminator {
+        stable_mir::mir::Terminator::Return => {}
+        other => panic!("{:?}", other),
+    }
+    match body.locals[1].kind() {
+        stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Ref(inner, _)) => {
+            match inner.kind() {
+                stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Slice(_)) => {}
+                other => panic!("{:?}", other),
+            }
+        }
+        other => panic!("{:?}", other),
+    }
+
+    // `foo_bar` calls `foo` twice, and `wrapping_add` once.
+    let foo_bar = get_item(tcx, &items, (DefKind::Fn, "foo_bar")).unwrap();
+    let body = foo_bar.body();
+    assert_eq!(body.arg_count, 2);
+    let mut callees = Vec::new();
+    for block in &body.blocks {
+        if let stable_mir::mir::Terminator::Call { func, target, .. } = &block.terminator {
+            assert!(block.terminator.successors().contains(&target.unwrap()));
+            callees.push(callee_name(tcx, func));
+        }
+    }
+    assert_eq!(callees.iter().filter(|name| *name == "foo").count(), 2);
+    assert!(callees.iter().any(|name| name.ends_with("wrapping_add")));
+
+    // Constants and statics have bodies too, which take no arguments.
+    let limit = get_item(tcx, &items, (DefKind::Const, "LIMIT")).unwrap();
+    let body = limit.body();
+    assert_eq!(body.arg_count, 0);
+    match body.locals[0].kind() {
+        stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Uint(
+            stable_mir::ty::UintTy::U32,
+        )) => {}
+        other => panic!("{:?}", other),
+    }
+    let names = items
+        .iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            matches!(tcx.def_kind(def_id), DefKind::Static(_)) && item.name() == "NAMES"
+        })
+        .unwrap();
+    let body = names.body();
+    assert_eq!(body.arg_count, 0);
+    match body.locals[0].kind() {
+        stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Array(_, Some(2))) => {}
+        other => panic!("{:?}", other),
+    }
+
+    // Every item has a body, and every successor of every block must be a valid block index.
+    for item in &items {
+        let body = item.body();
+        for block in &body.blocks {
+            for succ in block.terminator.successors() {
+                assert!(succ < body.blocks.len());
+            }
+        }
+    }
+}
+
+// Use internal API to find the path of the function called through `func`.
+fn callee_name(tcx: TyCtxt<'_>, func: &stable_mir::mir::Operand) -> String {
+    let constant = match func {
+        stable_mir::mir::Operand::Constant(constant) => constant,
+        other => panic!("{:?}", other),
+    };
+    match constant.ty.kind() {
+        stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::FnDef(def, _)) => {
+            tcx.def_path_str(rustc_internal::fn_def_id(&def))
+        }
+        other => panic!("{:?}", other),
+    }
+}
+
+// Use internal API to find a function in a crate.
+fn get_item<'a>(
+    tcx: TyCtxt,
+    items: &'a stable_mir::CrateItems,
+    item: (DefKind, &str),
+) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| {
+        let def_id = rustc_internal::item_def_id(crate_item);
+        tcx.def_kind(def_id) == item.0 && tcx.def_path_str(def_id) == item.1
+    })
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"pub fn bar(x: &[u8]) -> &[u8] {{ x }}
+
+    pub fn foo_bar(x: i32, y: i32) -> i64 {{
+        let x_64 = foo(x);
+        let y_64 = foo(y);
+        x_64.wrapping_add(y_64)
+    }}
+
+    fn foo(x: i32) -> i64 {{
+        x as i64
+    }}
+
+    pub const LIMIT: u32 = 3 * 7;
+
+    pub static NAMES: [&str; 2] = ["a", "b"];"#
+    )?;
+    Ok(())
+}
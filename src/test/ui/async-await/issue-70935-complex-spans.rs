@@ -9,9 +9,9 @@
 async fn baz<T>(_c: impl FnMut() -> T) where T: Future<Output=()> {
 }
 
-fn foo(tx: std::sync::mpsc::Sender<i32>) -> impl Future + Send {
+fn foo(tx: std::cell::Cell<i32>) -> impl Future + Send {
     //[normal]~^ ERROR future cannot be sent between threads safely
-    //[drop_tracking]~^^ ERROR `Sender<i32>` cannot be shared between threads
+    //[drop_tracking]~^^ ERROR `Cell<i32>` cannot be shared between threads
     async move {
         baz(|| async{
             foo(tx.clone());
@@ -23,6 +23,6 @@ fn bar(_s: impl Future + Send) {
 }
 
 fn main() {
-    let (tx, _rx) = std::sync::mpsc::channel();
+    let tx = std::cell::Cell::new(0);
     bar(foo(tx));
 }
@@ -0,0 +1,72 @@
+// run-pass
+// ignore-emscripten no processes
+// ignore-sgx no processes
+// ignore-wasm32 no processes
+
+// Checks that advisory file locks taken through `File::lock` and friends are
+// observed by other processes.
+
+#![feature(file_lock)]
+
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::path::PathBuf;
+use std::process::Command;
+
+fn lock_path() -> PathBuf {
+    env::current_exe().unwrap().with_extension("lock")
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() >= 2 {
+        child(&args[1]);
+        return;
+    }
+
+    let path = lock_path();
+    let file = File::create(&path).unwrap();
+
+    // While we hold an exclusive lock, the child can take no lock at all.
+    file.lock().unwrap();
+    run_child(&args[0], "exclusive");
+    file.unlock().unwrap();
+
+    // While we hold a shared lock, the child can only take another shared lock.
+    file.lock_shared().unwrap();
+    run_child(&args[0], "shared");
+    file.unlock().unwrap();
+
+    // Once released, the child can take any lock.
+    run_child(&args[0], "unlocked");
+
+    drop(file);
+    std::fs::remove_file(&path).unwrap();
+}
+
+fn run_child(me: &str, mode: &str) {
+    let status = Command::new(me).arg(mode).status().unwrap();
+    assert!(status.success(), "child failed in `{}` mode: {}", mode, status);
+}
+
+fn child(mode: &str) {
+    let file = OpenOptions::new().write(true).open(lock_path()).unwrap();
+    match mode {
+        "exclusive" => {
+            assert!(!file.try_lock().unwrap());
+            assert!(!file.try_lock_shared().unwrap());
+        }
+        "shared" => {
+            assert!(!file.try_lock().unwrap());
+            assert!(file.try_lock_shared().unwrap());
+            file.unlock().unwrap();
+        }
+        "unlocked" => {
+            assert!(file.try_lock().unwrap());
+            file.unlock().unwrap();
+            assert!(file.try_lock_shared().unwrap());
+            file.unlock().unwrap();
+        }
+        _ => panic!("unknown mode `{}`", mode),
+    }
+}